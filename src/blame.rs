@@ -0,0 +1,365 @@
+//! Decision context for source lines
+//!
+//! Maps `git blame` output onto decision graph nodes: each line's commit is
+//! matched against `metadata_json.commit`, and the matching node is resolved
+//! to the action, decision and goal it belongs to.
+
+use crate::db::{DecisionEdge, DecisionNode};
+use crate::tui::state::find_root_goal;
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+use std::process::Command;
+
+/// Shortest commit prefix accepted when matching node commits to blame commits
+const MIN_COMMIT_PREFIX: usize = 7;
+
+/// A single line from `git blame`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    /// 1-based line number in the current file
+    pub line: usize,
+    /// Full commit hash (all zeros for uncommitted lines)
+    pub commit: String,
+    /// Commit summary line
+    pub summary: String,
+    pub content: String,
+}
+
+/// Minimal node reference for annotations
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeRef {
+    pub id: i32,
+    pub node_type: String,
+    pub title: String,
+}
+
+impl From<&DecisionNode> for NodeRef {
+    fn from(node: &DecisionNode) -> Self {
+        Self {
+            id: node.id,
+            node_type: node.node_type.clone(),
+            title: node.title.clone(),
+        }
+    }
+}
+
+/// A run of consecutive lines from the same commit
+#[derive(Debug, Clone, Serialize)]
+pub struct BlameHunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub commit: String,
+    pub summary: String,
+    /// Nodes whose commit matches this hunk's commit
+    pub nodes: Vec<NodeRef>,
+    pub goal: Option<NodeRef>,
+    pub decision: Option<NodeRef>,
+    pub action: Option<NodeRef>,
+    /// Line contents (omitted from JSON output)
+    #[serde(skip)]
+    pub lines: Vec<String>,
+}
+
+impl BlameHunk {
+    /// True if any node is linked to this hunk's commit
+    pub fn is_annotated(&self) -> bool {
+        !self.nodes.is_empty()
+    }
+
+    /// One-line "goal › decision › action" summary
+    pub fn context_line(&self) -> String {
+        [
+            ("goal", &self.goal),
+            ("decision", &self.decision),
+            ("action", &self.action),
+        ]
+        .iter()
+        .filter_map(|(label, node)| {
+            node.as_ref()
+                .map(|n| format!("{} #{}: {}", label, n.id, n.title))
+        })
+        .collect::<Vec<_>>()
+        .join(" › ")
+    }
+}
+
+/// Lookup from commit hashes to the nodes that recorded them
+///
+/// Node commits may be abbreviated; they match any full hash they prefix, as
+/// long as they are at least 7 characters.
+pub struct CommitIndex<'a> {
+    linked: Vec<(String, &'a DecisionNode)>,
+}
+
+impl<'a> CommitIndex<'a> {
+    /// Index every node with a `commit` in its metadata
+    pub fn new(nodes: &'a [DecisionNode]) -> Self {
+        let linked = nodes
+            .iter()
+            .filter_map(|n| {
+                let commit = n
+                    .metadata_json
+                    .as_deref()
+                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())?
+                    .get("commit")?
+                    .as_str()?
+                    .to_lowercase();
+                (commit.len() >= MIN_COMMIT_PREFIX).then_some((commit, n))
+            })
+            .collect();
+        Self { linked }
+    }
+
+    /// Nodes linked to a (full) commit hash
+    pub fn nodes_for(&self, commit: &str) -> Vec<&'a DecisionNode> {
+        let commit = commit.to_lowercase();
+        self.linked
+            .iter()
+            .filter(|(c, _)| commit.starts_with(c.as_str()))
+            .map(|(_, n)| *n)
+            .collect()
+    }
+}
+
+/// Run `git blame --line-porcelain` on a file
+pub fn run_git_blame(path: &Path) -> Result<Vec<BlameLine>, String> {
+    let output = Command::new("git")
+        .arg("blame")
+        .arg("--line-porcelain")
+        .arg("--")
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run git blame: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(parse_blame_porcelain(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parse `git blame --line-porcelain` output
+pub fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for raw in output.lines() {
+        if let Some(content) = raw.strip_prefix('\t') {
+            if let Some(mut line) = current.take() {
+                line.content = content.to_string();
+                lines.push(line);
+            }
+            continue;
+        }
+
+        let mut parts = raw.split(' ');
+        let first = parts.next().unwrap_or_default();
+        if first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit()) {
+            let final_line = parts.nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+            current = Some(BlameLine {
+                line: final_line,
+                commit: first.to_string(),
+                summary: String::new(),
+                content: String::new(),
+            });
+        } else if let Some(summary) = raw.strip_prefix("summary ") {
+            if let Some(line) = current.as_mut() {
+                line.summary = summary.to_string();
+            }
+        }
+    }
+
+    lines
+}
+
+/// Group blame lines into hunks and attach decision context
+pub fn annotate_blame(
+    lines: &[BlameLine],
+    nodes: &[DecisionNode],
+    edges: &[DecisionEdge],
+) -> Vec<BlameHunk> {
+    let index = CommitIndex::new(nodes);
+
+    let mut hunks: Vec<BlameHunk> = Vec::new();
+    for line in lines {
+        if let Some(hunk) = hunks.last_mut() {
+            if hunk.commit == line.commit && hunk.end_line + 1 == line.line {
+                hunk.end_line = line.line;
+                hunk.lines.push(line.content.clone());
+                continue;
+            }
+        }
+
+        let matches = index.nodes_for(&line.commit);
+
+        // Prefer an action as the node the context is resolved from
+        let primary = matches
+            .iter()
+            .find(|n| n.node_type == "action")
+            .or_else(|| matches.first())
+            .copied();
+
+        let (goal, decision, action) = match primary {
+            Some(node) => (
+                find_root_goal(node.id, nodes, edges)
+                    .and_then(|id| nodes.iter().find(|n| n.id == id))
+                    .map(NodeRef::from),
+                nearest_ancestor(node.id, "decision", nodes, edges).map(NodeRef::from),
+                nearest_ancestor(node.id, "action", nodes, edges).map(NodeRef::from),
+            ),
+            None => (None, None, None),
+        };
+
+        hunks.push(BlameHunk {
+            start_line: line.line,
+            end_line: line.line,
+            commit: line.commit.clone(),
+            summary: line.summary.clone(),
+            nodes: matches.into_iter().map(NodeRef::from).collect(),
+            goal,
+            decision,
+            action,
+            lines: vec![line.content.clone()],
+        });
+    }
+
+    hunks
+}
+
+/// Closest node of `node_type` at or above `start_id`, following incoming edges
+pub fn nearest_ancestor<'a>(
+    start_id: i32,
+    node_type: &str,
+    nodes: &'a [DecisionNode],
+    edges: &[DecisionEdge],
+) -> Option<&'a DecisionNode> {
+    let mut queue = VecDeque::from([start_id]);
+    let mut visited = HashSet::new();
+
+    while let Some(id) = queue.pop_front() {
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(node) = nodes.iter().find(|n| n.id == id) {
+            if node.node_type == node_type {
+                return Some(node);
+            }
+        }
+        for edge in edges.iter().filter(|e| e.to_node_id == id) {
+            queue.push_back(edge.from_node_id);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const COMMIT_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn node(id: i32, node_type: &str, title: &str, commit: Option<&str>) -> DecisionNode {
+        DecisionNode {
+            id,
+            change_id: format!("cid-{}", id),
+            node_type: node_type.to_string(),
+            title: title.to_string(),
+            description: None,
+            status: "pending".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata_json: commit.map(|c| format!(r#"{{"commit": "{}"}}"#, c)),
+        }
+    }
+
+    fn edge(id: i32, from: i32, to: i32) -> DecisionEdge {
+        DecisionEdge {
+            id,
+            from_node_id: from,
+            to_node_id: to,
+            from_change_id: Some(format!("cid-{}", from)),
+            to_change_id: Some(format!("cid-{}", to)),
+            edge_type: "leads_to".to_string(),
+            weight: Some(1.0),
+            rationale: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn blame_line(line: usize, commit: &str, content: &str) -> BlameLine {
+        BlameLine {
+            line,
+            commit: commit.to_string(),
+            summary: "summary".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = format!(
+            "{a} 1 1 2\nauthor Alice\nsummary Add db\nfilename src/db.rs\n\tuse diesel;\n\
+             {a} 2 2\nauthor Alice\nsummary Add db\nfilename src/db.rs\n\t\n\
+             {b} 5 3 1\nauthor Bob\nsummary Fix lock\nfilename src/db.rs\n\tfn lock() {{}}\n",
+            a = COMMIT_A,
+            b = COMMIT_B
+        );
+
+        let lines = parse_blame_porcelain(&output);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].line, 1);
+        assert_eq!(lines[0].content, "use diesel;");
+        assert_eq!(lines[0].summary, "Add db");
+        assert_eq!(lines[1].content, "");
+        assert_eq!(lines[2].line, 3);
+        assert_eq!(lines[2].commit, COMMIT_B);
+        assert_eq!(lines[2].summary, "Fix lock");
+    }
+
+    #[test]
+    fn test_annotate_blame_resolves_context() {
+        let nodes = vec![
+            node(1, "goal", "Persist graph", None),
+            node(2, "decision", "Which ORM?", None),
+            node(3, "option", "Diesel", None),
+            node(4, "action", "Add diesel schema", Some(&COMMIT_A[..7])),
+        ];
+        let edges = vec![edge(1, 1, 2), edge(2, 2, 3), edge(3, 3, 4)];
+        let lines = vec![
+            blame_line(1, COMMIT_A, "use diesel;"),
+            blame_line(2, COMMIT_A, ""),
+            blame_line(3, COMMIT_B, "fn lock() {}"),
+        ];
+
+        let hunks = annotate_blame(&lines, &nodes, &edges);
+        assert_eq!(hunks.len(), 2);
+
+        let first = &hunks[0];
+        assert_eq!((first.start_line, first.end_line), (1, 2));
+        assert_eq!(first.nodes.len(), 1);
+        assert_eq!(first.goal.as_ref().unwrap().id, 1);
+        assert_eq!(first.decision.as_ref().unwrap().id, 2);
+        assert_eq!(first.action.as_ref().unwrap().id, 4);
+        assert_eq!(
+            first.context_line(),
+            "goal #1: Persist graph › decision #2: Which ORM? › action #4: Add diesel schema"
+        );
+
+        assert!(!hunks[1].is_annotated());
+        assert_eq!(hunks[1].context_line(), "");
+    }
+
+    #[test]
+    fn test_annotate_blame_ignores_short_commits() {
+        let nodes = vec![node(1, "action", "Tiny hash", Some("aaa"))];
+        let lines = vec![blame_line(1, COMMIT_A, "x")];
+
+        let hunks = annotate_blame(&lines, &nodes, &[]);
+        assert!(!hunks[0].is_annotated());
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! Reads from .deciduous/config.toml
 
+use crate::fields::FieldSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Configuration structure
@@ -15,6 +17,129 @@ pub struct Config {
     /// GitHub settings for external repository references
     #[serde(default)]
     pub github: GithubConfig,
+
+    /// Graph lint rule settings for `deciduous audit --lint`
+    #[serde(default, skip_serializing_if = "LintConfig::is_empty")]
+    pub lint: LintConfig,
+
+    /// Mirroring of decision links into git notes and commit trailers
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Custom typed fields per node type (`[fields.action]`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: FieldSchema,
+
+    /// Patch signing and verification
+    #[serde(default)]
+    pub patches: PatchesConfig,
+
+    /// Issue tracker used by `roadmap sync`
+    #[serde(default)]
+    pub tracker: TrackerConfig,
+}
+
+/// Issue tracker settings
+///
+/// ```toml
+/// [tracker]
+/// kind = "gitlab"                      # github (default), gitlab or gitea
+/// url = "https://gitlab.example.com"   # instance root (gitlab/gitea)
+/// repo = "group/project"               # default: from the origin remote
+/// token_env = "GITLAB_TOKEN"           # env var holding the API token
+/// ```
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct TrackerConfig {
+    /// Which tracker backend to use
+    #[serde(default)]
+    pub kind: TrackerKind,
+
+    /// Base URL of the tracker instance (for GitHub, the API root)
+    /// Default: https://api.github.com, https://gitlab.com; required for Gitea
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Repository/project path ("owner/repo" or "group/subgroup/project")
+    #[serde(default)]
+    pub repo: Option<String>,
+
+    /// Environment variable holding the API token
+    /// Default: GITHUB_TOKEN (or GH_TOKEN / `gh auth token`), GITLAB_TOKEN, GITEA_TOKEN
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+/// Issue tracker backend
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerKind {
+    #[default]
+    Github,
+    Gitlab,
+    Gitea,
+}
+
+/// Patch signing settings
+///
+/// ```toml
+/// [patches]
+/// signing_key = "~/.ssh/id_ed25519"   # sign every `diff export`
+/// require_signed = true                # refuse unsigned or unknown-signer patches
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PatchesConfig {
+    /// SSH private key used to sign exported patches
+    #[serde(default)]
+    pub signing_key: Option<PathBuf>,
+
+    /// Identity recorded as the signer
+    /// Default: git user.email
+    #[serde(default)]
+    pub signer: Option<String>,
+
+    /// OpenSSH allowed_signers file listing trusted signers
+    /// Default: .deciduous/allowed_signers
+    #[serde(default = "default_allowed_signers")]
+    pub allowed_signers: PathBuf,
+
+    /// Reject patches that are unsigned or signed by someone not in
+    /// allowed_signers when applying
+    #[serde(default)]
+    pub require_signed: bool,
+}
+
+fn default_allowed_signers() -> PathBuf {
+    PathBuf::from(".deciduous/allowed_signers")
+}
+
+impl Default for PatchesConfig {
+    fn default() -> Self {
+        Self {
+            signing_key: None,
+            signer: None,
+            allowed_signers: default_allowed_signers(),
+            require_signed: false,
+        }
+    }
+}
+
+/// Git integration settings
+///
+/// ```toml
+/// [git]
+/// notes = true      # mirror node-commit links into refs/notes/deciduous
+/// trailers = true   # add Decision-Id trailers from the prepare-commit-msg hook
+/// ```
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct GitConfig {
+    /// Write a note on each commit a node is linked to
+    #[serde(default)]
+    pub notes: bool,
+
+    /// Add `Decision-Id` trailers for in-progress actions when committing
+    /// (requires `deciduous hooks install`)
+    #[serde(default)]
+    pub trailers: bool,
 }
 
 /// GitHub-related configuration for commit/PR links
@@ -27,6 +152,51 @@ pub struct GithubConfig {
     pub commit_repo: Option<String>,
 }
 
+/// Graph lint configuration
+///
+/// ```toml
+/// [lint.rules]
+/// action-without-commit = "off"
+/// unreachable-outcome = "error"
+/// ```
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct LintConfig {
+    /// Per-rule severity overrides, keyed by rule name
+    /// Rules not listed use their built-in default level
+    #[serde(default)]
+    pub rules: BTreeMap<String, LintLevel>,
+}
+
+impl LintConfig {
+    /// True when no overrides are configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Severity of a lint rule
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Rule is disabled
+    Off,
+    /// Reported, but does not fail the lint run
+    Warn,
+    /// Reported and causes a non-zero exit code
+    Error,
+}
+
+impl LintLevel {
+    /// Config string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintLevel::Off => "off",
+            LintLevel::Warn => "warn",
+            LintLevel::Error => "error",
+        }
+    }
+}
+
 /// Branch-related configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BranchConfig {
@@ -121,4 +291,76 @@ auto_detect = true
         assert!(config.is_main_branch("develop"));
         assert!(!config.is_main_branch("feature-x"));
     }
+
+    #[test]
+    fn test_parse_lint_rules() {
+        let toml = r#"
+[lint.rules]
+action-without-commit = "off"
+cycle = "warn"
+"#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.lint.rules.get("action-without-commit"),
+            Some(&LintLevel::Off)
+        );
+        assert_eq!(config.lint.rules.get("cycle"), Some(&LintLevel::Warn));
+        assert!(Config::default().lint.is_empty());
+    }
+
+    #[test]
+    fn test_parse_field_schema() {
+        let toml = r#"
+[fields.action]
+cost_estimate = "number"
+risk = "enum[low,med,high]"
+"#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.fields["action"]["cost_estimate"],
+            crate::fields::FieldType::Number
+        );
+        assert!(toml::from_str::<Config>("[fields.action]\nrisk = \"date\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_patches_config() {
+        let config: Config = toml::from_str(
+            "[patches]\nsigning_key = \"~/.ssh/id_ed25519\"\nrequire_signed = true\n",
+        )
+        .unwrap();
+        assert!(config.patches.require_signed);
+        assert_eq!(
+            config.patches.signing_key,
+            Some(PathBuf::from("~/.ssh/id_ed25519"))
+        );
+        assert_eq!(
+            config.patches.allowed_signers,
+            PathBuf::from(".deciduous/allowed_signers")
+        );
+        assert!(!Config::default().patches.require_signed);
+    }
+
+    #[test]
+    fn test_parse_tracker_config() {
+        let config: Config = toml::from_str(
+            "[tracker]\nkind = \"gitea\"\nurl = \"https://git.example.com\"\nrepo = \"team/app\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.tracker.kind, TrackerKind::Gitea);
+        assert_eq!(
+            config.tracker.url.as_deref(),
+            Some("https://git.example.com")
+        );
+        assert_eq!(config.tracker.repo.as_deref(), Some("team/app"));
+        assert_eq!(Config::default().tracker.kind, TrackerKind::Github);
+    }
+
+    #[test]
+    fn test_parse_git_config() {
+        let config: Config = toml::from_str("[git]\nnotes = true\n").unwrap();
+        assert!(config.git.notes);
+        assert!(!config.git.trailers);
+        assert!(!Config::default().git.notes);
+    }
 }
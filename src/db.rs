@@ -10,6 +10,7 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
 use diesel::sqlite::SqliteConnection;
 use serde_json::json;
+use std::collections::HashSet;
 use std::path::Path;
 #[cfg(feature = "ts-rs")]
 use ts_rs::TS;
@@ -92,6 +93,28 @@ pub fn get_current_git_commit() -> Option<String> {
         })
 }
 
+/// Get the identity of the person making changes (for operation log scoping)
+///
+/// Uses git's configured user.email, falling back to $USER, then "unknown".
+pub fn get_current_actor() -> String {
+    std::process::Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .ok()
+        .and_then(|output| {
+            if output.status.success() {
+                String::from_utf8(output.stdout)
+                    .ok()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            } else {
+                None
+            }
+        })
+        .or_else(|| std::env::var("USER").ok().filter(|s| !s.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 /// Walk up directory tree to find .deciduous folder (like git finds .git)
 /// Respects active context from active.json file.
 /// Can be overridden with DECIDUOUS_DB_PATH or DECIDUOUS_CONTEXT env vars.
@@ -149,6 +172,10 @@ pub const CURRENT_SCHEMA: DecisionSchema = DecisionSchema {
         "decision_context",
         "decision_sessions",
         "command_log",
+        "operation_log",
+        "node_aliases",
+        "node_files",
+        "node_tags",
     ],
 };
 
@@ -230,7 +257,7 @@ pub struct NewDecisionNode<'a> {
 }
 
 /// Queryable decision node
-#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize)]
+#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
 #[diesel(table_name = decision_nodes)]
@@ -261,7 +288,7 @@ pub struct NewDecisionEdge<'a> {
 }
 
 /// Queryable decision edge
-#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize)]
+#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
 #[diesel(table_name = decision_edges)]
@@ -364,6 +391,210 @@ pub struct CommandLog {
     pub decision_node_id: Option<i32>,
 }
 
+// ============================================================================
+// Operation Log Models
+// ============================================================================
+
+/// Kind of graph mutation recorded in the operation log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// A node was created (`deciduous add`)
+    AddNode,
+    /// An edge was created (`deciduous link`)
+    AddEdge,
+    /// A node's status changed (`deciduous status`)
+    Status,
+    /// A node's prompt changed (`deciduous prompt`)
+    Prompt,
+    /// A node's custom fields changed (`deciduous edit`, `add --field`)
+    Fields,
+}
+
+impl OperationKind {
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationKind::AddNode => "add_node",
+            OperationKind::AddEdge => "add_edge",
+            OperationKind::Status => "status",
+            OperationKind::Prompt => "prompt",
+            OperationKind::Fields => "fields",
+        }
+    }
+
+    /// Parse from database string representation
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "add_node" => Some(OperationKind::AddNode),
+            "add_edge" => Some(OperationKind::AddEdge),
+            "status" => Some(OperationKind::Status),
+            "prompt" => Some(OperationKind::Prompt),
+            "fields" => Some(OperationKind::Fields),
+            _ => None,
+        }
+    }
+}
+
+/// Which operations undo/redo may touch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationScope {
+    /// Operations recorded under a DECIDUOUS_SESSION name
+    Session(String),
+    /// Operations recorded by a user (see `get_current_actor`)
+    Actor(String),
+    /// Every operation regardless of who made it
+    All,
+}
+
+impl OperationScope {
+    /// Scope for the current process: the DECIDUOUS_SESSION if set, otherwise the current user
+    pub fn current() -> Self {
+        match std::env::var("DECIDUOUS_SESSION") {
+            Ok(session) if !session.is_empty() => OperationScope::Session(session),
+            _ => OperationScope::Actor(get_current_actor()),
+        }
+    }
+}
+
+impl std::fmt::Display for OperationScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationScope::Session(s) => write!(f, "session '{}'", s),
+            OperationScope::Actor(a) => write!(f, "user '{}'", a),
+            OperationScope::All => write!(f, "all users"),
+        }
+    }
+}
+
+/// Insertable operation log entry
+#[derive(Insertable)]
+#[diesel(table_name = operation_log)]
+pub struct NewOperationLog<'a> {
+    pub operation: &'a str,
+    pub target_change_id: Option<&'a str>,
+    pub before_json: Option<&'a str>,
+    pub after_json: Option<&'a str>,
+    pub actor: &'a str,
+    pub session: Option<&'a str>,
+    pub state: &'a str,
+    pub created_at: &'a str,
+    pub undone_at: Option<&'a str>,
+}
+
+/// Queryable operation log entry
+#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[diesel(table_name = operation_log)]
+pub struct OperationLog {
+    pub id: i32,
+    pub operation: String,
+    pub target_change_id: Option<String>,
+    pub before_json: Option<String>,
+    pub after_json: Option<String>,
+    pub actor: String,
+    pub session: Option<String>,
+    /// applied, undone, or discarded (undone and then superseded by a new
+    /// operation, or about a node that was since merged away)
+    pub state: String,
+    pub created_at: String,
+    pub undone_at: Option<String>,
+}
+
+impl OperationLog {
+    /// Get the operation kind as a typed enum
+    pub fn kind(&self) -> Option<OperationKind> {
+        OperationKind::parse(&self.operation)
+    }
+
+    /// Human-readable one-line description of the operation
+    pub fn describe(&self) -> String {
+        let before: serde_json::Value = self
+            .before_json
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default();
+        let after: serde_json::Value = self
+            .after_json
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default();
+        let title = after
+            .get("title")
+            .or_else(|| before.get("title"))
+            .and_then(|t| t.as_str())
+            .unwrap_or("?");
+
+        match self.kind() {
+            Some(OperationKind::AddNode) => format!(
+                "add {} node '{}'",
+                after
+                    .get("node_type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("?"),
+                title
+            ),
+            Some(OperationKind::AddEdge) => format!(
+                "link {} -> {} ({})",
+                after
+                    .get("from_node_id")
+                    .unwrap_or(&serde_json::Value::Null),
+                after.get("to_node_id").unwrap_or(&serde_json::Value::Null),
+                after
+                    .get("edge_type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("?")
+            ),
+            Some(OperationKind::Status) => format!(
+                "status of '{}': {} -> {}",
+                title,
+                before.get("status").and_then(|s| s.as_str()).unwrap_or("?"),
+                after.get("status").and_then(|s| s.as_str()).unwrap_or("?")
+            ),
+            Some(OperationKind::Prompt) => format!("prompt of '{}'", title),
+            Some(OperationKind::Fields) => format!("fields of '{}'", title),
+            None => format!("unknown operation '{}'", self.operation),
+        }
+    }
+}
+
+// ============================================================================
+// Node Alias Models
+// ============================================================================
+
+/// Insertable node alias
+#[derive(Insertable)]
+#[diesel(table_name = node_aliases)]
+pub struct NewNodeAlias<'a> {
+    pub alias_change_id: &'a str,
+    pub change_id: &'a str,
+    pub created_at: &'a str,
+}
+
+/// A change_id that was merged into another node
+#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[diesel(table_name = node_aliases)]
+pub struct NodeAlias {
+    /// change_id of the node that was dropped
+    pub alias_change_id: String,
+    /// change_id of the node it was merged into
+    pub change_id: String,
+    pub created_at: String,
+}
+
+/// Summary of a `merge_nodes` call
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct MergeResult {
+    /// Number of nodes folded into the kept node
+    pub nodes_merged: usize,
+    /// Edges moved onto the kept node
+    pub edges_rewired: usize,
+    /// Edges deleted because they became self-loops or duplicates
+    pub edges_removed: usize,
+}
+
 // ============================================================================
 // Roadmap Board Models
 // ============================================================================
@@ -415,6 +646,46 @@ impl CheckboxState {
     }
 }
 
+/// How a roadmap sync conflict was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub enum ConflictResolution {
+    /// ROADMAP.md's value was applied to the issue
+    KeepLocal,
+    /// The issue's value was applied to ROADMAP.md
+    KeepRemote,
+    /// A hand-edited value was applied to both
+    Edited,
+}
+
+impl ConflictResolution {
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictResolution::KeepLocal => "keep_local",
+            ConflictResolution::KeepRemote => "keep_remote",
+            ConflictResolution::Edited => "edited",
+        }
+    }
+
+    /// Parse from database string representation
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "keep_local" => Some(ConflictResolution::KeepLocal),
+            "keep_remote" => Some(ConflictResolution::KeepRemote),
+            "edited" => Some(ConflictResolution::Edited),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ConflictResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 impl std::fmt::Display for CheckboxState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -441,6 +712,11 @@ pub struct NewRoadmapItem<'a> {
     pub created_at: &'a str,
     pub updated_at: &'a str,
     pub last_synced_at: Option<&'a str>,
+    pub synced_checkbox_state: Option<&'a str>,
+    pub milestone: Option<&'a str>,
+    pub due_date: Option<&'a str>,
+    pub goal_change_id: Option<&'a str>,
+    pub roadmap_path: Option<&'a str>,
 }
 
 /// Queryable roadmap item
@@ -466,6 +742,17 @@ pub struct RoadmapItem {
     pub created_at: String,
     pub updated_at: String,
     pub last_synced_at: Option<String>,
+    /// Checkbox state ROADMAP.md and the issue agreed on at `last_synced_at`
+    pub synced_checkbox_state: Option<String>,
+    /// Milestone of the item's section
+    pub milestone: Option<String>,
+    /// Target date of the item's section (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    /// Goal node tracking this item
+    pub goal_change_id: Option<String>,
+    /// Roadmap file the item was imported from (NULL for imports that
+    /// predate multiple roadmap files)
+    pub roadmap_path: Option<String>,
 }
 
 impl RoadmapItem {
@@ -518,6 +805,8 @@ pub struct NewRoadmapConflict<'a> {
     pub resolution: Option<&'a str>,
     pub detected_at: &'a str,
     pub resolved_at: Option<&'a str>,
+    pub resolved_value: Option<&'a str>,
+    pub resolved_by: Option<&'a str>,
 }
 
 /// Queryable roadmap conflict
@@ -534,6 +823,47 @@ pub struct RoadmapConflict {
     pub resolution: Option<String>,
     pub detected_at: String,
     pub resolved_at: Option<String>,
+    /// Value applied to both sides
+    pub resolved_value: Option<String>,
+    /// Who resolved it (see `get_current_actor`)
+    pub resolved_by: Option<String>,
+}
+
+impl RoadmapConflict {
+    /// The resolution as a typed enum (None while unresolved)
+    pub fn resolution_kind(&self) -> Option<ConflictResolution> {
+        self.resolution
+            .as_deref()
+            .and_then(ConflictResolution::parse)
+    }
+}
+
+/// Insertable roadmap item history entry
+#[derive(Insertable)]
+#[diesel(table_name = roadmap_item_history)]
+pub struct NewRoadmapItemHistory<'a> {
+    pub item_change_id: &'a str,
+    pub field: &'a str,
+    pub old_value: Option<&'a str>,
+    pub new_value: Option<&'a str>,
+    pub changed_at: &'a str,
+}
+
+/// One change to a roadmap item's progress
+///
+/// `field` is "checkbox" (checkbox state), "outcome" (linked outcome
+/// change_id) or "issue_state" ("open"/"closed").
+#[derive(Queryable, Selectable, Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[diesel(table_name = roadmap_item_history)]
+pub struct RoadmapItemHistory {
+    pub id: i32,
+    pub item_change_id: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: String,
 }
 
 // ============================================================================
@@ -553,6 +883,8 @@ pub struct NewGitHubIssueCache<'a> {
     pub created_at: &'a str,
     pub updated_at: &'a str,
     pub cached_at: &'a str,
+    pub etag: Option<&'a str>,
+    pub milestone: Option<&'a str>,
 }
 
 /// Queryable GitHub issue cache entry
@@ -571,6 +903,10 @@ pub struct GitHubIssueCache {
     pub created_at: String,
     pub updated_at: String,
     pub cached_at: String,
+    /// Validator for conditional requests (GitHub ETag)
+    pub etag: Option<String>,
+    /// Title of the issue's milestone
+    pub milestone: Option<String>,
 }
 
 // ============================================================================
@@ -633,6 +969,8 @@ pub struct Database {
     /// Option because open_at_unlocked() skips locking for special cases.
     #[allow(dead_code)]
     lock: Option<LockGuard>,
+    /// Actor recorded on operations, looked up on first use
+    actor: std::sync::OnceLock<String>,
 }
 
 /// Error type for database operations
@@ -777,6 +1115,7 @@ impl Database {
             pool,
             path: path.to_path_buf(),
             lock,
+            actor: std::sync::OnceLock::new(),
         };
         // Auto-migrate FIRST - add change_id columns to existing databases before init_schema creates new tables
         let _ = db.migrate_add_change_ids_raw();
@@ -857,6 +1196,12 @@ impl Database {
         Ok(true) // Migration performed
     }
 
+    /// Identity recorded on operations (see `get_current_actor`), cached so
+    /// each mutation doesn't spawn git
+    fn actor(&self) -> &str {
+        self.actor.get_or_init(get_current_actor)
+    }
+
     fn get_conn(&self) -> Result<DbConn> {
         self.pool
             .get()
@@ -1001,6 +1346,11 @@ impl Database {
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 last_synced_at TEXT,
+                synced_checkbox_state TEXT,
+                milestone TEXT,
+                due_date TEXT,
+                goal_change_id TEXT,
+                roadmap_path TEXT,
                 FOREIGN KEY (parent_id) REFERENCES roadmap_items(id),
                 FOREIGN KEY (outcome_node_id) REFERENCES decision_nodes(id)
             )
@@ -1034,12 +1384,29 @@ impl Database {
                 resolution TEXT,
                 detected_at TEXT NOT NULL,
                 resolved_at TEXT,
+                resolved_value TEXT,
+                resolved_by TEXT,
                 FOREIGN KEY (item_change_id) REFERENCES roadmap_items(change_id)
             )
         "#,
         )
         .execute(&mut conn)?;
 
+        // Progress history of roadmap items (survives refreshes)
+        diesel::sql_query(
+            r#"
+            CREATE TABLE IF NOT EXISTS roadmap_item_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                item_change_id TEXT NOT NULL,
+                field TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                changed_at TEXT NOT NULL
+            )
+        "#,
+        )
+        .execute(&mut conn)?;
+
         // GitHub issue cache for TUI/Web display
         diesel::sql_query(
             r#"
@@ -1054,12 +1421,71 @@ impl Database {
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 cached_at TEXT NOT NULL,
+                etag TEXT,
+                milestone TEXT,
                 UNIQUE(repo, issue_number)
             )
         "#,
         )
         .execute(&mut conn)?;
 
+        // Operation log for undo/redo
+        diesel::sql_query(
+            r#"
+            CREATE TABLE IF NOT EXISTS operation_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                operation TEXT NOT NULL,
+                target_change_id TEXT,
+                before_json TEXT,
+                after_json TEXT,
+                actor TEXT NOT NULL,
+                session TEXT,
+                state TEXT NOT NULL DEFAULT 'applied',
+                created_at TEXT NOT NULL,
+                undone_at TEXT
+            )
+        "#,
+        )
+        .execute(&mut conn)?;
+
+        // Aliases left behind by `deciduous merge` so patches can still
+        // reference nodes that were folded into another one
+        diesel::sql_query(
+            r#"
+            CREATE TABLE IF NOT EXISTS node_aliases (
+                alias_change_id TEXT PRIMARY KEY NOT NULL,
+                change_id TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+        "#,
+        )
+        .execute(&mut conn)?;
+
+        // Index of metadata_json.files for reverse file lookup
+        diesel::sql_query(
+            r#"
+            CREATE TABLE IF NOT EXISTS node_files (
+                node_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                PRIMARY KEY (node_id, path)
+            )
+        "#,
+        )
+        .execute(&mut conn)?;
+
+        // Free-form labels on nodes
+        diesel::sql_query(
+            r#"
+            CREATE TABLE IF NOT EXISTS node_tags (
+                node_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (node_id, tag)
+            )
+        "#,
+        )
+        .execute(&mut conn)?;
+
         // Create indexes
         diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_nodes_type ON decision_nodes(node_type)")
             .execute(&mut conn)?;
@@ -1100,8 +1526,92 @@ impl Database {
         diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_roadmap_items_github_issue ON roadmap_items(github_issue_number)").execute(&mut conn)?;
         diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_roadmap_items_outcome ON roadmap_items(outcome_change_id)").execute(&mut conn)?;
         diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_roadmap_conflicts_item ON roadmap_conflicts(item_change_id)").execute(&mut conn)?;
+        diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_roadmap_item_history_item ON roadmap_item_history(item_change_id)").execute(&mut conn)?;
         diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_github_issue_cache_repo ON github_issue_cache(repo, issue_number)").execute(&mut conn)?;
 
+        // ETag column for caches created before conditional requests
+        let cache_columns: Vec<PragmaTableInfo> =
+            diesel::sql_query("PRAGMA table_info(github_issue_cache)")
+                .load(&mut conn)
+                .unwrap_or_default();
+        if !cache_columns.iter().any(|c| c.name == "etag") {
+            diesel::sql_query("ALTER TABLE github_issue_cache ADD COLUMN etag TEXT")
+                .execute(&mut conn)?;
+        }
+        if !cache_columns.iter().any(|c| c.name == "milestone") {
+            diesel::sql_query("ALTER TABLE github_issue_cache ADD COLUMN milestone TEXT")
+                .execute(&mut conn)?;
+        }
+
+        // Checkbox sync base for roadmaps synced before two-way checkbox sync
+        let item_columns: Vec<PragmaTableInfo> =
+            diesel::sql_query("PRAGMA table_info(roadmap_items)")
+                .load(&mut conn)
+                .unwrap_or_default();
+        if !item_columns
+            .iter()
+            .any(|c| c.name == "synced_checkbox_state")
+        {
+            diesel::sql_query("ALTER TABLE roadmap_items ADD COLUMN synced_checkbox_state TEXT")
+                .execute(&mut conn)?;
+        }
+        // Section milestones, goal links and source files for roadmaps
+        // imported before them
+        for column in ["milestone", "due_date", "goal_change_id", "roadmap_path"] {
+            if !item_columns.iter().any(|c| c.name == column) {
+                diesel::sql_query(format!(
+                    "ALTER TABLE roadmap_items ADD COLUMN {} TEXT",
+                    column
+                ))
+                .execute(&mut conn)?;
+            }
+        }
+
+        // Resolution details for conflicts recorded before the resolver
+        let conflict_columns: Vec<PragmaTableInfo> =
+            diesel::sql_query("PRAGMA table_info(roadmap_conflicts)")
+                .load(&mut conn)
+                .unwrap_or_default();
+        for column in ["resolved_value", "resolved_by"] {
+            if !conflict_columns.iter().any(|c| c.name == column) {
+                diesel::sql_query(format!(
+                    "ALTER TABLE roadmap_conflicts ADD COLUMN {} TEXT",
+                    column
+                ))
+                .execute(&mut conn)?;
+            }
+        }
+
+        // Operation log indexes
+        diesel::sql_query(
+            "CREATE INDEX IF NOT EXISTS idx_operation_log_state ON operation_log(state)",
+        )
+        .execute(&mut conn)?;
+        diesel::sql_query(
+            "CREATE INDEX IF NOT EXISTS idx_operation_log_actor ON operation_log(actor, session)",
+        )
+        .execute(&mut conn)?;
+        diesel::sql_query(
+            "CREATE INDEX IF NOT EXISTS idx_node_aliases_change_id ON node_aliases(change_id)",
+        )
+        .execute(&mut conn)?;
+        diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_node_files_path ON node_files(path)")
+            .execute(&mut conn)?;
+        diesel::sql_query("CREATE INDEX IF NOT EXISTS idx_node_tags_tag ON node_tags(tag)")
+            .execute(&mut conn)?;
+
+        // Backfill the file index for databases created before it existed
+        let indexed: i64 = node_files::table.count().get_result(&mut conn)?;
+        if indexed == 0 {
+            let with_files: i64 = decision_nodes::table
+                .filter(decision_nodes::metadata_json.like("%\"files\"%"))
+                .count()
+                .get_result(&mut conn)?;
+            if with_files > 0 {
+                self.rebuild_node_files()?;
+            }
+        }
+
         // Register current schema
         self.register_schema(&CURRENT_SCHEMA)?;
         Ok(())
@@ -1263,6 +1773,18 @@ impl Database {
             "last_insert_rowid()",
         ))
         .first(&mut conn)?;
+        sync_node_files(&mut conn, id)?;
+
+        // Snapshot the node so undo/redo can remove and recreate it
+        let created = decision_nodes::table
+            .filter(decision_nodes::id.eq(id))
+            .first::<DecisionNode>(&mut conn)?;
+        self.record_operation(
+            OperationKind::AddNode,
+            Some(&change_id),
+            None,
+            serde_json::to_value(&created).ok(),
+        )?;
 
         Ok(id)
     }
@@ -1317,98 +1839,129 @@ impl Database {
             "last_insert_rowid()",
         ))
         .first(&mut conn)?;
+        sync_node_files(&mut conn, id)?;
 
         Ok(id)
     }
 
-    /// Create an edge between nodes
-    pub fn create_edge(
+    /// Insert a node exactly as another database exported it (for patch application)
+    ///
+    /// Unlike `create_node_with_change_id`, status, created_at and the whole
+    /// metadata_json (commit links, custom fields, ...) are kept as given.
+    pub fn import_node(
         &self,
-        from_id: i32,
-        to_id: i32,
-        edge_type: &str,
-        rationale: Option<&str>,
+        change_id: &str,
+        node_type: &str,
+        title: &str,
+        description: Option<&str>,
+        status: &str,
+        created_at: &str,
+        metadata_json: Option<&str>,
     ) -> Result<i32> {
         let mut conn = self.get_conn()?;
-
-        // Validate both nodes exist and get their change_ids
-        let from_node = decision_nodes::table
-            .filter(decision_nodes::id.eq(from_id))
-            .first::<DecisionNode>(&mut conn)
-            .ok();
-        let to_node = decision_nodes::table
-            .filter(decision_nodes::id.eq(to_id))
-            .first::<DecisionNode>(&mut conn)
-            .ok();
-
-        let from_change_id = from_node.as_ref().map(|n| n.change_id.clone());
-        let to_change_id = to_node.as_ref().map(|n| n.change_id.clone());
-
-        if from_node.is_none() && to_node.is_none() {
-            return Err(DbError::Validation(format!(
-                "Both nodes {} and {} do not exist. Run 'deciduous nodes' to see existing nodes.",
-                from_id, to_id
-            )));
-        } else if from_node.is_none() {
-            return Err(DbError::Validation(format!(
-                "Source node {} does not exist. Run 'deciduous nodes' to see existing nodes.",
-                from_id
-            )));
-        } else if to_node.is_none() {
-            return Err(DbError::Validation(format!(
-                "Target node {} does not exist. Run 'deciduous nodes' to see existing nodes.",
-                to_id
-            )));
-        }
-
         let now = chrono::Local::now().to_rfc3339();
 
-        let new_edge = NewDecisionEdge {
-            from_node_id: from_id,
-            to_node_id: to_id,
-            from_change_id: from_change_id.as_deref(),
-            to_change_id: to_change_id.as_deref(),
-            edge_type,
-            weight: Some(1.0),
-            rationale,
-            created_at: &now,
+        let new_node = NewDecisionNode {
+            change_id,
+            node_type,
+            title,
+            description,
+            status,
+            created_at,
+            updated_at: &now,
+            metadata_json,
         };
 
-        diesel::insert_into(decision_edges::table)
-            .values(&new_edge)
+        diesel::insert_into(decision_nodes::table)
+            .values(&new_node)
             .execute(&mut conn)?;
 
         let id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
             "last_insert_rowid()",
         ))
         .first(&mut conn)?;
+        sync_node_files(&mut conn, id)?;
 
         Ok(id)
     }
 
-    /// Add an edge (alias for create_edge for doc examples)
-    pub fn add_edge(
+    /// Create an edge between nodes
+    pub fn create_edge(
         &self,
         from_id: i32,
         to_id: i32,
         edge_type: &str,
         rationale: Option<&str>,
     ) -> Result<i32> {
-        self.create_edge(from_id, to_id, edge_type, rationale)
-    }
+        let id = self.insert_edge(from_id, to_id, edge_type, Some(1.0), rationale)?;
 
-    /// Update node status
-    pub fn update_node_status(&self, node_id: i32, status: &str) -> Result<()> {
+        // Snapshot the edge so undo/redo can remove and recreate it
         let mut conn = self.get_conn()?;
-        let now = chrono::Local::now().to_rfc3339();
+        let created = decision_edges::table
+            .filter(decision_edges::id.eq(id))
+            .first::<DecisionEdge>(&mut conn)?;
+        self.record_operation(
+            OperationKind::AddEdge,
+            None,
+            None,
+            serde_json::to_value(&created).ok(),
+        )?;
 
-        diesel::update(decision_nodes::table.filter(decision_nodes::id.eq(node_id)))
-            .set((
-                decision_nodes::status.eq(status),
-                decision_nodes::updated_at.eq(&now),
+        Ok(id)
+    }
+
+    /// Insert an edge without recording it in the operation log
+    ///
+    /// Used by patch application and redo, which are not themselves undoable steps.
+    pub(crate) fn insert_edge(
+        &self,
+        from_id: i32,
+        to_id: i32,
+        edge_type: &str,
+        weight: Option<f64>,
+        rationale: Option<&str>,
+    ) -> Result<i32> {
+        let mut conn = self.get_conn()?;
+        insert_edge_in(&mut conn, from_id, to_id, edge_type, weight, rationale)
+    }
+
+    /// Add an edge (alias for create_edge for doc examples)
+    pub fn add_edge(
+        &self,
+        from_id: i32,
+        to_id: i32,
+        edge_type: &str,
+        rationale: Option<&str>,
+    ) -> Result<i32> {
+        self.create_edge(from_id, to_id, edge_type, rationale)
+    }
+
+    /// Update node status
+    pub fn update_node_status(&self, node_id: i32, status: &str) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+
+        let previous = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .first::<DecisionNode>(&mut conn)
+            .optional()?;
+
+        diesel::update(decision_nodes::table.filter(decision_nodes::id.eq(node_id)))
+            .set((
+                decision_nodes::status.eq(status),
+                decision_nodes::updated_at.eq(&now),
             ))
             .execute(&mut conn)?;
 
+        if let Some(node) = previous {
+            self.record_operation(
+                OperationKind::Status,
+                Some(&node.change_id),
+                Some(json!({ "title": node.title, "status": node.status })),
+                Some(json!({ "title": node.title, "status": status })),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -1447,6 +2000,112 @@ impl Database {
         Ok(())
     }
 
+    /// Set and clear custom field values in a node's metadata_json
+    ///
+    /// `set` values are merged into the existing `fields` object; names in
+    /// `unset` are removed. Values are not validated here.
+    pub fn update_node_fields(
+        &self,
+        node_id: i32,
+        set: &serde_json::Map<String, serde_json::Value>,
+        unset: &[String],
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+
+        let current_meta: Option<String> = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .select(decision_nodes::metadata_json)
+            .first(&mut conn)?;
+
+        let mut meta: serde_json::Value = current_meta
+            .as_ref()
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_else(|| serde_json::json!({}));
+        let previous_fields = meta.get(crate::fields::FIELDS_KEY).cloned();
+
+        if let Some(obj) = meta.as_object_mut() {
+            let fields = obj
+                .entry(crate::fields::FIELDS_KEY)
+                .or_insert_with(|| serde_json::json!({}));
+            if let Some(fields) = fields.as_object_mut() {
+                for (name, value) in set {
+                    fields.insert(name.clone(), value.clone());
+                }
+                for name in unset {
+                    fields.remove(name);
+                }
+            }
+            if obj
+                .get(crate::fields::FIELDS_KEY)
+                .and_then(|f| f.as_object())
+                .is_some_and(|f| f.is_empty())
+            {
+                obj.remove(crate::fields::FIELDS_KEY);
+            }
+        }
+        let fields = meta.get(crate::fields::FIELDS_KEY).cloned();
+
+        let new_meta = serde_json::to_string(&meta)
+            .map_err(|e| DbError::Validation(format!("JSON serialization error: {}", e)))?;
+
+        diesel::update(decision_nodes::table.filter(decision_nodes::id.eq(node_id)))
+            .set((
+                decision_nodes::metadata_json.eq(Some(new_meta)),
+                decision_nodes::updated_at.eq(&now),
+            ))
+            .execute(&mut conn)?;
+
+        let (change_id, title): (String, String) = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .select((decision_nodes::change_id, decision_nodes::title))
+            .first(&mut conn)?;
+        self.record_operation(
+            OperationKind::Fields,
+            Some(&change_id),
+            Some(json!({ "title": title, "fields": previous_fields })),
+            Some(json!({ "title": title, "fields": fields })),
+        )?;
+
+        Ok(())
+    }
+
+    /// Replace a node's associated files in metadata_json
+    pub fn update_node_files(&self, node_id: i32, files: &[String]) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+
+        // Get current metadata
+        let current_meta: Option<String> = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .select(decision_nodes::metadata_json)
+            .first(&mut conn)?;
+
+        // Parse existing metadata or create new
+        let mut meta: serde_json::Value = current_meta
+            .as_ref()
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_else(|| serde_json::json!({}));
+
+        // Add/update files field
+        if let Some(obj) = meta.as_object_mut() {
+            obj.insert("files".to_string(), serde_json::json!(files));
+        }
+
+        let new_meta = serde_json::to_string(&meta)
+            .map_err(|e| DbError::Validation(format!("JSON serialization error: {}", e)))?;
+
+        diesel::update(decision_nodes::table.filter(decision_nodes::id.eq(node_id)))
+            .set((
+                decision_nodes::metadata_json.eq(Some(new_meta)),
+                decision_nodes::updated_at.eq(&now),
+            ))
+            .execute(&mut conn)?;
+        sync_node_files(&mut conn, node_id)?;
+
+        Ok(())
+    }
+
     /// Update a node's prompt in metadata_json
     pub fn update_node_prompt(&self, node_id: i32, prompt: &str) -> Result<()> {
         let mut conn = self.get_conn()?;
@@ -1463,6 +2122,7 @@ impl Database {
             .as_ref()
             .and_then(|m| serde_json::from_str(m).ok())
             .unwrap_or_else(|| serde_json::json!({}));
+        let previous_prompt = meta.get("prompt").cloned();
 
         // Add/update prompt field
         if let Some(obj) = meta.as_object_mut() {
@@ -1479,6 +2139,17 @@ impl Database {
             ))
             .execute(&mut conn)?;
 
+        let (change_id, title): (String, String) = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .select((decision_nodes::change_id, decision_nodes::title))
+            .first(&mut conn)?;
+        self.record_operation(
+            OperationKind::Prompt,
+            Some(&change_id),
+            Some(json!({ "title": title, "prompt": previous_prompt })),
+            Some(json!({ "title": title, "prompt": prompt })),
+        )?;
+
         Ok(())
     }
 
@@ -1557,6 +2228,369 @@ impl Database {
         })
     }
 
+    // ========================================================================
+    // Merge & Aliases
+    // ========================================================================
+
+    /// Merge duplicate nodes into `keep_id`
+    ///
+    /// Every edge touching a dropped node is moved onto the kept node (edges
+    /// that would become self-loops or duplicates are deleted), the `files`
+    /// and commits from each node's metadata are unioned into the kept node,
+    /// and the dropped change_ids are recorded as aliases so patches that
+    /// still reference them resolve to the kept node. Logged operations on the
+    /// dropped nodes are discarded, since undo/redo can't replay them.
+    pub fn merge_nodes(&self, keep_id: i32, drop_ids: &[i32]) -> Result<MergeResult> {
+        if drop_ids.contains(&keep_id) {
+            return Err(DbError::Validation(format!(
+                "Cannot merge node {} into itself",
+                keep_id
+            )));
+        }
+
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+
+        conn.transaction::<_, DbError, _>(|conn| {
+            let keep = decision_nodes::table
+                .filter(decision_nodes::id.eq(keep_id))
+                .first::<DecisionNode>(conn)
+                .optional()?
+                .ok_or_else(|| DbError::Validation(format!("Node {} not found", keep_id)))?;
+
+            let mut dropped = Vec::new();
+            for &id in drop_ids {
+                let node = decision_nodes::table
+                    .filter(decision_nodes::id.eq(id))
+                    .first::<DecisionNode>(conn)
+                    .optional()?
+                    .ok_or_else(|| DbError::Validation(format!("Node {} not found", id)))?;
+                if !dropped.iter().any(|d: &DecisionNode| d.id == node.id) {
+                    dropped.push(node);
+                }
+            }
+
+            let dropped_ids: HashSet<i32> = dropped.iter().map(|n| n.id).collect();
+            let remap = |id: i32| {
+                if dropped_ids.contains(&id) {
+                    keep.id
+                } else {
+                    id
+                }
+            };
+
+            let mut result = MergeResult {
+                nodes_merged: dropped.len(),
+                ..Default::default()
+            };
+
+            // Rewire edges. Untouched edges are registered first so a moved
+            // edge that duplicates one of them is dropped rather than doubled.
+            let edges = decision_edges::table.load::<DecisionEdge>(conn)?;
+            let (moved, untouched): (Vec<_>, Vec<_>) = edges.into_iter().partition(|e| {
+                dropped_ids.contains(&e.from_node_id) || dropped_ids.contains(&e.to_node_id)
+            });
+            let mut seen: HashSet<(i32, i32, String)> = untouched
+                .iter()
+                .map(|e| (e.from_node_id, e.to_node_id, e.edge_type.clone()))
+                .collect();
+
+            for edge in moved {
+                let from = remap(edge.from_node_id);
+                let to = remap(edge.to_node_id);
+
+                if from == to || !seen.insert((from, to, edge.edge_type.clone())) {
+                    diesel::delete(decision_edges::table.filter(decision_edges::id.eq(edge.id)))
+                        .execute(conn)?;
+                    result.edges_removed += 1;
+                    continue;
+                }
+
+                let from_change_id = if from == keep.id {
+                    Some(keep.change_id.clone())
+                } else {
+                    edge.from_change_id.clone()
+                };
+                let to_change_id = if to == keep.id {
+                    Some(keep.change_id.clone())
+                } else {
+                    edge.to_change_id.clone()
+                };
+
+                diesel::update(decision_edges::table.filter(decision_edges::id.eq(edge.id)))
+                    .set((
+                        decision_edges::from_node_id.eq(from),
+                        decision_edges::to_node_id.eq(to),
+                        decision_edges::from_change_id.eq(from_change_id),
+                        decision_edges::to_change_id.eq(to_change_id),
+                    ))
+                    .execute(conn)?;
+                result.edges_rewired += 1;
+            }
+
+            // Union metadata into the kept node
+            let merged_meta = merge_metadata_json(
+                keep.metadata_json.as_deref(),
+                dropped.iter().map(|n| n.metadata_json.as_deref()),
+            );
+            diesel::update(decision_nodes::table.filter(decision_nodes::id.eq(keep.id)))
+                .set((
+                    decision_nodes::metadata_json.eq(merged_meta),
+                    decision_nodes::updated_at.eq(&now),
+                ))
+                .execute(conn)?;
+            sync_node_files(conn, keep.id)?;
+
+            // Carry tags over to the kept node
+            let dropped_ids: Vec<i32> = dropped.iter().map(|n| n.id).collect();
+            let tags: Vec<String> = node_tags::table
+                .filter(node_tags::node_id.eq_any(&dropped_ids))
+                .select(node_tags::tag)
+                .distinct()
+                .load(conn)?;
+            for tag in &tags {
+                diesel::insert_or_ignore_into(node_tags::table)
+                    .values((
+                        node_tags::node_id.eq(keep.id),
+                        node_tags::tag.eq(tag),
+                        node_tags::created_at.eq(&now),
+                    ))
+                    .execute(conn)?;
+            }
+            diesel::delete(node_tags::table.filter(node_tags::node_id.eq_any(&dropped_ids)))
+                .execute(conn)?;
+
+            // Record aliases, re-pointing any that targeted a dropped node
+            for node in &dropped {
+                diesel::update(
+                    node_aliases::table.filter(node_aliases::change_id.eq(&node.change_id)),
+                )
+                .set(node_aliases::change_id.eq(&keep.change_id))
+                .execute(conn)?;
+
+                diesel::replace_into(node_aliases::table)
+                    .values(&NewNodeAlias {
+                        alias_change_id: &node.change_id,
+                        change_id: &keep.change_id,
+                        created_at: &now,
+                    })
+                    .execute(conn)?;
+
+                diesel::delete(decision_nodes::table.filter(decision_nodes::id.eq(node.id)))
+                    .execute(conn)?;
+                sync_node_files(conn, node.id)?;
+            }
+
+            // Earlier operations on the dropped nodes (or their edges, which now
+            // belong to the kept node) can no longer be undone or redone
+            let dropped_change_ids: Vec<&str> =
+                dropped.iter().map(|n| n.change_id.as_str()).collect();
+            let mentions_dropped = |json: Option<&str>| {
+                json.is_some_and(|j| dropped_change_ids.iter().any(|c| j.contains(c)))
+            };
+            let stale: Vec<i32> = operation_log::table
+                .filter(operation_log::state.eq_any(["applied", "undone"]))
+                .load::<OperationLog>(conn)?
+                .into_iter()
+                .filter(|op| {
+                    op.target_change_id
+                        .as_deref()
+                        .is_some_and(|c| dropped_change_ids.contains(&c))
+                        || mentions_dropped(op.before_json.as_deref())
+                        || mentions_dropped(op.after_json.as_deref())
+                })
+                .map(|op| op.id)
+                .collect();
+            diesel::update(operation_log::table.filter(operation_log::id.eq_any(stale)))
+                .set(operation_log::state.eq("discarded"))
+                .execute(conn)?;
+
+            Ok(result)
+        })
+    }
+
+    /// Get all recorded node aliases
+    pub fn get_node_aliases(&self) -> Result<Vec<NodeAlias>> {
+        let mut conn = self.get_conn()?;
+        let aliases = node_aliases::table
+            .order(node_aliases::created_at.asc())
+            .load::<NodeAlias>(&mut conn)?;
+        Ok(aliases)
+    }
+
+    /// Resolve a change_id through the alias table
+    ///
+    /// Returns the change_id of the node it was merged into, or the input
+    /// unchanged if it is not an alias.
+    pub fn resolve_change_id(&self, change_id: &str) -> Result<String> {
+        let mut conn = self.get_conn()?;
+        let target = node_aliases::table
+            .filter(node_aliases::alias_change_id.eq(change_id))
+            .select(node_aliases::change_id)
+            .first::<String>(&mut conn)
+            .optional()?;
+        Ok(target.unwrap_or_else(|| change_id.to_string()))
+    }
+
+    // ========================================================================
+    // File Index
+    // ========================================================================
+
+    /// Rebuild the node_files index from every node's metadata_json.files
+    pub fn rebuild_node_files(&self) -> Result<usize> {
+        let mut conn = self.get_conn()?;
+
+        conn.transaction::<_, DbError, _>(|conn| {
+            diesel::delete(node_files::table).execute(conn)?;
+
+            let ids: Vec<i32> = decision_nodes::table
+                .select(decision_nodes::id)
+                .load(conn)?;
+            let mut total = 0;
+            for id in ids {
+                total += sync_node_files(conn, id)?;
+            }
+            Ok(total)
+        })
+    }
+
+    /// Get the files associated with a node
+    pub fn get_node_files(&self, node_id: i32) -> Result<Vec<String>> {
+        let mut conn = self.get_conn()?;
+        let files = node_files::table
+            .filter(node_files::node_id.eq(node_id))
+            .select(node_files::path)
+            .order(node_files::path.asc())
+            .load::<String>(&mut conn)?;
+        Ok(files)
+    }
+
+    /// Get every distinct file path linked to any node
+    pub fn get_all_linked_files(&self) -> Result<Vec<String>> {
+        let mut conn = self.get_conn()?;
+        let files = node_files::table
+            .select(node_files::path)
+            .distinct()
+            .order(node_files::path.asc())
+            .load::<String>(&mut conn)?;
+        Ok(files)
+    }
+
+    /// Find nodes that touched a file
+    ///
+    /// `pattern` is either a path (also matching everything below it when it
+    /// names a directory) or a glob such as `src/*.rs`. Globs use SQLite GLOB
+    /// semantics, where `*` also matches across `/`.
+    pub fn find_nodes_by_file(&self, pattern: &str) -> Result<Vec<DecisionNode>> {
+        let mut conn = self.get_conn()?;
+        let pattern = normalize_file_path(pattern);
+
+        let glob = if pattern.contains(['*', '?', '[']) {
+            pattern.clone()
+        } else {
+            format!("{}/*", pattern.trim_end_matches('/'))
+        };
+
+        let node_ids: Vec<i32> = node_files::table
+            .filter(
+                node_files::path
+                    .eq(&pattern)
+                    .or(diesel::dsl::sql::<diesel::sql_types::Bool>("path GLOB ")
+                        .bind::<diesel::sql_types::Text, _>(glob)),
+            )
+            .select(node_files::node_id)
+            .distinct()
+            .load(&mut conn)?;
+
+        let nodes = decision_nodes::table
+            .filter(decision_nodes::id.eq_any(node_ids))
+            .order(decision_nodes::created_at.asc())
+            .load::<DecisionNode>(&mut conn)?;
+        Ok(nodes)
+    }
+
+    // ========================================================================
+    // Tags
+    // ========================================================================
+
+    /// Add tags to a node, returning how many were new
+    pub fn add_node_tags(&self, node_id: i32, tags: &[String]) -> Result<usize> {
+        let tags = normalize_tags(tags)?;
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+
+        let exists: i64 = decision_nodes::table
+            .filter(decision_nodes::id.eq(node_id))
+            .count()
+            .get_result(&mut conn)?;
+        if exists == 0 {
+            return Err(DbError::Validation(format!("Node {} not found", node_id)));
+        }
+
+        let mut added = 0;
+        for tag in &tags {
+            added += diesel::insert_or_ignore_into(node_tags::table)
+                .values((
+                    node_tags::node_id.eq(node_id),
+                    node_tags::tag.eq(tag),
+                    node_tags::created_at.eq(&now),
+                ))
+                .execute(&mut conn)?;
+        }
+        Ok(added)
+    }
+
+    /// Remove tags from a node, returning how many were removed
+    pub fn remove_node_tags(&self, node_id: i32, tags: &[String]) -> Result<usize> {
+        let tags = normalize_tags(tags)?;
+        let mut conn = self.get_conn()?;
+        let removed = diesel::delete(
+            node_tags::table
+                .filter(node_tags::node_id.eq(node_id))
+                .filter(node_tags::tag.eq_any(&tags)),
+        )
+        .execute(&mut conn)?;
+        Ok(removed)
+    }
+
+    /// Get a node's tags, sorted
+    pub fn get_node_tags(&self, node_id: i32) -> Result<Vec<String>> {
+        let mut conn = self.get_conn()?;
+        let tags = node_tags::table
+            .filter(node_tags::node_id.eq(node_id))
+            .select(node_tags::tag)
+            .order(node_tags::tag.asc())
+            .load::<String>(&mut conn)?;
+        Ok(tags)
+    }
+
+    /// Get every node's tags, keyed by node ID
+    pub fn get_all_node_tags(&self) -> Result<std::collections::HashMap<i32, Vec<String>>> {
+        let mut conn = self.get_conn()?;
+        let rows = node_tags::table
+            .select((node_tags::node_id, node_tags::tag))
+            .order((node_tags::node_id.asc(), node_tags::tag.asc()))
+            .load::<(i32, String)>(&mut conn)?;
+
+        let mut tags: std::collections::HashMap<i32, Vec<String>> =
+            std::collections::HashMap::new();
+        for (node_id, tag) in rows {
+            tags.entry(node_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Every tag in use with the number of nodes carrying it
+    pub fn get_tag_counts(&self) -> Result<Vec<(String, i64)>> {
+        let mut conn = self.get_conn()?;
+        let counts = node_tags::table
+            .group_by(node_tags::tag)
+            .select((node_tags::tag, diesel::dsl::count_star()))
+            .order(node_tags::tag.asc())
+            .load::<(String, i64)>(&mut conn)?;
+        Ok(counts)
+    }
+
     // ========================================================================
     // Command Log Operations
     // ========================================================================
@@ -1632,60 +2666,242 @@ impl Database {
     }
 
     // ========================================================================
-    // Roadmap Board Operations
+    // Operation Log (Undo/Redo)
     // ========================================================================
 
-    /// Create a new roadmap item
-    pub fn create_roadmap_item(
+    /// Record a graph mutation in the operation log
+    ///
+    /// Any operations previously undone in the same scope can no longer be
+    /// redone once a new operation is recorded, so they are marked discarded.
+    fn record_operation(
         &self,
-        title: &str,
-        description: Option<&str>,
-        section: Option<&str>,
-        parent_id: Option<i32>,
-        checkbox_state: &str,
-    ) -> Result<i32> {
+        kind: OperationKind,
+        target_change_id: Option<&str>,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let session = std::env::var("DECIDUOUS_SESSION")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let scope = match &session {
+            Some(s) => OperationScope::Session(s.clone()),
+            None => OperationScope::Actor(self.actor().to_string()),
+        };
+        let stale: Vec<i32> = self
+            .get_operations(&scope, "undone", false, None)?
+            .iter()
+            .map(|op| op.id)
+            .collect();
+
         let mut conn = self.get_conn()?;
-        let now = chrono::Local::now().to_rfc3339();
-        let change_id = Uuid::new_v4().to_string();
+        if !stale.is_empty() {
+            diesel::update(operation_log::table.filter(operation_log::id.eq_any(stale)))
+                .set(operation_log::state.eq("discarded"))
+                .execute(&mut conn)?;
+        }
 
-        let new_item = NewRoadmapItem {
-            change_id: &change_id,
-            title,
-            description,
-            section,
-            parent_id,
-            checkbox_state,
-            github_issue_number: None,
-            github_issue_state: None,
-            outcome_node_id: None,
-            outcome_change_id: None,
-            markdown_line_start: None,
-            markdown_line_end: None,
-            content_hash: None,
+        let now = chrono::Local::now().to_rfc3339();
+        let before_json = before.map(|v| v.to_string());
+        let after_json = after.map(|v| v.to_string());
+
+        let new_op = NewOperationLog {
+            operation: kind.as_str(),
+            target_change_id,
+            before_json: before_json.as_deref(),
+            after_json: after_json.as_deref(),
+            actor: self.actor(),
+            session: session.as_deref(),
+            state: "applied",
             created_at: &now,
-            updated_at: &now,
-            last_synced_at: None,
+            undone_at: None,
         };
 
-        diesel::insert_into(roadmap_items::table)
-            .values(&new_item)
+        diesel::insert_into(operation_log::table)
+            .values(&new_op)
             .execute(&mut conn)?;
 
-        let id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
-            "last_insert_rowid()",
-        ))
-        .first(&mut conn)?;
-
-        Ok(id)
+        Ok(())
     }
 
-    /// Create a roadmap item with full metadata (for sync operations)
-    pub fn create_roadmap_item_full(
+    /// Get operations in a scope with the given state (applied, undone, discarded)
+    pub fn get_operations(
         &self,
-        change_id: &str,
-        title: &str,
-        description: Option<&str>,
-        section: Option<&str>,
+        scope: &OperationScope,
+        state: &str,
+        newest_first: bool,
+        limit: Option<i64>,
+    ) -> Result<Vec<OperationLog>> {
+        let mut conn = self.get_conn()?;
+
+        let mut query = operation_log::table
+            .filter(operation_log::state.eq(state))
+            .into_boxed();
+
+        match scope {
+            OperationScope::Session(s) => {
+                query = query.filter(operation_log::session.eq(s.as_str()));
+            }
+            OperationScope::Actor(a) => {
+                query = query.filter(operation_log::actor.eq(a.as_str()));
+            }
+            OperationScope::All => {}
+        }
+
+        query = if newest_first {
+            query.order(operation_log::id.desc())
+        } else {
+            query.order(operation_log::id.asc())
+        };
+
+        if let Some(n) = limit {
+            query = query.limit(n);
+        }
+
+        Ok(query.load::<OperationLog>(&mut conn)?)
+    }
+
+    /// Undo the last `count` applied operations in a scope (most recent first)
+    ///
+    /// Returns the operations that were (or, with `dry_run`, would be) reverted.
+    pub fn undo_operations(
+        &self,
+        count: usize,
+        scope: &OperationScope,
+        dry_run: bool,
+    ) -> Result<Vec<OperationLog>> {
+        let ops = self.get_operations(scope, "applied", true, Some(count as i64))?;
+        if dry_run {
+            return Ok(ops);
+        }
+
+        // All or nothing, so a failure part way doesn't leave the graph and log out of step
+        let mut conn = self.get_conn()?;
+        conn.transaction::<_, DbError, _>(|conn| {
+            for op in &ops {
+                revert_operation(conn, op)?;
+
+                let now = chrono::Local::now().to_rfc3339();
+                diesel::update(operation_log::table.filter(operation_log::id.eq(op.id)))
+                    .set((
+                        operation_log::state.eq("undone"),
+                        operation_log::undone_at.eq(Some(&now)),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+
+        Ok(ops)
+    }
+
+    /// Redo the next `count` undone operations in a scope (oldest undone first)
+    ///
+    /// Returns the operations that were (or, with `dry_run`, would be) reapplied.
+    pub fn redo_operations(
+        &self,
+        count: usize,
+        scope: &OperationScope,
+        dry_run: bool,
+    ) -> Result<Vec<OperationLog>> {
+        let ops = self.get_operations(scope, "undone", false, Some(count as i64))?;
+        if dry_run {
+            return Ok(ops);
+        }
+
+        let mut conn = self.get_conn()?;
+        conn.transaction::<_, DbError, _>(|conn| {
+            for op in &ops {
+                reapply_operation(conn, op)?;
+
+                diesel::update(operation_log::table.filter(operation_log::id.eq(op.id)))
+                    .set((
+                        operation_log::state.eq("applied"),
+                        operation_log::undone_at.eq(None::<String>),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+
+        Ok(ops)
+    }
+
+    /// Point a node at the roadmap item it tracks (`roadmap_item` in its
+    /// metadata_json), or clear the link
+    pub fn set_node_roadmap_item(
+        &self,
+        node_change_id: &str,
+        item_change_id: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        set_metadata_field_by_change_id(
+            &mut conn,
+            node_change_id,
+            "roadmap_item",
+            item_change_id.map(|id| json!(id)),
+        )
+    }
+
+    // ========================================================================
+    // Roadmap Board Operations
+    // ========================================================================
+
+    /// Create a new roadmap item
+    pub fn create_roadmap_item(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        section: Option<&str>,
+        parent_id: Option<i32>,
+        checkbox_state: &str,
+    ) -> Result<i32> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+        let change_id = Uuid::new_v4().to_string();
+
+        let new_item = NewRoadmapItem {
+            change_id: &change_id,
+            title,
+            description,
+            section,
+            parent_id,
+            checkbox_state,
+            github_issue_number: None,
+            github_issue_state: None,
+            outcome_node_id: None,
+            outcome_change_id: None,
+            markdown_line_start: None,
+            markdown_line_end: None,
+            content_hash: None,
+            created_at: &now,
+            updated_at: &now,
+            last_synced_at: None,
+            synced_checkbox_state: None,
+            milestone: None,
+            due_date: None,
+            goal_change_id: None,
+            roadmap_path: None,
+        };
+
+        diesel::insert_into(roadmap_items::table)
+            .values(&new_item)
+            .execute(&mut conn)?;
+
+        let id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
+            "last_insert_rowid()",
+        ))
+        .first(&mut conn)?;
+
+        Ok(id)
+    }
+
+    /// Create a roadmap item with full metadata (for sync operations)
+    pub fn create_roadmap_item_full(
+        &self,
+        change_id: &str,
+        title: &str,
+        description: Option<&str>,
+        section: Option<&str>,
         parent_id: Option<i32>,
         checkbox_state: &str,
         github_issue_number: Option<i32>,
@@ -1716,6 +2932,11 @@ impl Database {
             created_at: &now,
             updated_at: &now,
             last_synced_at: None,
+            synced_checkbox_state: None,
+            milestone: None,
+            due_date: None,
+            goal_change_id: None,
+            roadmap_path: None,
         };
 
         diesel::insert_into(roadmap_items::table)
@@ -1746,6 +2967,32 @@ impl Database {
         Ok(deleted)
     }
 
+    /// Clear the items imported from one roadmap file (for refresh)
+    ///
+    /// Items with no file recorded are cleared too; they come from imports
+    /// made before multiple roadmap files and are re-imported with their file.
+    pub fn clear_roadmap_items_in(&self, roadmap_path: &str) -> Result<usize> {
+        let mut conn = self.get_conn()?;
+        let deleted = diesel::delete(
+            roadmap_items::table.filter(
+                roadmap_items::roadmap_path
+                    .eq(roadmap_path)
+                    .or(roadmap_items::roadmap_path.is_null()),
+            ),
+        )
+        .execute(&mut conn)?;
+        Ok(deleted)
+    }
+
+    /// Record which roadmap file an item came from
+    pub fn set_roadmap_item_path(&self, change_id: &str, roadmap_path: &str) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        diesel::update(roadmap_items::table.filter(roadmap_items::change_id.eq(change_id)))
+            .set(roadmap_items::roadmap_path.eq(roadmap_path))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
     /// Get roadmap items by section
     pub fn get_roadmap_items_by_section(&self, section: &str) -> Result<Vec<RoadmapItem>> {
         let mut conn = self.get_conn()?;
@@ -1775,6 +3022,7 @@ impl Database {
     ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let before = roadmap_item_by_id(&mut conn, item_id)?;
 
         diesel::update(roadmap_items::table.filter(roadmap_items::id.eq(item_id)))
             .set((
@@ -1784,6 +3032,16 @@ impl Database {
             ))
             .execute(&mut conn)?;
 
+        if let Some(item) = before {
+            log_roadmap_change(
+                &mut conn,
+                &item.change_id,
+                "issue_state",
+                item.github_issue_state.as_deref(),
+                issue_state,
+                &now,
+            )?;
+        }
         Ok(())
     }
 
@@ -1796,6 +3054,9 @@ impl Database {
     ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let before: Vec<RoadmapItem> = roadmap_items::table
+            .filter(roadmap_items::title.eq(title))
+            .load(&mut conn)?;
 
         let affected = diesel::update(roadmap_items::table.filter(roadmap_items::title.eq(title)))
             .set((
@@ -1812,6 +3073,16 @@ impl Database {
             )));
         }
 
+        for item in &before {
+            log_roadmap_change(
+                &mut conn,
+                &item.change_id,
+                "issue_state",
+                item.github_issue_state.as_deref(),
+                Some(issue_state),
+                &now,
+            )?;
+        }
         Ok(())
     }
 
@@ -1824,6 +3095,11 @@ impl Database {
     ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let previous_state: Option<Option<String>> = roadmap_items::table
+            .filter(roadmap_items::change_id.eq(change_id))
+            .select(roadmap_items::github_issue_state)
+            .first(&mut conn)
+            .optional()?;
 
         let affected =
             diesel::update(roadmap_items::table.filter(roadmap_items::change_id.eq(change_id)))
@@ -1841,6 +3117,14 @@ impl Database {
             )));
         }
 
+        log_roadmap_change(
+            &mut conn,
+            change_id,
+            "issue_state",
+            previous_state.flatten().as_deref(),
+            Some(issue_state),
+            &now,
+        )?;
         Ok(())
     }
 
@@ -1853,6 +3137,7 @@ impl Database {
     ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let before = roadmap_item_by_id(&mut conn, item_id)?;
 
         diesel::update(roadmap_items::table.filter(roadmap_items::id.eq(item_id)))
             .set((
@@ -1862,6 +3147,16 @@ impl Database {
             ))
             .execute(&mut conn)?;
 
+        if let Some(item) = before {
+            log_roadmap_change(
+                &mut conn,
+                &item.change_id,
+                "outcome",
+                item.outcome_change_id.as_deref(),
+                Some(outcome_change_id),
+                &now,
+            )?;
+        }
         Ok(())
     }
 
@@ -1869,6 +3164,7 @@ impl Database {
     pub fn unlink_roadmap_from_outcome(&self, item_id: i32) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let before = roadmap_item_by_id(&mut conn, item_id)?;
 
         diesel::update(roadmap_items::table.filter(roadmap_items::id.eq(item_id)))
             .set((
@@ -1878,6 +3174,16 @@ impl Database {
             ))
             .execute(&mut conn)?;
 
+        if let Some(item) = before {
+            log_roadmap_change(
+                &mut conn,
+                &item.change_id,
+                "outcome",
+                item.outcome_change_id.as_deref(),
+                None,
+                &now,
+            )?;
+        }
         Ok(())
     }
 
@@ -1885,6 +3191,7 @@ impl Database {
     pub fn update_roadmap_item_checkbox(&self, item_id: i32, checkbox_state: &str) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
+        let before = roadmap_item_by_id(&mut conn, item_id)?;
 
         diesel::update(roadmap_items::table.filter(roadmap_items::id.eq(item_id)))
             .set((
@@ -1893,6 +3200,16 @@ impl Database {
             ))
             .execute(&mut conn)?;
 
+        if let Some(item) = before {
+            log_roadmap_change(
+                &mut conn,
+                &item.change_id,
+                "checkbox",
+                Some(&item.checkbox_state),
+                Some(checkbox_state),
+                &now,
+            )?;
+        }
         Ok(())
     }
 
@@ -1911,6 +3228,134 @@ impl Database {
         Ok(())
     }
 
+    /// Record the checkbox state both sides agreed on in a checkbox sync
+    ///
+    /// Creates the item (keyed by its ROADMAP.md change_id) if it isn't
+    /// tracked yet. The recorded state is the base for the next three-way
+    /// merge.
+    pub fn record_roadmap_item_synced(
+        &self,
+        change_id: &str,
+        title: &str,
+        section: Option<&str>,
+        checkbox_state: &str,
+    ) -> Result<()> {
+        self.set_roadmap_item_sync_base(
+            change_id,
+            title,
+            section,
+            checkbox_state,
+            &chrono::Local::now().to_rfc3339(),
+        )
+    }
+
+    /// Set an item's sync base with an explicit timestamp (used to carry the
+    /// base over when items are re-imported)
+    pub fn set_roadmap_item_sync_base(
+        &self,
+        change_id: &str,
+        title: &str,
+        section: Option<&str>,
+        checkbox_state: &str,
+        synced_at: &str,
+    ) -> Result<()> {
+        if self.get_roadmap_item_by_change_id(change_id)?.is_none() {
+            self.create_roadmap_item_full(
+                change_id,
+                title,
+                None,
+                section,
+                None,
+                checkbox_state,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )?;
+        }
+
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+        diesel::update(roadmap_items::table.filter(roadmap_items::change_id.eq(change_id)))
+            .set((
+                roadmap_items::synced_checkbox_state.eq(Some(checkbox_state)),
+                roadmap_items::last_synced_at.eq(Some(synced_at)),
+                roadmap_items::updated_at.eq(&now),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    /// Set the milestone and due date an item inherits from its section
+    pub fn set_roadmap_item_milestone(
+        &self,
+        change_id: &str,
+        milestone: Option<&str>,
+        due_date: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        diesel::update(roadmap_items::table.filter(roadmap_items::change_id.eq(change_id)))
+            .set((
+                roadmap_items::milestone.eq(milestone),
+                roadmap_items::due_date.eq(due_date),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    /// Set (or clear) the goal node tracking an item
+    pub fn set_roadmap_item_goal(
+        &self,
+        change_id: &str,
+        goal_change_id: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+        diesel::update(roadmap_items::table.filter(roadmap_items::change_id.eq(change_id)))
+            .set((
+                roadmap_items::goal_change_id.eq(goal_change_id),
+                roadmap_items::updated_at.eq(&now),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    /// Record a change in an item's checkbox state made outside the database
+    /// (e.g. ROADMAP.md edited between refreshes)
+    pub fn record_roadmap_checkbox_change(
+        &self,
+        change_id: &str,
+        old_state: Option<&str>,
+        new_state: &str,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let now = chrono::Local::now().to_rfc3339();
+        log_roadmap_change(
+            &mut conn,
+            change_id,
+            "checkbox",
+            old_state,
+            Some(new_state),
+            &now,
+        )
+    }
+
+    /// Full progress history of roadmap items, oldest first
+    pub fn get_roadmap_item_history(&self) -> Result<Vec<RoadmapItemHistory>> {
+        let mut conn = self.get_conn()?;
+        let history = roadmap_item_history::table
+            .order((
+                roadmap_item_history::changed_at.asc(),
+                roadmap_item_history::id.asc(),
+            ))
+            .load::<RoadmapItemHistory>(&mut conn)?;
+        Ok(history)
+    }
+
     /// Get roadmap sync state (returns None if not initialized)
     pub fn get_roadmap_sync_state(&self, roadmap_path: &str) -> Result<Option<RoadmapSyncState>> {
         let mut conn = self.get_conn()?;
@@ -2007,6 +3452,8 @@ impl Database {
             resolution: None,
             detected_at: &now,
             resolved_at: None,
+            resolved_value: None,
+            resolved_by: None,
         };
 
         diesel::insert_into(roadmap_conflicts::table)
@@ -2031,17 +3478,44 @@ impl Database {
         Ok(conflicts)
     }
 
-    /// Resolve a conflict
-    pub fn resolve_roadmap_conflict(&self, conflict_id: i32, resolution: &str) -> Result<()> {
+    /// Get a conflict by ID
+    pub fn get_roadmap_conflict(&self, conflict_id: i32) -> Result<Option<RoadmapConflict>> {
+        let mut conn = self.get_conn()?;
+        let conflict = roadmap_conflicts::table
+            .filter(roadmap_conflicts::id.eq(conflict_id))
+            .first::<RoadmapConflict>(&mut conn)
+            .optional()?;
+        Ok(conflict)
+    }
+
+    /// Resolve a conflict, recording the value applied to both sides and who
+    /// chose it
+    pub fn resolve_roadmap_conflict(
+        &self,
+        conflict_id: i32,
+        resolution: ConflictResolution,
+        value: &str,
+        actor: &str,
+    ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
 
-        diesel::update(roadmap_conflicts::table.filter(roadmap_conflicts::id.eq(conflict_id)))
-            .set((
-                roadmap_conflicts::resolution.eq(Some(resolution)),
-                roadmap_conflicts::resolved_at.eq(Some(&now)),
-            ))
-            .execute(&mut conn)?;
+        let affected =
+            diesel::update(roadmap_conflicts::table.filter(roadmap_conflicts::id.eq(conflict_id)))
+                .set((
+                    roadmap_conflicts::resolution.eq(Some(resolution.as_str())),
+                    roadmap_conflicts::resolved_at.eq(Some(&now)),
+                    roadmap_conflicts::resolved_value.eq(Some(value)),
+                    roadmap_conflicts::resolved_by.eq(Some(actor)),
+                ))
+                .execute(&mut conn)?;
+
+        if affected == 0 {
+            return Err(DbError::Validation(format!(
+                "No conflict found with id: {}",
+                conflict_id
+            )));
+        }
 
         Ok(())
     }
@@ -2074,6 +3548,9 @@ impl Database {
     // ========================================================================
 
     /// Cache a GitHub issue for local display in TUI/Web
+    ///
+    /// `etag` is the response validator, sent back as `If-None-Match` on the
+    /// next fetch of this issue; `milestone` is the milestone title.
     pub fn cache_github_issue(
         &self,
         issue_number: i32,
@@ -2084,6 +3561,8 @@ impl Database {
         html_url: &str,
         created_at: &str,
         updated_at: &str,
+        etag: Option<&str>,
+        milestone: Option<&str>,
     ) -> Result<()> {
         let mut conn = self.get_conn()?;
         let now = chrono::Local::now().to_rfc3339();
@@ -2106,6 +3585,8 @@ impl Database {
             created_at,
             updated_at,
             cached_at: &now,
+            etag,
+            milestone,
         };
 
         diesel::insert_into(github_issue_cache::table)
@@ -2170,31 +3651,537 @@ impl Database {
     }
 }
 
-// ============================================================================
-// Additional Types
-// ============================================================================
+/// Reverse a single recorded operation
+fn revert_operation(conn: &mut SqliteConnection, op: &OperationLog) -> Result<()> {
+    match op.kind() {
+        Some(OperationKind::AddNode) => {
+            let change_id = operation_target(op)?;
+            let node = decision_nodes::table
+                .filter(decision_nodes::change_id.eq(change_id))
+                .first::<DecisionNode>(conn)
+                .optional()?;
+
+            // Already gone - nothing to revert
+            let node = match node {
+                Some(n) => n,
+                None => return Ok(()),
+            };
+
+            let edge_count: i64 = decision_edges::table
+                .filter(
+                    decision_edges::from_node_id
+                        .eq(node.id)
+                        .or(decision_edges::to_node_id.eq(node.id)),
+                )
+                .count()
+                .get_result(conn)?;
+
+            if edge_count > 0 {
+                return Err(DbError::Validation(format!(
+                    "Cannot undo creation of node {}: it still has {} edge(s). Undo or remove those links first.",
+                    node.id, edge_count
+                )));
+            }
 
-/// Summary statistics from the database (kept for compatibility)
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct DbSummary {
-    pub total_nodes: i32,
-    pub total_edges: i32,
+            diesel::delete(decision_nodes::table.filter(decision_nodes::id.eq(node.id)))
+                .execute(conn)?;
+            sync_node_files(conn, node.id)?;
+            diesel::delete(node_tags::table.filter(node_tags::node_id.eq(node.id)))
+                .execute(conn)?;
+        }
+        Some(OperationKind::AddEdge) => {
+            let edge: DecisionEdge = operation_snapshot(op.after_json.as_deref())?;
+            diesel::delete(
+                decision_edges::table
+                    .filter(decision_edges::from_change_id.eq(edge.from_change_id.as_deref()))
+                    .filter(decision_edges::to_change_id.eq(edge.to_change_id.as_deref()))
+                    .filter(decision_edges::edge_type.eq(&edge.edge_type)),
+            )
+            .execute(conn)?;
+        }
+        Some(OperationKind::Status) => {
+            let before: serde_json::Value = operation_snapshot(op.before_json.as_deref())?;
+            let status = before
+                .get("status")
+                .and_then(|s| s.as_str())
+                .unwrap_or("pending");
+            set_status_by_change_id(conn, operation_target(op)?, status)?;
+        }
+        Some(OperationKind::Prompt) => {
+            let before: serde_json::Value = operation_snapshot(op.before_json.as_deref())?;
+            set_metadata_field_by_change_id(
+                conn,
+                operation_target(op)?,
+                "prompt",
+                before.get("prompt").filter(|p| !p.is_null()).cloned(),
+            )?;
+        }
+        Some(OperationKind::Fields) => {
+            let before: serde_json::Value = operation_snapshot(op.before_json.as_deref())?;
+            set_metadata_field_by_change_id(
+                conn,
+                operation_target(op)?,
+                crate::fields::FIELDS_KEY,
+                before.get("fields").filter(|f| !f.is_null()).cloned(),
+            )?;
+        }
+        None => {
+            return Err(DbError::Validation(format!(
+                "Unknown operation '{}' in operation log",
+                op.operation
+            )));
+        }
+    }
+
+    Ok(())
 }
 
-/// Alias for backwards compatibility
-pub type DbRecord = DecisionNode;
+/// Apply a previously undone operation again
+fn reapply_operation(conn: &mut SqliteConnection, op: &OperationLog) -> Result<()> {
+    match op.kind() {
+        Some(OperationKind::AddNode) => {
+            let node: DecisionNode = operation_snapshot(op.after_json.as_deref())?;
+            let exists = decision_nodes::table
+                .filter(decision_nodes::change_id.eq(&node.change_id))
+                .first::<DecisionNode>(conn)
+                .optional()?
+                .is_some();
+
+            if !exists {
+                let new_node = NewDecisionNode {
+                    change_id: &node.change_id,
+                    node_type: &node.node_type,
+                    title: &node.title,
+                    description: node.description.as_deref(),
+                    status: &node.status,
+                    created_at: &node.created_at,
+                    updated_at: &node.updated_at,
+                    metadata_json: node.metadata_json.as_deref(),
+                };
+                diesel::insert_into(decision_nodes::table)
+                    .values(&new_node)
+                    .execute(conn)?;
+
+                let id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
+                    "last_insert_rowid()",
+                ))
+                .first(conn)?;
+                sync_node_files(conn, id)?;
+            }
+        }
+        Some(OperationKind::AddEdge) => {
+            let edge: DecisionEdge = operation_snapshot(op.after_json.as_deref())?;
+            let from_id = node_id_for_change_id(conn, edge.from_change_id.as_deref())?;
+            let to_id = node_id_for_change_id(conn, edge.to_change_id.as_deref())?;
+            insert_edge_in(
+                conn,
+                from_id,
+                to_id,
+                &edge.edge_type,
+                edge.weight,
+                edge.rationale.as_deref(),
+            )?;
+        }
+        Some(OperationKind::Status) => {
+            let after: serde_json::Value = operation_snapshot(op.after_json.as_deref())?;
+            let status = after
+                .get("status")
+                .and_then(|s| s.as_str())
+                .unwrap_or("pending");
+            set_status_by_change_id(conn, operation_target(op)?, status)?;
+        }
+        Some(OperationKind::Prompt) => {
+            let after: serde_json::Value = operation_snapshot(op.after_json.as_deref())?;
+            set_metadata_field_by_change_id(
+                conn,
+                operation_target(op)?,
+                "prompt",
+                after.get("prompt").filter(|p| !p.is_null()).cloned(),
+            )?;
+        }
+        Some(OperationKind::Fields) => {
+            let after: serde_json::Value = operation_snapshot(op.after_json.as_deref())?;
+            set_metadata_field_by_change_id(
+                conn,
+                operation_target(op)?,
+                crate::fields::FIELDS_KEY,
+                after.get("fields").filter(|f| !f.is_null()).cloned(),
+            )?;
+        }
+        None => {
+            return Err(DbError::Validation(format!(
+                "Unknown operation '{}' in operation log",
+                op.operation
+            )));
+        }
+    }
 
-/// Full decision graph for serialization
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct DecisionGraph {
-    pub nodes: Vec<DecisionNode>,
-    pub edges: Vec<DecisionEdge>,
-    /// Optional config from .deciduous/config.toml (for external repo links, etc.)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub config: Option<crate::config::Config>,
+    Ok(())
 }
 
-#[cfg(test)]
+/// Resolve a node's local id from its change_id
+fn node_id_for_change_id(conn: &mut SqliteConnection, change_id: Option<&str>) -> Result<i32> {
+    let change_id = change_id.unwrap_or_default();
+
+    decision_nodes::table
+        .filter(decision_nodes::change_id.eq(change_id))
+        .select(decision_nodes::id)
+        .first::<i32>(conn)
+        .optional()?
+        .ok_or_else(|| {
+            DbError::Validation(format!(
+                "Node with change_id {} no longer exists",
+                change_id
+            ))
+        })
+}
+
+/// Set a node's status without recording an operation
+fn set_status_by_change_id(
+    conn: &mut SqliteConnection,
+    change_id: &str,
+    status: &str,
+) -> Result<()> {
+    let now = chrono::Local::now().to_rfc3339();
+
+    diesel::update(decision_nodes::table.filter(decision_nodes::change_id.eq(change_id)))
+        .set((
+            decision_nodes::status.eq(status),
+            decision_nodes::updated_at.eq(&now),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Set (or remove, when `value` is None) a metadata_json field without recording an operation
+fn set_metadata_field_by_change_id(
+    conn: &mut SqliteConnection,
+    change_id: &str,
+    key: &str,
+    value: Option<serde_json::Value>,
+) -> Result<()> {
+    let now = chrono::Local::now().to_rfc3339();
+
+    let current_meta: Option<String> = decision_nodes::table
+        .filter(decision_nodes::change_id.eq(change_id))
+        .select(decision_nodes::metadata_json)
+        .first(conn)?;
+
+    let mut meta: serde_json::Value = current_meta
+        .as_ref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    if let Some(obj) = meta.as_object_mut() {
+        match value {
+            Some(v) => {
+                obj.insert(key.to_string(), v);
+            }
+            None => {
+                obj.remove(key);
+            }
+        }
+    }
+
+    let new_meta = serde_json::to_string(&meta)
+        .map_err(|e| DbError::Validation(format!("JSON serialization error: {}", e)))?;
+
+    diesel::update(decision_nodes::table.filter(decision_nodes::change_id.eq(change_id)))
+        .set((
+            decision_nodes::metadata_json.eq(Some(new_meta)),
+            decision_nodes::updated_at.eq(&now),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Insert an edge on an existing connection (see `Database::insert_edge`)
+fn insert_edge_in(
+    conn: &mut SqliteConnection,
+    from_id: i32,
+    to_id: i32,
+    edge_type: &str,
+    weight: Option<f64>,
+    rationale: Option<&str>,
+) -> Result<i32> {
+    // Validate both nodes exist and get their change_ids
+    let from_node = decision_nodes::table
+        .filter(decision_nodes::id.eq(from_id))
+        .first::<DecisionNode>(conn)
+        .ok();
+    let to_node = decision_nodes::table
+        .filter(decision_nodes::id.eq(to_id))
+        .first::<DecisionNode>(conn)
+        .ok();
+
+    let from_change_id = from_node.as_ref().map(|n| n.change_id.clone());
+    let to_change_id = to_node.as_ref().map(|n| n.change_id.clone());
+
+    if from_node.is_none() && to_node.is_none() {
+        return Err(DbError::Validation(format!(
+            "Both nodes {} and {} do not exist. Run 'deciduous nodes' to see existing nodes.",
+            from_id, to_id
+        )));
+    } else if from_node.is_none() {
+        return Err(DbError::Validation(format!(
+            "Source node {} does not exist. Run 'deciduous nodes' to see existing nodes.",
+            from_id
+        )));
+    } else if to_node.is_none() {
+        return Err(DbError::Validation(format!(
+            "Target node {} does not exist. Run 'deciduous nodes' to see existing nodes.",
+            to_id
+        )));
+    }
+
+    let now = chrono::Local::now().to_rfc3339();
+
+    let new_edge = NewDecisionEdge {
+        from_node_id: from_id,
+        to_node_id: to_id,
+        from_change_id: from_change_id.as_deref(),
+        to_change_id: to_change_id.as_deref(),
+        edge_type,
+        weight,
+        rationale,
+        created_at: &now,
+    };
+
+    diesel::insert_into(decision_edges::table)
+        .values(&new_edge)
+        .execute(conn)?;
+
+    let id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
+        "last_insert_rowid()",
+    ))
+    .first(conn)?;
+
+    Ok(id)
+}
+
+fn operation_target(op: &OperationLog) -> Result<&str> {
+    op.target_change_id
+        .as_deref()
+        .ok_or_else(|| DbError::Validation(format!("Operation {} has no target node", op.id)))
+}
+
+/// Deserialize a before/after snapshot from the operation log
+fn operation_snapshot<T: serde::de::DeserializeOwned>(json: Option<&str>) -> Result<T> {
+    let json = json.ok_or_else(|| {
+        DbError::Validation("Operation log entry is missing its snapshot".to_string())
+    })?;
+    serde_json::from_str(json)
+        .map_err(|e| DbError::Validation(format!("Corrupt operation log snapshot: {}", e)))
+}
+
+/// Normalize a single tag: trimmed, lowercase, without a leading `#`
+///
+/// Returns `None` for tags that are empty or contain whitespace or commas.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').to_lowercase();
+    if tag.is_empty() || tag.contains(|c: char| c.is_whitespace() || c == ',') {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
+/// Split a comma-separated tag list (`--tags a,b`) into tags
+pub fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let t = normalize_tag(tag)
+            .ok_or_else(|| DbError::Validation(format!("Invalid tag: '{}'", tag)))?;
+        if !normalized.contains(&t) {
+            normalized.push(t);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Normalize a file path for the node_files index
+fn normalize_file_path(path: &str) -> String {
+    path.trim().trim_start_matches("./").to_string()
+}
+
+fn roadmap_item_by_id(conn: &mut SqliteConnection, item_id: i32) -> Result<Option<RoadmapItem>> {
+    Ok(roadmap_items::table
+        .filter(roadmap_items::id.eq(item_id))
+        .first::<RoadmapItem>(conn)
+        .optional()?)
+}
+
+/// Append to roadmap_item_history; a no-op when nothing changed
+fn log_roadmap_change(
+    conn: &mut SqliteConnection,
+    change_id: &str,
+    field: &str,
+    old_value: Option<&str>,
+    new_value: Option<&str>,
+    changed_at: &str,
+) -> Result<()> {
+    if old_value == new_value {
+        return Ok(());
+    }
+    diesel::insert_into(roadmap_item_history::table)
+        .values(&NewRoadmapItemHistory {
+            item_change_id: change_id,
+            field,
+            old_value,
+            new_value,
+            changed_at,
+        })
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Re-derive a node's node_files rows from its metadata_json.files
+///
+/// Removes the rows entirely if the node no longer exists. Returns the number
+/// of files indexed.
+fn sync_node_files(conn: &mut SqliteConnection, node_id: i32) -> Result<usize> {
+    diesel::delete(node_files::table.filter(node_files::node_id.eq(node_id))).execute(conn)?;
+
+    let meta: Option<Option<String>> = decision_nodes::table
+        .filter(decision_nodes::id.eq(node_id))
+        .select(decision_nodes::metadata_json)
+        .first(conn)
+        .optional()?;
+
+    let mut paths: Vec<String> = meta
+        .flatten()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok())
+        .and_then(|v| v.get("files").and_then(|f| f.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|f| f.as_str())
+        .map(normalize_file_path)
+        .filter(|p| !p.is_empty())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    for path in &paths {
+        diesel::insert_into(node_files::table)
+            .values((node_files::node_id.eq(node_id), node_files::path.eq(path)))
+            .execute(conn)?;
+    }
+
+    Ok(paths.len())
+}
+
+/// Union metadata_json from merged nodes into the kept node's metadata
+///
+/// `files` are unioned in order. The kept node's `commit` wins; when more than
+/// one distinct commit is involved, all of them are listed under `commits`.
+/// Any other key missing on the kept node is taken from the first dropped
+/// node that has it.
+fn merge_metadata_json<'a>(
+    keep: Option<&str>,
+    dropped: impl Iterator<Item = Option<&'a str>>,
+) -> Option<String> {
+    let parse = |m: Option<&str>| -> serde_json::Map<String, serde_json::Value> {
+        m.and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default()
+    };
+
+    let mut merged = parse(keep);
+    let mut files: Vec<String> = Vec::new();
+    let mut commits: Vec<String> = Vec::new();
+
+    let mut collect = |meta: &serde_json::Map<String, serde_json::Value>| {
+        for f in meta
+            .get("files")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|f| f.as_str())
+        {
+            if !files.iter().any(|existing| existing == f) {
+                files.push(f.to_string());
+            }
+        }
+        let listed = meta
+            .get("commits")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c.as_str());
+        for c in meta
+            .get("commit")
+            .and_then(|c| c.as_str())
+            .into_iter()
+            .chain(listed)
+        {
+            if !commits.iter().any(|existing| existing == c) {
+                commits.push(c.to_string());
+            }
+        }
+    };
+
+    collect(&merged);
+    for meta in dropped.map(parse) {
+        collect(&meta);
+        for (key, value) in meta {
+            merged.entry(key).or_insert(value);
+        }
+    }
+
+    if !files.is_empty() {
+        merged.insert("files".to_string(), json!(files));
+    }
+    if let Some(first) = commits.first() {
+        merged
+            .entry("commit".to_string())
+            .or_insert_with(|| json!(first));
+    }
+    if commits.len() > 1 {
+        merged.insert("commits".to_string(), json!(commits));
+    }
+
+    if merged.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&merged).ok()
+    }
+}
+
+// ============================================================================
+// Additional Types
+// ============================================================================
+
+/// Summary statistics from the database (kept for compatibility)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DbSummary {
+    pub total_nodes: i32,
+    pub total_edges: i32,
+}
+
+/// Alias for backwards compatibility
+pub type DbRecord = DecisionNode;
+
+/// Full decision graph for serialization
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DecisionGraph {
+    pub nodes: Vec<DecisionNode>,
+    pub edges: Vec<DecisionEdge>,
+    /// Optional config from .deciduous/config.toml (for external repo links, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<crate::config::Config>,
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -2444,4 +4431,787 @@ mod tests {
 
         assert_eq!(meta.get("commit").unwrap(), "new_commit_hash");
     }
+
+    #[test]
+    fn test_update_node_files_preserves_existing_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let node_id = db
+            .create_node("action", "Test action", None, None, Some("abc123"))
+            .unwrap();
+
+        db.update_node_files(node_id, &["src/a.rs".to_string(), "src/b.rs".to_string()])
+            .unwrap();
+
+        let nodes = db.get_all_nodes().unwrap();
+        let node = nodes.iter().find(|n| n.id == node_id).unwrap();
+        let meta: serde_json::Value =
+            serde_json::from_str(node.metadata_json.as_ref().unwrap()).unwrap();
+        assert_eq!(meta["files"], json!(["src/a.rs", "src/b.rs"]));
+        assert_eq!(meta["commit"], "abc123");
+    }
+
+    // === File Index Tests ===
+
+    #[test]
+    fn test_find_nodes_by_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let db_node = db
+            .create_node_full(
+                "action",
+                "Touch db",
+                None,
+                None,
+                None,
+                None,
+                Some("./src/db.rs, src/schema.rs"),
+                None,
+            )
+            .unwrap();
+        let tui_node = db
+            .create_node_full(
+                "action",
+                "Touch tui",
+                None,
+                None,
+                None,
+                None,
+                Some("src/tui/app.rs"),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            db.get_node_files(db_node).unwrap(),
+            vec!["src/db.rs", "src/schema.rs"]
+        );
+
+        let ids = |nodes: Vec<DecisionNode>| nodes.iter().map(|n| n.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(db.find_nodes_by_file("src/db.rs").unwrap()),
+            vec![db_node]
+        );
+        assert_eq!(
+            ids(db.find_nodes_by_file("src/tui").unwrap()),
+            vec![tui_node]
+        );
+        assert_eq!(
+            ids(db.find_nodes_by_file("src/*.rs").unwrap()),
+            vec![db_node, tui_node]
+        );
+        assert!(db.find_nodes_by_file("src/d").unwrap().is_empty());
+
+        // Updating files re-indexes the node
+        db.update_node_files(tui_node, &["src/tui/ui.rs".to_string()])
+            .unwrap();
+        assert!(db.find_nodes_by_file("src/tui/app.rs").unwrap().is_empty());
+        assert_eq!(
+            db.get_all_linked_files().unwrap(),
+            vec!["src/db.rs", "src/schema.rs", "src/tui/ui.rs"]
+        );
+    }
+
+    // === Undo/Redo Tests ===
+
+    #[test]
+    fn test_undo_add_edge_then_node() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let goal = db.create_node("goal", "Goal", None, None, None).unwrap();
+        let action = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        db.create_edge(goal, action, "leads_to", None).unwrap();
+
+        // Undo the edge, then the action node
+        let undone = db.undo_operations(2, &OperationScope::All, false).unwrap();
+        assert_eq!(undone.len(), 2);
+        assert_eq!(undone[0].kind(), Some(OperationKind::AddEdge));
+        assert_eq!(undone[1].kind(), Some(OperationKind::AddNode));
+
+        assert!(db.get_all_edges().unwrap().is_empty());
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, goal);
+    }
+
+    #[test]
+    fn test_undo_node_with_edges_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let goal = db.create_node("goal", "Goal", None, None, None).unwrap();
+        let action = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        db.create_edge(goal, action, "leads_to", None).unwrap();
+
+        // Drop the edge from the log so the node creation is the newest undoable op
+        let mut conn = db.get_conn().unwrap();
+        diesel::update(operation_log::table.filter(operation_log::operation.eq("add_edge")))
+            .set(operation_log::state.eq("discarded"))
+            .execute(&mut conn)
+            .unwrap();
+
+        let result = db.undo_operations(1, &OperationScope::All, false);
+        assert!(result.is_err());
+        assert_eq!(db.get_all_nodes().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_undo_failure_rolls_back_earlier_steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let goal = db.create_node("goal", "Goal", None, None, None).unwrap();
+        let action = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        db.create_edge(goal, action, "leads_to", None).unwrap();
+        db.update_node_status(goal, "completed").unwrap();
+
+        // The status change undoes fine, then the action node can't (it has an edge)
+        let mut conn = db.get_conn().unwrap();
+        diesel::update(operation_log::table.filter(operation_log::operation.eq("add_edge")))
+            .set(operation_log::state.eq("discarded"))
+            .execute(&mut conn)
+            .unwrap();
+
+        assert!(db.undo_operations(2, &OperationScope::All, false).is_err());
+
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(
+            nodes.iter().find(|n| n.id == goal).unwrap().status,
+            "completed"
+        );
+        let undone = db
+            .get_operations(&OperationScope::All, "undone", false, None)
+            .unwrap();
+        assert!(undone.is_empty());
+    }
+
+    #[test]
+    fn test_undo_redo_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let node_id = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        db.update_node_status(node_id, "completed").unwrap();
+
+        db.undo_operations(1, &OperationScope::All, false).unwrap();
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(
+            nodes.iter().find(|n| n.id == node_id).unwrap().status,
+            "pending"
+        );
+
+        db.redo_operations(1, &OperationScope::All, false).unwrap();
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(
+            nodes.iter().find(|n| n.id == node_id).unwrap().status,
+            "completed"
+        );
+    }
+
+    #[test]
+    fn test_undo_redo_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let node_id = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        let fields = |db: &Database| {
+            let nodes = db.get_all_nodes().unwrap();
+            let node = nodes.iter().find(|n| n.id == node_id).unwrap();
+            crate::fields::node_fields(node.metadata_json.as_deref())
+        };
+
+        let mut set = serde_json::Map::new();
+        set.insert("priority".to_string(), json!("high"));
+        db.update_node_fields(node_id, &set, &[]).unwrap();
+        db.update_node_fields(node_id, &serde_json::Map::new(), &["priority".to_string()])
+            .unwrap();
+        assert!(fields(&db).is_empty());
+
+        // Undo the unset, then the set
+        db.undo_operations(1, &OperationScope::All, false).unwrap();
+        assert_eq!(fields(&db).get("priority"), Some(&json!("high")));
+        let undone = db.undo_operations(1, &OperationScope::All, false).unwrap();
+        assert_eq!(undone[0].kind(), Some(OperationKind::Fields));
+        assert!(fields(&db).is_empty());
+
+        db.redo_operations(1, &OperationScope::All, false).unwrap();
+        assert_eq!(fields(&db).get("priority"), Some(&json!("high")));
+    }
+
+    #[test]
+    fn test_undo_dry_run_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        db.create_node("goal", "Goal", None, None, None).unwrap();
+
+        let would_undo = db.undo_operations(5, &OperationScope::All, true).unwrap();
+        assert_eq!(would_undo.len(), 1);
+        assert_eq!(db.get_all_nodes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_new_operation_discards_redo() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        db.create_node("goal", "First", None, None, None).unwrap();
+        db.undo_operations(1, &OperationScope::All, false).unwrap();
+        db.create_node("goal", "Second", None, None, None).unwrap();
+
+        let redone = db.redo_operations(1, &OperationScope::All, false).unwrap();
+        assert!(redone.is_empty());
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].title, "Second");
+    }
+
+    // === Merge Tests ===
+
+    #[test]
+    fn test_merge_nodes_rewires_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let keep = db
+            .create_node("goal", "Add auth", None, None, None)
+            .unwrap();
+        let dup = db
+            .create_node("goal", "Add authentication", None, None, None)
+            .unwrap();
+        let a = db
+            .create_node("action", "Login form", None, None, None)
+            .unwrap();
+        let b = db
+            .create_node("action", "Sessions", None, None, None)
+            .unwrap();
+
+        db.create_edge(keep, a, "leads_to", None).unwrap();
+        db.create_edge(dup, a, "leads_to", None).unwrap(); // duplicate after merge
+        db.create_edge(dup, b, "leads_to", None).unwrap(); // rewired
+        db.create_edge(keep, dup, "leads_to", None).unwrap(); // self-loop after merge
+
+        let result = db.merge_nodes(keep, &[dup]).unwrap();
+        assert_eq!(result.nodes_merged, 1);
+        assert_eq!(result.edges_rewired, 1);
+        assert_eq!(result.edges_removed, 2);
+
+        let nodes = db.get_all_nodes().unwrap();
+        assert!(!nodes.iter().any(|n| n.id == dup));
+        let keep_cid = nodes
+            .iter()
+            .find(|n| n.id == keep)
+            .unwrap()
+            .change_id
+            .clone();
+
+        let edges = db.get_all_edges().unwrap();
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| e.from_node_id == keep));
+        assert!(edges
+            .iter()
+            .all(|e| e.from_change_id.as_deref() == Some(keep_cid.as_str())));
+    }
+
+    #[test]
+    fn test_merge_nodes_unions_metadata_and_records_alias() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let keep = db
+            .create_node_full(
+                "goal",
+                "Add auth",
+                None,
+                None,
+                Some("aaa111"),
+                None,
+                Some("src/auth.rs"),
+                None,
+            )
+            .unwrap();
+        let dup = db
+            .create_node_full(
+                "goal",
+                "Add authentication",
+                None,
+                None,
+                Some("bbb222"),
+                None,
+                Some("src/auth.rs,src/session.rs"),
+                None,
+            )
+            .unwrap();
+        let dup_cid = db
+            .get_all_nodes()
+            .unwrap()
+            .into_iter()
+            .find(|n| n.id == dup)
+            .unwrap()
+            .change_id;
+
+        db.merge_nodes(keep, &[dup]).unwrap();
+
+        let node = db
+            .get_all_nodes()
+            .unwrap()
+            .into_iter()
+            .find(|n| n.id == keep)
+            .unwrap();
+        let meta: serde_json::Value =
+            serde_json::from_str(node.metadata_json.as_ref().unwrap()).unwrap();
+        assert_eq!(meta["files"], json!(["src/auth.rs", "src/session.rs"]));
+        assert_eq!(meta["commit"], "aaa111");
+        assert_eq!(meta["commits"], json!(["aaa111", "bbb222"]));
+
+        assert_eq!(db.resolve_change_id(&dup_cid).unwrap(), node.change_id);
+    }
+
+    #[test]
+    fn test_merge_nodes_rejects_self() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let keep = db.create_node("goal", "Goal", None, None, None).unwrap();
+        assert!(db.merge_nodes(keep, &[keep]).is_err());
+        assert!(db.merge_nodes(keep, &[999]).is_err());
+    }
+
+    #[test]
+    fn test_merge_nodes_discards_operations_on_dropped_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let keep = db.create_node("goal", "Goal", None, None, None).unwrap();
+        let dup = db.create_node("goal", "Goal", None, None, None).unwrap();
+        let other = db
+            .create_node("action", "Action", None, None, None)
+            .unwrap();
+        db.create_edge(dup, other, "leads_to", None).unwrap();
+        db.update_node_status(dup, "completed").unwrap();
+        db.merge_nodes(keep, &[dup]).unwrap();
+
+        // Only the creation of the kept node and the action are left to undo;
+        // the action now has an edge via the kept node, so it stays put
+        let applied = db
+            .get_operations(&OperationScope::All, "applied", false, None)
+            .unwrap();
+        assert_eq!(applied.len(), 2);
+        assert!(db.undo_operations(1, &OperationScope::All, false).is_err());
+
+        // Redo never brings the dropped node back
+        let mut conn = db.get_conn().unwrap();
+        diesel::delete(decision_edges::table)
+            .execute(&mut conn)
+            .unwrap();
+        let undone = db.undo_operations(5, &OperationScope::All, false).unwrap();
+        assert_eq!(undone.len(), 2);
+        db.redo_operations(5, &OperationScope::All, false).unwrap();
+        assert_eq!(db.get_all_nodes().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_node_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let a = db
+            .create_node("goal", "Add auth", None, None, None)
+            .unwrap();
+        let b = db
+            .create_node("goal", "Add authentication", None, None, None)
+            .unwrap();
+
+        let tags = vec!["Security".to_string(), "#tech-debt".to_string()];
+        assert_eq!(db.add_node_tags(a, &tags).unwrap(), 2);
+        assert_eq!(db.add_node_tags(a, &["security".to_string()]).unwrap(), 0);
+        assert_eq!(db.get_node_tags(a).unwrap(), vec!["security", "tech-debt"]);
+        assert!(db.add_node_tags(a, &["two words".to_string()]).is_err());
+        assert!(db.add_node_tags(999, &["x".to_string()]).is_err());
+
+        db.add_node_tags(b, &["perf".to_string(), "security".to_string()])
+            .unwrap();
+        assert_eq!(
+            db.get_tag_counts().unwrap(),
+            vec![
+                ("perf".to_string(), 1),
+                ("security".to_string(), 2),
+                ("tech-debt".to_string(), 1)
+            ]
+        );
+
+        assert_eq!(
+            db.remove_node_tags(a, &["tech-debt".to_string()]).unwrap(),
+            1
+        );
+        assert_eq!(db.get_node_tags(a).unwrap(), vec!["security"]);
+
+        // Merging carries tags over to the kept node
+        db.merge_nodes(a, &[b]).unwrap();
+        assert_eq!(db.get_node_tags(a).unwrap(), vec!["perf", "security"]);
+        assert!(!db.get_all_node_tags().unwrap().contains_key(&b));
+    }
+
+    #[test]
+    fn test_patch_carries_tags() {
+        // Each database takes a lock in its own directory
+        let source_dir = tempfile::tempdir().unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+        let source = Database::new(source_dir.path().join("a.db").to_str().unwrap()).unwrap();
+        let target = Database::new(target_dir.path().join("b.db").to_str().unwrap()).unwrap();
+
+        let id = source
+            .create_node("goal", "Tagged", None, None, None)
+            .unwrap();
+        source.add_node_tags(id, &["infra".to_string()]).unwrap();
+
+        let patch = source.export_patch(None, None, None, None).unwrap();
+        assert_eq!(patch.nodes[0].tags, vec!["infra"]);
+
+        let result = target.apply_patch(&patch, false).unwrap();
+        assert_eq!(result.tags_added, 1);
+        let node = &target.get_all_nodes().unwrap()[0];
+        assert_eq!(target.get_node_tags(node.id).unwrap(), vec!["infra"]);
+
+        // Tags added later reach nodes the target already has
+        source.add_node_tags(id, &["security".to_string()]).unwrap();
+        let patch = source.export_patch(None, None, None, None).unwrap();
+        let result = target.apply_patch(&patch, false).unwrap();
+        assert_eq!(result.nodes_skipped, 1);
+        assert_eq!(result.tags_added, 1);
+        assert_eq!(
+            target.get_node_tags(node.id).unwrap(),
+            vec!["infra", "security"]
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_resolves_merged_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let keep = db
+            .create_node("goal", "Add auth", None, None, None)
+            .unwrap();
+        let dup = db
+            .create_node("goal", "Add authentication", None, None, None)
+            .unwrap();
+        let patch = db.export_patch(Some(vec![dup]), None, None, None).unwrap();
+        db.merge_nodes(keep, &[dup]).unwrap();
+
+        // A patch node that was merged away is skipped, not re-created
+        let result = db.apply_patch(&patch, false).unwrap();
+        assert_eq!(result.nodes_added, 0);
+        assert_eq!(result.nodes_skipped, 1);
+
+        // Edges that reference the dropped change_id land on the kept node
+        let mut incoming = crate::diff::GraphPatch::new(None, None, None);
+        incoming.nodes.push(crate::diff::PatchNode {
+            change_id: "new-action".to_string(),
+            node_type: "action".to_string(),
+            title: "Login form".to_string(),
+            description: None,
+            status: "pending".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata_json: None,
+            tags: Vec::new(),
+        });
+        incoming.edges.push(crate::diff::PatchEdge {
+            from_change_id: patch.nodes[0].change_id.clone(),
+            to_change_id: "new-action".to_string(),
+            edge_type: "leads_to".to_string(),
+            weight: None,
+            rationale: None,
+        });
+        let result = db.apply_patch(&incoming, false).unwrap();
+        assert_eq!(result.edges_added, 1);
+        assert!(result.edges_failed.is_empty());
+
+        let edges = db.get_all_edges().unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from_node_id, keep);
+    }
+
+    #[test]
+    fn test_cache_github_issue_keeps_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let cache = |title: &str, etag: Option<&str>| {
+            db.cache_github_issue(
+                7,
+                "owner/repo",
+                title,
+                Some("Body"),
+                "open",
+                "https://github.com/owner/repo/issues/7",
+                "2024-01-01T00:00:00Z",
+                "2024-01-02T00:00:00Z",
+                etag,
+                None,
+            )
+            .unwrap()
+        };
+        cache("First", Some("\"abc\""));
+        cache("Second", None);
+
+        let cached = db.get_cached_issue("owner/repo", 7).unwrap().unwrap();
+        assert_eq!(cached.title, "Second");
+        assert_eq!(cached.etag, None);
+        assert_eq!(
+            db.get_cached_issues_for_repo("owner/repo").unwrap().len(),
+            1
+        );
+
+        cache("Third", Some("\"def\""));
+        let cached = db.get_cached_issue("owner/repo", 7).unwrap().unwrap();
+        assert_eq!(cached.etag.as_deref(), Some("\"def\""));
+    }
+
+    #[test]
+    fn test_record_roadmap_item_synced() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        // Untracked items are created on first sync
+        db.record_roadmap_item_synced("item-1", "Write docs", Some("Docs"), "checked")
+            .unwrap();
+        let item = db.get_roadmap_item_by_change_id("item-1").unwrap().unwrap();
+        assert_eq!(item.synced_checkbox_state.as_deref(), Some("checked"));
+        assert!(item.last_synced_at.is_some());
+
+        // Later syncs only move the base, not the local state
+        db.update_roadmap_item_checkbox(item.id, "checked").unwrap();
+        db.set_roadmap_item_sync_base("item-1", "Write docs", None, "unchecked", "2024-01-01")
+            .unwrap();
+        let item = db.get_roadmap_item_by_change_id("item-1").unwrap().unwrap();
+        assert_eq!(item.checkbox_state, "checked");
+        assert_eq!(item.synced_checkbox_state.as_deref(), Some("unchecked"));
+        assert_eq!(item.last_synced_at.as_deref(), Some("2024-01-01"));
+        assert_eq!(db.get_all_roadmap_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_roadmap_item_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let outcome = db
+            .create_node("outcome", "Shipped", None, None, None)
+            .unwrap();
+        let outcome_cid = db
+            .get_all_nodes()
+            .unwrap()
+            .into_iter()
+            .find(|n| n.id == outcome)
+            .unwrap()
+            .change_id;
+
+        let id = db
+            .create_roadmap_item("Ship it", None, Some("Beta"), None, "unchecked")
+            .unwrap();
+        db.update_roadmap_item_checkbox(id, "checked").unwrap();
+        // Unchanged values aren't history
+        db.update_roadmap_item_checkbox(id, "checked").unwrap();
+        db.link_roadmap_to_outcome(id, outcome, &outcome_cid)
+            .unwrap();
+        db.update_roadmap_item_github(id, Some(3), Some("closed"))
+            .unwrap();
+
+        let history = db.get_roadmap_item_history().unwrap();
+        let changes: Vec<(&str, Option<&str>, Option<&str>)> = history
+            .iter()
+            .map(|h| {
+                (
+                    h.field.as_str(),
+                    h.old_value.as_deref(),
+                    h.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("checkbox", Some("unchecked"), Some("checked")),
+                ("outcome", None, Some(outcome_cid.as_str())),
+                ("issue_state", None, Some("closed")),
+            ]
+        );
+
+        // History outlives a refresh of the items
+        db.clear_roadmap_items().unwrap();
+        assert_eq!(db.get_roadmap_item_history().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_clear_roadmap_items_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        for (change_id, path) in [
+            ("root", Some("ROADMAP.md")),
+            ("api", Some("packages/api/ROADMAP.md")),
+            ("legacy", None),
+        ] {
+            db.create_roadmap_item_full(
+                change_id,
+                change_id,
+                None,
+                None,
+                None,
+                "unchecked",
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            if let Some(path) = path {
+                db.set_roadmap_item_path(change_id, path).unwrap();
+            }
+        }
+
+        // Refreshing one file leaves the other file's items alone
+        assert_eq!(db.clear_roadmap_items_in("ROADMAP.md").unwrap(), 2);
+        let left = db.get_all_roadmap_items().unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].change_id, "api");
+        assert_eq!(
+            left[0].roadmap_path.as_deref(),
+            Some("packages/api/ROADMAP.md")
+        );
+    }
+
+    #[test]
+    fn test_roadmap_item_goal_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        let goal_id = db
+            .add_node("goal", "Offline mode", None, None, None)
+            .unwrap();
+        let goal = db
+            .get_all_nodes()
+            .unwrap()
+            .into_iter()
+            .find(|n| n.id == goal_id)
+            .unwrap();
+        db.record_roadmap_item_synced("item-1", "Offline mode", Some("Beta"), "unchecked")
+            .unwrap();
+
+        db.set_roadmap_item_goal("item-1", Some(&goal.change_id))
+            .unwrap();
+        db.set_node_roadmap_item(&goal.change_id, Some("item-1"))
+            .unwrap();
+
+        let item = db.get_roadmap_item_by_change_id("item-1").unwrap().unwrap();
+        assert_eq!(
+            item.goal_change_id.as_deref(),
+            Some(goal.change_id.as_str())
+        );
+        let goal = db
+            .get_all_nodes()
+            .unwrap()
+            .into_iter()
+            .find(|n| n.id == goal_id)
+            .unwrap();
+        let meta: serde_json::Value =
+            serde_json::from_str(goal.metadata_json.as_deref().unwrap()).unwrap();
+        assert_eq!(meta["roadmap_item"], "item-1");
+    }
+
+    #[test]
+    fn test_set_roadmap_item_milestone() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        db.record_roadmap_item_synced("item-1", "Ship it", Some("Beta"), "unchecked")
+            .unwrap();
+        db.set_roadmap_item_milestone("item-1", Some("v1.0"), Some("2024-06-30"))
+            .unwrap();
+        let item = db.get_roadmap_item_by_change_id("item-1").unwrap().unwrap();
+        assert_eq!(item.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(item.due_date.as_deref(), Some("2024-06-30"));
+
+        db.set_roadmap_item_milestone("item-1", None, None).unwrap();
+        let item = db.get_roadmap_item_by_change_id("item-1").unwrap().unwrap();
+        assert_eq!(item.milestone, None);
+        assert_eq!(item.due_date, None);
+    }
+
+    #[test]
+    fn test_resolve_roadmap_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).unwrap();
+
+        db.create_roadmap_item_full(
+            "item-1", "Ship it", None, None, None, "checked", None, None, None, None, None, None,
+            None,
+        )
+        .unwrap();
+        let id = db
+            .create_roadmap_conflict("item-1", "checkbox", Some("checked"), Some("unchecked"))
+            .unwrap();
+        assert_eq!(db.get_unresolved_conflicts().unwrap().len(), 1);
+
+        db.resolve_roadmap_conflict(
+            id,
+            ConflictResolution::KeepRemote,
+            "unchecked",
+            "ana@example.com",
+        )
+        .unwrap();
+        assert!(db.get_unresolved_conflicts().unwrap().is_empty());
+
+        let conflict = db.get_roadmap_conflict(id).unwrap().unwrap();
+        assert_eq!(
+            conflict.resolution_kind(),
+            Some(ConflictResolution::KeepRemote)
+        );
+        assert_eq!(conflict.resolved_value.as_deref(), Some("unchecked"));
+        assert_eq!(conflict.resolved_by.as_deref(), Some("ana@example.com"));
+        assert!(conflict.resolved_at.is_some());
+
+        assert!(db
+            .resolve_roadmap_conflict(999, ConflictResolution::KeepLocal, "checked", "ana")
+            .is_err());
+    }
 }
@@ -5,9 +5,12 @@
 
 use crate::db::{Database, DecisionEdge, DecisionNode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Metadata key recording who signed the patch that introduced a node
+pub const SIGNED_BY_KEY: &str = "signed_by";
+
 /// A patch file containing nodes and edges to sync
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphPatch {
@@ -25,10 +28,30 @@ pub struct GraphPatch {
     pub nodes: Vec<PatchNode>,
     /// Edges included in this patch
     pub edges: Vec<PatchEdge>,
+    /// Identity that signed this patch (a principal in allowed_signers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// SSH signature over the patch, computed with this field unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Outcome of checking a patch's signature
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchVerification {
+    /// The patch carries no signature
+    Unsigned,
+    /// Signed by this identity, which is in allowed_signers
+    Verified(String),
+    /// A valid signature, but its signer isn't in allowed_signers (or there
+    /// is no allowed_signers file)
+    UnknownSigner(String),
+    /// Signature missing its signer or not matching the patch contents
+    Invalid(String),
 }
 
 /// A node in a patch file (uses change_id, not integer id)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatchNode {
     /// Globally unique change ID
     pub change_id: String,
@@ -44,10 +67,13 @@ pub struct PatchNode {
     pub metadata_json: Option<String>,
     /// Created timestamp
     pub created_at: String,
+    /// Tags on the node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 /// An edge in a patch file (uses change_ids for references)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatchEdge {
     /// Source node change_id
     pub from_change_id: String,
@@ -55,6 +81,9 @@ pub struct PatchEdge {
     pub to_change_id: String,
     /// Edge type: leads_to, chosen, etc.
     pub edge_type: String,
+    /// Edge weight (patches written before weights were exported omit it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
     /// Optional rationale for the edge
     pub rationale: Option<String>,
 }
@@ -74,6 +103,8 @@ impl GraphPatch {
             base_commit,
             nodes: Vec::new(),
             edges: Vec::new(),
+            signer: None,
+            signature: None,
         }
     }
 
@@ -98,6 +129,116 @@ impl GraphPatch {
         std::fs::write(path, content).map_err(|e| format!("Failed to write patch file: {}", e))
     }
 
+    /// Bytes covered by the signature: the compact JSON of the patch
+    /// without its `signature` field
+    pub fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let unsigned = GraphPatch {
+            signature: None,
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned).map_err(|e| format!("Failed to serialize patch: {}", e))
+    }
+
+    /// Sign the patch with an SSH private key as `signer`
+    pub fn sign(&mut self, key: &Path, signer: &str) -> Result<(), String> {
+        self.signer = Some(signer.to_string());
+        self.signature = None;
+        let payload = self.signing_payload()?;
+        self.signature = Some(crate::signing::sign(&payload, key)?);
+        Ok(())
+    }
+
+    /// Check the signature against an allowed_signers file
+    pub fn verify(&self, allowed_signers: &Path) -> PatchVerification {
+        let Some(signature) = &self.signature else {
+            return PatchVerification::Unsigned;
+        };
+        let Some(signer) = &self.signer else {
+            return PatchVerification::Invalid("Signature has no signer".to_string());
+        };
+        let checked = self.signing_payload().and_then(|payload| {
+            crate::signing::verify(&payload, signature, signer, allowed_signers)
+        });
+        let Err(e) = checked else {
+            return PatchVerification::Verified(signer.clone());
+        };
+        // A signature that holds up without allowed_signers is intact, just
+        // not from anyone we trust
+        let intact = self
+            .signing_payload()
+            .and_then(|payload| crate::signing::check_signature(&payload, signature))
+            .is_ok();
+        if intact {
+            PatchVerification::UnknownSigner(format!("{} (signer: {})", e, signer))
+        } else {
+            PatchVerification::Invalid(format!("{} (signer: {})", e, signer))
+        }
+    }
+
+    /// Merge patches into one, keeping each node and edge once
+    ///
+    /// Patches are taken oldest first (by `created_at`), and a later copy of a
+    /// node or edge replaces an earlier one in place; node tags are unioned.
+    /// Author and branch are kept when all patches agree, and the base commit
+    /// is the oldest patch's. Signatures are not carried over.
+    pub fn squash(patches: &[GraphPatch]) -> GraphPatch {
+        let mut ordered: Vec<&GraphPatch> = patches.iter().collect();
+        ordered.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+        let mut squashed = GraphPatch::new(
+            common_value(ordered.iter().map(|p| &p.author)),
+            common_value(ordered.iter().map(|p| &p.branch)),
+            ordered.first().and_then(|p| p.base_commit.clone()),
+        );
+
+        let mut node_index: HashMap<String, usize> = HashMap::new();
+        let mut edge_index: HashMap<(String, String, String), usize> = HashMap::new();
+        for patch in &ordered {
+            for node in &patch.nodes {
+                match node_index.get(&node.change_id) {
+                    Some(&i) => {
+                        let mut tags = std::mem::take(&mut squashed.nodes[i].tags);
+                        for tag in &node.tags {
+                            if !tags.contains(tag) {
+                                tags.push(tag.clone());
+                            }
+                        }
+                        squashed.nodes[i] = PatchNode {
+                            tags,
+                            ..node.clone()
+                        };
+                    }
+                    None => {
+                        node_index.insert(node.change_id.clone(), squashed.nodes.len());
+                        squashed.nodes.push(node.clone());
+                    }
+                }
+            }
+            for edge in &patch.edges {
+                match edge_index.get(&edge.key()) {
+                    Some(&i) => squashed.edges[i] = edge.clone(),
+                    None => {
+                        edge_index.insert(edge.key(), squashed.edges.len());
+                        squashed.edges.push(edge.clone());
+                    }
+                }
+            }
+        }
+
+        squashed
+    }
+
+    /// True if every node and edge in the patch is already in `graph`
+    pub fn is_contained_in(&self, graph: &GraphContents) -> bool {
+        self.nodes
+            .iter()
+            .all(|n| graph.change_ids.contains(&n.change_id))
+            && self
+                .edges
+                .iter()
+                .all(|e| graph.edge_keys.contains(&e.key()))
+    }
+
     /// Add a node to the patch
     pub fn add_node(&mut self, node: &DecisionNode) {
         self.nodes.push(PatchNode {
@@ -108,6 +249,7 @@ impl GraphPatch {
             status: node.status.clone(),
             metadata_json: node.metadata_json.clone(),
             created_at: node.created_at.clone(),
+            tags: Vec::new(),
         });
     }
 
@@ -118,12 +260,62 @@ impl GraphPatch {
                 from_change_id: from_cid.clone(),
                 to_change_id: to_cid.clone(),
                 edge_type: edge.edge_type.clone(),
+                weight: edge.weight,
                 rationale: edge.rationale.clone(),
             });
         }
     }
 }
 
+impl PatchEdge {
+    /// Identity of the edge across databases: (from, to, edge_type)
+    pub fn key(&self) -> (String, String, String) {
+        (
+            self.from_change_id.clone(),
+            self.to_change_id.clone(),
+            self.edge_type.clone(),
+        )
+    }
+}
+
+/// Node change_ids and edge keys of an exported graph (`graph-data.json`)
+#[derive(Debug, Default)]
+pub struct GraphContents {
+    pub change_ids: HashSet<String>,
+    pub edge_keys: HashSet<(String, String, String)>,
+}
+
+impl GraphContents {
+    /// Read from `deciduous sync` output
+    ///
+    /// Only change_ids are looked at, so exports from older versions with
+    /// different node fields still load.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Failed to parse graph JSON: {}", e))?;
+        let str_field = |v: &serde_json::Value, key: &str| {
+            v.get(key).and_then(|s| s.as_str()).map(|s| s.to_string())
+        };
+
+        let mut contents = GraphContents::default();
+        for node in value["nodes"].as_array().into_iter().flatten() {
+            if let Some(change_id) = str_field(node, "change_id") {
+                contents.change_ids.insert(change_id);
+            }
+        }
+        for edge in value["edges"].as_array().into_iter().flatten() {
+            if let (Some(from), Some(to), Some(edge_type)) = (
+                str_field(edge, "from_change_id"),
+                str_field(edge, "to_change_id"),
+                str_field(edge, "edge_type"),
+            ) {
+                contents.edge_keys.insert((from, to, edge_type));
+            }
+        }
+        Ok(contents)
+    }
+}
+
 /// Result of applying a patch
 #[derive(Debug, Default)]
 pub struct ApplyResult {
@@ -137,6 +329,8 @@ pub struct ApplyResult {
     pub edges_skipped: usize,
     /// Edges that couldn't be created (missing nodes)
     pub edges_failed: Vec<String>,
+    /// Number of tags added to new or existing nodes
+    pub tags_added: usize,
 }
 
 impl Database {
@@ -150,6 +344,7 @@ impl Database {
     ) -> Result<GraphPatch, crate::db::DbError> {
         let all_nodes = self.get_all_nodes()?;
         let all_edges = self.get_all_edges()?;
+        let mut all_tags = self.get_all_node_tags()?;
 
         // Get current branch for patch metadata
         let current_branch = crate::db::get_current_git_branch();
@@ -188,6 +383,9 @@ impl Database {
         // Add nodes to patch
         for node in &nodes {
             patch.add_node(node);
+            if let (Some(added), Some(tags)) = (patch.nodes.last_mut(), all_tags.remove(&node.id)) {
+                added.tags = tags;
+            }
         }
 
         // Add edges where BOTH endpoints are in the patch
@@ -210,6 +408,19 @@ impl Database {
         &self,
         patch: &GraphPatch,
         dry_run: bool,
+    ) -> Result<ApplyResult, crate::db::DbError> {
+        self.apply_patch_signed_by(patch, dry_run, None)
+    }
+
+    /// Apply a patch whose signature has been verified as `signer`
+    ///
+    /// The signer is recorded as `signed_by` in the metadata of every node the
+    /// patch introduces, replacing any `signed_by` the patch itself carries.
+    pub fn apply_patch_signed_by(
+        &self,
+        patch: &GraphPatch,
+        dry_run: bool,
+        signer: Option<&str>,
     ) -> Result<ApplyResult, crate::db::DbError> {
         let mut result = ApplyResult::default();
 
@@ -224,78 +435,71 @@ impl Database {
             .map(|n| (n.change_id.clone(), n.id))
             .collect();
 
+        // change_ids of nodes merged away locally -> the node they were merged into
+        let aliases: std::collections::HashMap<String, String> = self
+            .get_node_aliases()?
+            .into_iter()
+            .map(|a| (a.alias_change_id, a.change_id))
+            .collect();
+        let resolve = |change_id: &String| -> String {
+            aliases
+                .get(change_id)
+                .cloned()
+                .unwrap_or_else(|| change_id.clone())
+        };
+
+        let existing_tags = self.get_all_node_tags()?;
+
         // Apply nodes
         for patch_node in &patch.nodes {
-            if existing_change_ids.contains(&patch_node.change_id) {
+            if existing_change_ids.contains(&patch_node.change_id)
+                || aliases.contains_key(&patch_node.change_id)
+            {
                 result.nodes_skipped += 1;
+
+                // Tags are additive, so teammates' tags still land on nodes
+                // we already have
+                if let Some(&local_id) = change_id_to_local_id.get(&resolve(&patch_node.change_id))
+                {
+                    let current = existing_tags.get(&local_id);
+                    let new_tags: Vec<String> = patch_node
+                        .tags
+                        .iter()
+                        .filter(|t| current.map_or(true, |c| !c.contains(t)))
+                        .cloned()
+                        .collect();
+                    if !new_tags.is_empty() {
+                        result.tags_added += if dry_run {
+                            new_tags.len()
+                        } else {
+                            self.add_node_tags(local_id, &new_tags)?
+                        };
+                    }
+                }
                 continue;
             }
 
             if !dry_run {
-                // Get branch from metadata
-                let branch = patch_node
-                    .metadata_json
-                    .as_ref()
-                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
-                    .and_then(|j| {
-                        j.get("branch")
-                            .and_then(|b| b.as_str())
-                            .map(|s| s.to_string())
-                    });
-
-                let confidence = patch_node
-                    .metadata_json
-                    .as_ref()
-                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
-                    .and_then(|j| {
-                        j.get("confidence")
-                            .and_then(|c| c.as_u64())
-                            .map(|c| c as u8)
-                    });
-
-                let prompt = patch_node
-                    .metadata_json
-                    .as_ref()
-                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
-                    .and_then(|j| {
-                        j.get("prompt")
-                            .and_then(|p| p.as_str())
-                            .map(|s| s.to_string())
-                    });
-
-                let files = patch_node
-                    .metadata_json
-                    .as_ref()
-                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
-                    .and_then(|j| {
-                        j.get("files").and_then(|f| {
-                            if let Some(arr) = f.as_array() {
-                                Some(
-                                    arr.iter()
-                                        .filter_map(|v| v.as_str())
-                                        .collect::<Vec<_>>()
-                                        .join(","),
-                                )
-                            } else {
-                                None
-                            }
-                        })
-                    });
+                let metadata = with_signer(patch_node.metadata_json.as_deref(), signer);
 
-                // Create node with explicit change_id
-                let local_id = self.create_node_with_change_id(
+                // Keep the node as the author recorded it: commit links, custom
+                // fields and timestamps all live in these columns
+                let local_id = self.import_node(
                     &patch_node.change_id,
                     &patch_node.node_type,
                     &patch_node.title,
                     patch_node.description.as_deref(),
-                    confidence,
-                    None, // commit
-                    prompt.as_deref(),
-                    files.as_deref(),
-                    branch.as_deref(),
+                    &patch_node.status,
+                    &patch_node.created_at,
+                    metadata.as_deref(),
                 )?;
 
                 change_id_to_local_id.insert(patch_node.change_id.clone(), local_id);
+                if !patch_node.tags.is_empty() {
+                    result.tags_added += self.add_node_tags(local_id, &patch_node.tags)?;
+                }
+            } else {
+                result.tags_added += patch_node.tags.len();
             }
 
             result.nodes_added += 1;
@@ -303,7 +507,7 @@ impl Database {
 
         // Get existing edges (by change_id pairs)
         let existing_edges = self.get_all_edges()?;
-        let existing_edge_keys: HashSet<(String, String, String)> = existing_edges
+        let mut existing_edge_keys: HashSet<(String, String, String)> = existing_edges
             .iter()
             .filter_map(|e| match (&e.from_change_id, &e.to_change_id) {
                 (Some(from), Some(to)) => Some((from.clone(), to.clone(), e.edge_type.clone())),
@@ -313,31 +517,36 @@ impl Database {
 
         // Apply edges
         for patch_edge in &patch.edges {
+            let from_change_id = resolve(&patch_edge.from_change_id);
+            let to_change_id = resolve(&patch_edge.to_change_id);
             let edge_key = (
-                patch_edge.from_change_id.clone(),
-                patch_edge.to_change_id.clone(),
+                from_change_id.clone(),
+                to_change_id.clone(),
                 patch_edge.edge_type.clone(),
             );
 
-            if existing_edge_keys.contains(&edge_key) {
+            // An edge between two nodes that were merged together collapses away
+            if existing_edge_keys.contains(&edge_key) || from_change_id == to_change_id {
                 result.edges_skipped += 1;
                 continue;
             }
 
             // Look up local IDs
-            let from_id = change_id_to_local_id.get(&patch_edge.from_change_id);
-            let to_id = change_id_to_local_id.get(&patch_edge.to_change_id);
+            let from_id = change_id_to_local_id.get(&from_change_id);
+            let to_id = change_id_to_local_id.get(&to_change_id);
 
             match (from_id, to_id) {
                 (Some(&from), Some(&to)) => {
                     if !dry_run {
-                        self.create_edge(
+                        self.insert_edge(
                             from,
                             to,
                             &patch_edge.edge_type,
+                            patch_edge.weight.or(Some(1.0)),
                             patch_edge.rationale.as_deref(),
                         )?;
                     }
+                    existing_edge_keys.insert(edge_key);
                     result.edges_added += 1;
                 }
                 _ => {
@@ -354,6 +563,35 @@ impl Database {
     }
 }
 
+/// The value all items share, or None if they differ
+fn common_value<'a>(mut values: impl Iterator<Item = &'a Option<String>>) -> Option<String> {
+    let first = values.next()?.clone();
+    values.all(|v| *v == first).then_some(first).flatten()
+}
+
+/// Set `signed_by` in node metadata to the verified signer
+///
+/// Whatever `signed_by` the patch carries is dropped first, so an unsigned
+/// patch can't claim a signer.
+fn with_signer(metadata_json: Option<&str>, signer: Option<&str>) -> Option<String> {
+    if metadata_json.is_none() && signer.is_none() {
+        return None;
+    }
+    let mut meta: serde_json::Value = metadata_json
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    let Some(obj) = meta.as_object_mut() else {
+        return metadata_json.map(|m| m.to_string());
+    };
+    if obj.remove(SIGNED_BY_KEY).is_none() && signer.is_none() {
+        return metadata_json.map(|m| m.to_string());
+    }
+    if let Some(signer) = signer {
+        obj.insert(SIGNED_BY_KEY.to_string(), serde_json::json!(signer));
+    }
+    serde_json::to_string(&meta).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,6 +705,191 @@ mod tests {
         assert_eq!(restored.edges[0].from_change_id, "cid-1");
     }
 
+    #[test]
+    fn test_apply_patch_preserves_custom_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+
+        let mut node = sample_node(1, "cid-1", "action", "Add cache");
+        node.metadata_json = Some(
+            r#"{"branch": "main", "fields": {"risk": "high", "cost_estimate": 3}}"#.to_string(),
+        );
+        let mut patch = GraphPatch::new(None, None, None);
+        patch.add_node(&node);
+
+        db.apply_patch(&patch, false).unwrap();
+        let applied = &db.get_all_nodes().unwrap()[0];
+        let fields = crate::fields::node_fields(applied.metadata_json.as_deref());
+        assert_eq!(fields["risk"], serde_json::json!("high"));
+        assert_eq!(fields["cost_estimate"], serde_json::json!(3));
+    }
+
+    #[test]
+    fn test_apply_patch_preserves_node_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+
+        let mut goal = sample_node(1, "cid-1", "goal", "Ship sync");
+        goal.status = "completed".to_string();
+        let mut action = sample_node(2, "cid-2", "action", "Write patch format");
+        action.created_at = "2024-01-02T00:00:00Z".to_string();
+        action.metadata_json =
+            Some(r#"{"branch": "main", "commit": "abc1234", "reviewer": "bob"}"#.to_string());
+        let mut edge = sample_edge(1, 1, 2, "cid-1", "cid-2");
+        edge.weight = Some(0.5);
+
+        let mut patch = GraphPatch::new(None, None, None);
+        patch.add_node(&goal);
+        patch.add_node(&action);
+        patch.add_edge(&edge);
+        db.apply_patch(&patch, false).unwrap();
+
+        let nodes = db.get_all_nodes().unwrap();
+        assert_eq!(nodes[0].status, "completed");
+        assert_eq!(nodes[0].created_at, "2024-01-01T00:00:00Z");
+        assert_eq!(nodes[1].metadata_json, action.metadata_json);
+        assert_eq!(db.get_all_edges().unwrap()[0].weight, Some(0.5));
+    }
+
+    #[test]
+    fn test_patch_edge_weight_optional() {
+        let json = r#"{"from_change_id": "a", "to_change_id": "b",
+                       "edge_type": "leads_to", "rationale": null}"#;
+        let edge: PatchEdge = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(edge.weight, None);
+    }
+
+    #[test]
+    fn test_signing_payload_excludes_signature() {
+        let mut patch = GraphPatch::new(Some("alice".to_string()), None, None);
+        patch.add_node(&sample_node(1, "cid-1", "goal", "Goal"));
+        patch.signer = Some("alice@example.com".to_string());
+        let unsigned = patch.signing_payload().unwrap();
+
+        patch.signature = Some("sig".to_string());
+        assert_eq!(patch.signing_payload().unwrap(), unsigned);
+
+        // The signer is covered, so it can't be swapped after signing
+        patch.signer = Some("mallory@example.com".to_string());
+        assert_ne!(patch.signing_payload().unwrap(), unsigned);
+
+        patch.signature = None;
+        assert_eq!(
+            patch.verify(Path::new("allowed_signers")),
+            PatchVerification::Unsigned
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_records_signer() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+
+        let mut patch = GraphPatch::new(None, None, None);
+        patch.add_node(&sample_node(1, "cid-1", "goal", "Goal"));
+        db.apply_patch_signed_by(&patch, false, Some("alice@example.com"))
+            .unwrap();
+
+        let node = &db.get_all_nodes().unwrap()[0];
+        let meta: serde_json::Value =
+            serde_json::from_str(node.metadata_json.as_deref().unwrap()).unwrap();
+        assert_eq!(meta[SIGNED_BY_KEY], "alice@example.com");
+        assert_eq!(meta["confidence"], 90);
+
+        // A signer claimed in the patch is replaced by the verified one, or
+        // dropped when there is none
+        let claimed = Some(r#"{"signed_by": "bob", "confidence": 90}"#);
+        assert_eq!(
+            with_signer(claimed, Some("alice")).as_deref(),
+            Some(r#"{"confidence":90,"signed_by":"alice"}"#)
+        );
+        assert_eq!(
+            with_signer(claimed, None).as_deref(),
+            Some(r#"{"confidence":90}"#)
+        );
+        assert_eq!(with_signer(None, None), None);
+    }
+
+    #[test]
+    fn test_squash_dedupes_nodes_and_edges() {
+        let mut older = GraphPatch::new(Some("alice".to_string()), None, Some("aaa".to_string()));
+        older.created_at = "2024-01-01T00:00:00Z".to_string();
+        older.add_node(&sample_node(1, "cid-1", "goal", "Goal"));
+        older.add_node(&sample_node(2, "cid-2", "action", "Draft"));
+        older.nodes[0].tags = vec!["auth".to_string()];
+        older.add_edge(&sample_edge(1, 1, 2, "cid-1", "cid-2"));
+
+        let mut newer = GraphPatch::new(Some("alice".to_string()), None, Some("bbb".to_string()));
+        newer.created_at = "2024-01-02T00:00:00Z".to_string();
+        let mut goal = sample_node(1, "cid-1", "goal", "Goal");
+        goal.status = "completed".to_string();
+        newer.add_node(&goal);
+        newer.nodes[0].tags = vec!["perf".to_string()];
+        newer.add_node(&sample_node(3, "cid-3", "outcome", "Shipped"));
+        let mut edge = sample_edge(1, 1, 2, "cid-1", "cid-2");
+        edge.rationale = Some("updated".to_string());
+        newer.add_edge(&edge);
+        newer.add_edge(&sample_edge(2, 2, 3, "cid-2", "cid-3"));
+
+        // Argument order doesn't matter; created_at does
+        let squashed = GraphPatch::squash(&[newer, older]);
+        let ids: Vec<&str> = squashed
+            .nodes
+            .iter()
+            .map(|n| n.change_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["cid-1", "cid-2", "cid-3"]);
+        assert_eq!(squashed.nodes[0].status, "completed");
+        assert_eq!(squashed.nodes[0].tags, vec!["auth", "perf"]);
+        assert_eq!(squashed.edges.len(), 2);
+        assert_eq!(squashed.edges[0].rationale.as_deref(), Some("updated"));
+        assert_eq!(squashed.author.as_deref(), Some("alice"));
+        assert_eq!(squashed.base_commit.as_deref(), Some("aaa"));
+        assert!(squashed.signature.is_none());
+    }
+
+    #[test]
+    fn test_patch_contained_in_graph() {
+        let graph = GraphContents::from_json(
+            r#"{"nodes": [{"id": 1, "change_id": "cid-1"}, {"id": 2, "change_id": "cid-2"}],
+                "edges": [{"from_change_id": "cid-1", "to_change_id": "cid-2",
+                           "edge_type": "leads_to"}]}"#,
+        )
+        .unwrap();
+
+        let mut patch = GraphPatch::new(None, None, None);
+        patch.add_node(&sample_node(1, "cid-1", "goal", "Goal"));
+        patch.add_node(&sample_node(2, "cid-2", "action", "Act"));
+        patch.add_edge(&sample_edge(1, 1, 2, "cid-1", "cid-2"));
+        assert!(patch.is_contained_in(&graph));
+
+        patch.edges[0].edge_type = "chosen".to_string();
+        assert!(!patch.is_contained_in(&graph));
+
+        patch.edges.clear();
+        patch.add_node(&sample_node(3, "cid-3", "outcome", "Done"));
+        assert!(!patch.is_contained_in(&graph));
+
+        assert!(GraphContents::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_patch_tags_optional() {
+        // Patches written before tags existed still load
+        let json = r#"{"version": "1.0", "author": null, "branch": null,
+            "created_at": "2024-01-01T00:00:00Z", "base_commit": null,
+            "nodes": [{"change_id": "cid-1", "node_type": "goal", "title": "Goal",
+                       "description": null, "status": "pending", "metadata_json": null,
+                       "created_at": "2024-01-01T00:00:00Z"}],
+            "edges": []}"#;
+        let patch: GraphPatch = serde_json::from_str(json).expect("deserialize");
+        assert!(patch.nodes[0].tags.is_empty());
+
+        // Untagged nodes don't write an empty list
+        let out = serde_json::to_string(&patch).unwrap();
+        assert!(!out.contains("tags"));
+    }
+
     #[test]
     fn test_patch_json_format() {
         let patch = GraphPatch::new(Some("alice".to_string()), None, None);
@@ -566,3 +989,118 @@ mod tests {
         );
     }
 }
+
+// =============================================================================
+// Property-Based Tests (Proptest)
+// =============================================================================
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const NODE_TYPES: &[&str] = &["goal", "decision", "option", "action", "outcome"];
+    const STATUSES: &[&str] = &["pending", "active", "completed", "rejected"];
+    const EDGE_TYPES: &[&str] = &["leads_to", "chosen", "rejected"];
+
+    type NodeSpec = (usize, String, usize, Option<u8>, Option<String>, [bool; 3]);
+
+    fn node_spec() -> impl Strategy<Value = NodeSpec> {
+        (
+            0..NODE_TYPES.len(),
+            "[A-Za-z][A-Za-z ]{0,20}",
+            0..STATUSES.len(),
+            proptest::option::of(0..=100u8),
+            proptest::option::of("[0-9a-f]{7,40}"),
+            any::<[bool; 3]>(),
+        )
+    }
+
+    fn build_patch(nodes: &[NodeSpec], edges: &[(usize, usize, usize, u8)]) -> GraphPatch {
+        let mut patch = GraphPatch::new(None, None, None);
+
+        for (i, (type_idx, title, status_idx, confidence, commit, tags)) in nodes.iter().enumerate()
+        {
+            let mut meta = serde_json::json!({ "branch": "main", "fields": { "rank": i } });
+            if let Some(c) = confidence {
+                meta["confidence"] = serde_json::json!(c);
+            }
+            if let Some(c) = commit {
+                meta["commit"] = serde_json::json!(c);
+            }
+            patch.nodes.push(PatchNode {
+                change_id: format!("cid-{}", i),
+                node_type: NODE_TYPES[*type_idx].to_string(),
+                title: title.clone(),
+                description: None,
+                status: STATUSES[*status_idx].to_string(),
+                metadata_json: Some(meta.to_string()),
+                created_at: format!("2024-01-01T00:{:02}:00Z", i),
+                tags: ["auth", "perf", "q3"]
+                    .iter()
+                    .zip(tags)
+                    .filter(|(_, on)| **on)
+                    .map(|(t, _)| t.to_string())
+                    .collect(),
+            });
+        }
+
+        let mut seen = HashSet::new();
+        for &(from, to, type_idx, weight) in edges {
+            let (from, to) = (from % nodes.len(), to % nodes.len());
+            if from == to || !seen.insert((from, to, type_idx)) {
+                continue;
+            }
+            patch.edges.push(PatchEdge {
+                from_change_id: format!("cid-{}", from),
+                to_change_id: format!("cid-{}", to),
+                edge_type: EDGE_TYPES[type_idx].to_string(),
+                weight: Some(f64::from(weight) / 10.0),
+                rationale: None,
+            });
+        }
+
+        patch
+    }
+
+    /// Apply a patch to a fresh database and export everything back out
+    fn apply_and_export(patch: &GraphPatch) -> GraphPatch {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+        db.apply_patch(patch, false).unwrap();
+        sorted(db.export_patch(None, None, None, None).unwrap())
+    }
+
+    /// Nodes by change_id and edges by key, so patches compare field by field
+    fn sorted(mut patch: GraphPatch) -> GraphPatch {
+        patch.nodes.sort_by(|a, b| a.change_id.cmp(&b.change_id));
+        patch.edges.sort_by_key(PatchEdge::key);
+        patch
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(24))]
+
+        #[test]
+        fn prop_patch_round_trip(
+            nodes in proptest::collection::vec(node_spec(), 1..8),
+            edges in proptest::collection::vec(
+                (0..8usize, 0..8usize, 0..EDGE_TYPES.len(), 0..=20u8),
+                0..12,
+            ),
+        ) {
+            let original = sorted(build_patch(&nodes, &edges));
+
+            // export -> apply -> export changes nothing
+            let first = apply_and_export(&original);
+            let second = apply_and_export(&first);
+            prop_assert_eq!(&first.nodes, &second.nodes);
+            prop_assert_eq!(&first.edges, &second.edges);
+
+            // and nothing in the original patch was dropped on the way in:
+            // status, metadata, timestamps, tags, edge weights
+            prop_assert_eq!(&first.nodes, &original.nodes);
+            prop_assert_eq!(&first.edges, &original.edges);
+        }
+    }
+}
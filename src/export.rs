@@ -83,6 +83,7 @@ fn edge_style(edge_type: &str) -> &'static str {
         "chosen" => "bold",
         "rejected" => "dashed",
         "blocks" => "dotted",
+        "supersedes" => "dashed",
         _ => "solid",
     }
 }
@@ -90,11 +91,12 @@ fn edge_style(edge_type: &str) -> &'static str {
 /// Get the edge color based on edge type
 fn edge_color(edge_type: &str) -> &'static str {
     match edge_type {
-        "chosen" => "#228B22",   // Forest green
-        "rejected" => "#DC143C", // Crimson
-        "blocks" => "#FF4500",   // Orange red
-        "enables" => "#4169E1",  // Royal blue
-        _ => "#333333",          // Dark gray
+        "chosen" => "#228B22",     // Forest green
+        "rejected" => "#DC143C",   // Crimson
+        "blocks" => "#FF4500",     // Orange red
+        "enables" => "#4169E1",    // Royal blue
+        "supersedes" => "#808080", // Gray
+        _ => "#333333",            // Dark gray
     }
 }
 
@@ -138,6 +140,19 @@ fn extract_commit(metadata: &Option<String>) -> Option<String> {
     })
 }
 
+/// Custom fields as a " _(name: value, ...)_" suffix, or empty
+fn fields_badge(metadata: &Option<String>) -> String {
+    let fields = crate::fields::node_fields(metadata.as_deref());
+    if fields.is_empty() {
+        return String::new();
+    }
+    let parts: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, crate::fields::format_field_value(value)))
+        .collect();
+    format!(" _({})_", parts.join(", "))
+}
+
 /// Convert a decision graph to DOT format
 pub fn graph_to_dot(graph: &DecisionGraph, config: &DotConfig) -> String {
     let mut dot = String::new();
@@ -439,7 +454,13 @@ pub fn generate_pr_writeup(graph: &DecisionGraph, config: &WriteupConfig) -> Str
                 .map(|c| format!(" `{}`", &c[..7.min(c.len())]))
                 .unwrap_or_default();
 
-            wln!(writeup, "- {}{}", action.title, commit_badge);
+            wln!(
+                writeup,
+                "- {}{}{}",
+                action.title,
+                commit_badge,
+                fields_badge(&action.metadata_json)
+            );
         }
         wln!(writeup);
     }
@@ -460,7 +481,13 @@ pub fn generate_pr_writeup(graph: &DecisionGraph, config: &WriteupConfig) -> Str
                 .map(|c| format!(" ({}% confidence)", c))
                 .unwrap_or_default();
 
-            wln!(writeup, "- {}{}", outcome.title, conf_badge);
+            wln!(
+                writeup,
+                "- {}{}{}",
+                outcome.title,
+                conf_badge,
+                fields_badge(&outcome.metadata_json)
+            );
         }
         wln!(writeup);
     }
@@ -659,6 +686,13 @@ mod tests {
         assert!(writeup.contains("```dot"));
     }
 
+    #[test]
+    fn test_fields_badge() {
+        let meta = Some(r#"{"fields": {"risk": "low", "cost_estimate": 3}}"#.to_string());
+        assert_eq!(fields_badge(&meta), " _(cost_estimate: 3, risk: low)_");
+        assert_eq!(fields_badge(&None), "");
+    }
+
     #[test]
     fn test_extract_confidence() {
         let meta = Some(r#"{"confidence":85}"#.to_string());
@@ -704,6 +738,7 @@ mod tests {
         assert_eq!(edge_style("chosen"), "bold");
         assert_eq!(edge_style("rejected"), "dashed");
         assert_eq!(edge_style("blocks"), "dotted");
+        assert_eq!(edge_style("supersedes"), "dashed");
         assert_eq!(edge_style("unknown"), "solid"); // default
     }
 
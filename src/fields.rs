@@ -0,0 +1,256 @@
+//! Typed custom fields on nodes
+//!
+//! Fields are declared per node type in `.deciduous/config.toml`:
+//!
+//! ```toml
+//! [fields.action]
+//! cost_estimate = "number"
+//! risk = "enum[low,med,high]"
+//! ```
+//!
+//! Values live under `fields` in a node's metadata_json, so they travel with
+//! the node through patches and JSON exports.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Field declarations: node type -> field name -> type
+pub type FieldSchema = BTreeMap<String, BTreeMap<String, FieldType>>;
+
+/// Metadata key holding custom field values
+pub const FIELDS_KEY: &str = "fields";
+
+/// Type of a custom field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum FieldType {
+    /// Free text (`"string"`)
+    Text,
+    /// Any number (`"number"`)
+    Number,
+    /// Whole number (`"integer"`)
+    Integer,
+    /// true/false (`"bool"`)
+    Bool,
+    /// One of a fixed set of values (`"enum[low,med,high]"`)
+    Enum(Vec<String>),
+}
+
+impl FieldType {
+    /// Parse a type declaration from config
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        match s {
+            "string" | "text" => Ok(FieldType::Text),
+            "number" => Ok(FieldType::Number),
+            "integer" | "int" => Ok(FieldType::Integer),
+            "bool" | "boolean" => Ok(FieldType::Bool),
+            _ => {
+                let values = s
+                    .strip_prefix("enum[")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown field type '{}' (expected string, number, integer, bool or enum[a,b,...])",
+                            s
+                        )
+                    })?;
+                let values: Vec<String> = values
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+                if values.is_empty() {
+                    return Err(format!("Field type '{}' has no values", s));
+                }
+                Ok(FieldType::Enum(values))
+            }
+        }
+    }
+
+    /// Convert a command-line value into a typed JSON value
+    pub fn coerce(&self, raw: &str) -> Result<Value, String> {
+        let raw = raw.trim();
+        match self {
+            FieldType::Text => Ok(Value::String(raw.to_string())),
+            FieldType::Number => raw
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("'{}' is not a number", raw)),
+            FieldType::Integer => raw
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("'{}' is not an integer", raw)),
+            FieldType::Bool => match raw.to_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok(Value::Bool(true)),
+                "false" | "no" | "0" => Ok(Value::Bool(false)),
+                _ => Err(format!("'{}' is not true or false", raw)),
+            },
+            FieldType::Enum(values) => values
+                .iter()
+                .find(|v| v.eq_ignore_ascii_case(raw))
+                .map(|v| Value::String(v.clone()))
+                .ok_or_else(|| format!("'{}' is not one of: {}", raw, values.join(", "))),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Text => write!(f, "string"),
+            FieldType::Number => write!(f, "number"),
+            FieldType::Integer => write!(f, "integer"),
+            FieldType::Bool => write!(f, "bool"),
+            FieldType::Enum(values) => write!(f, "enum[{}]", values.join(",")),
+        }
+    }
+}
+
+impl TryFrom<String> for FieldType {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        FieldType::parse(&s)
+    }
+}
+
+impl From<FieldType> for String {
+    fn from(t: FieldType) -> Self {
+        t.to_string()
+    }
+}
+
+/// Validate `name=value` assignments against the fields declared for a node type
+pub fn parse_field_values(
+    schema: &FieldSchema,
+    node_type: &str,
+    assignments: &[String],
+) -> Result<Map<String, Value>, String> {
+    let declared = schema.get(node_type);
+    let mut values = Map::new();
+
+    for assignment in assignments {
+        let (name, raw) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected name=value, got '{}'", assignment))?;
+        let name = name.trim();
+
+        let field_type = declared.and_then(|d| d.get(name)).ok_or_else(|| {
+            let known: Vec<&str> = declared
+                .map(|d| d.keys().map(|k| k.as_str()).collect())
+                .unwrap_or_default();
+            if known.is_empty() {
+                format!(
+                    "No custom fields are declared for {} nodes (add [fields.{}] to .deciduous/config.toml)",
+                    node_type, node_type
+                )
+            } else {
+                format!(
+                    "Unknown field '{}' for {} nodes (known: {})",
+                    name,
+                    node_type,
+                    known.join(", ")
+                )
+            }
+        })?;
+
+        let value = field_type
+            .coerce(raw)
+            .map_err(|e| format!("Field '{}': {}", name, e))?;
+        values.insert(name.to_string(), value);
+    }
+
+    Ok(values)
+}
+
+/// Custom field values from a node's metadata_json
+pub fn node_fields(metadata_json: Option<&str>) -> BTreeMap<String, Value> {
+    metadata_json
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .and_then(|v| v.get(FIELDS_KEY).and_then(|f| f.as_object()).cloned())
+        .map(|f| f.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Display form of a field value (strings without quotes)
+pub fn format_field_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> FieldSchema {
+        let toml = r#"
+            [action]
+            cost_estimate = "number"
+            risk = "enum[low,med,high]"
+            reviewed = "bool"
+        "#;
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_parse_field_types() {
+        assert_eq!(FieldType::parse("number"), Ok(FieldType::Number));
+        assert_eq!(
+            FieldType::parse("enum[low, med ,high]"),
+            Ok(FieldType::Enum(vec![
+                "low".to_string(),
+                "med".to_string(),
+                "high".to_string()
+            ]))
+        );
+        assert!(FieldType::parse("enum[]").is_err());
+        assert!(FieldType::parse("date").is_err());
+        assert_eq!(
+            FieldType::parse("enum[a,b]").unwrap().to_string(),
+            "enum[a,b]"
+        );
+    }
+
+    #[test]
+    fn test_parse_field_values() {
+        let schema = schema();
+        let values = parse_field_values(
+            &schema,
+            "action",
+            &[
+                "cost_estimate=2.5".to_string(),
+                "risk=HIGH".to_string(),
+                "reviewed=yes".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(values["cost_estimate"], serde_json::json!(2.5));
+        assert_eq!(values["risk"], serde_json::json!("high"));
+        assert_eq!(values["reviewed"], serde_json::json!(true));
+
+        let err = parse_field_values(&schema, "action", &["risk=extreme".to_string()]);
+        assert!(err.unwrap_err().contains("not one of: low, med, high"));
+        let err = parse_field_values(&schema, "action", &["owner=me".to_string()]);
+        assert!(err.unwrap_err().contains("Unknown field 'owner'"));
+        let err = parse_field_values(&schema, "goal", &["risk=low".to_string()]);
+        assert!(err.unwrap_err().contains("[fields.goal]"));
+        assert!(parse_field_values(&schema, "action", &["risk".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_node_fields() {
+        let meta = r#"{"branch": "main", "fields": {"risk": "low", "cost_estimate": 3}}"#;
+        let fields = node_fields(Some(meta));
+        assert_eq!(fields.len(), 2);
+        assert_eq!(format_field_value(&fields["risk"]), "low");
+        assert_eq!(format_field_value(&fields["cost_estimate"]), "3");
+        assert!(node_fields(None).is_empty());
+    }
+}
@@ -1,9 +1,14 @@
-//! GitHub CLI (`gh`) Integration
+//! GitHub Issues integration
 //!
-//! Wrapper around the GitHub CLI for issue operations.
-//! Uses `gh` instead of direct API to avoid token management complexity.
+//! Talks to the GitHub REST API directly, so it works wherever there is a
+//! token and no `gh` install is needed. The token comes from `GITHUB_TOKEN`
+//! or `GH_TOKEN`, falling back to `gh auth token` when the CLI is logged in.
+//! `GITHUB_API_URL` points the client at GitHub Enterprise.
 
+use crate::tracker::http::{query, RestClient};
+use crate::tracker::{origin_url, repo_from_remote_url, IssueFetch, IssueTracker, Milestone};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::process::Command;
 
 /// GitHub Issue representation
@@ -16,6 +21,9 @@ pub struct GitHubIssue {
     pub html_url: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Title of the issue's milestone
+    #[serde(default)]
+    pub milestone: Option<String>,
 }
 
 /// GitHub Issue Comment
@@ -37,12 +45,29 @@ pub struct CommentAuthor {
 /// Error type for GitHub operations
 #[derive(Debug)]
 pub enum GitHubError {
-    CommandFailed { command: String, stderr: String },
+    CommandFailed {
+        command: String,
+        stderr: String,
+    },
     NotAuthenticated,
     RateLimited,
-    IssueNotFound { number: i32 },
-    ParseError { message: String },
+    IssueNotFound {
+        number: i32,
+    },
+    ParseError {
+        message: String,
+    },
     IoError(std::io::Error),
+    /// HTTP request to a tracker API failed (status 0: no response)
+    RequestFailed {
+        url: String,
+        status: u16,
+        message: String,
+    },
+    /// Tracker settings in config.toml are incomplete
+    NotConfigured {
+        message: String,
+    },
 }
 
 impl std::fmt::Display for GitHubError {
@@ -54,7 +79,7 @@ impl std::fmt::Display for GitHubError {
             GitHubError::NotAuthenticated => {
                 write!(
                     f,
-                    "Not authenticated with GitHub. Run 'gh auth login' first."
+                    "Not authenticated with GitHub. Set GITHUB_TOKEN or run 'gh auth login'."
                 )
             }
             GitHubError::RateLimited => {
@@ -67,6 +92,19 @@ impl std::fmt::Display for GitHubError {
                 write!(f, "Failed to parse GitHub response: {}", message)
             }
             GitHubError::IoError(e) => write!(f, "IO error: {}", e),
+            GitHubError::RequestFailed {
+                url,
+                status: 0,
+                message,
+            } => write!(f, "Request to {} failed: {}", url, message),
+            GitHubError::RequestFailed {
+                url,
+                status,
+                message,
+            } => write!(f, "Request to {} failed ({}): {}", url, status, message),
+            GitHubError::NotConfigured { message } => {
+                write!(f, "Issue tracker not configured: {}", message)
+            }
         }
     }
 }
@@ -81,448 +119,283 @@ impl From<std::io::Error> for GitHubError {
 
 pub type Result<T> = std::result::Result<T, GitHubError>;
 
-/// GitHub client using `gh` CLI
+/// Default REST API root
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// GitHub client using the REST API
 pub struct GitHubClient {
     repo: Option<String>, // "owner/repo" format
+    http: RestClient,
 }
 
-impl GitHubClient {
-    /// Create a new client, optionally with explicit repo
-    pub fn new(repo: Option<String>) -> Self {
-        Self { repo }
-    }
+/// Issue as returned by the REST API
+#[derive(Deserialize)]
+struct ApiIssue {
+    number: i32,
+    title: String,
+    body: Option<String>,
+    state: String,
+    html_url: String,
+    created_at: String,
+    updated_at: String,
+    /// Present when the "issue" is a pull request
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+    #[serde(default)]
+    milestone: Option<ApiMilestone>,
+}
 
-    /// Auto-detect repo from git remote
-    pub fn auto_detect() -> Result<Self> {
-        let output = Command::new("gh")
-            .args([
-                "repo",
-                "view",
-                "--json",
-                "nameWithOwner",
-                "-q",
-                ".nameWithOwner",
-            ])
-            .output()?;
-
-        if output.status.success() {
-            let repo = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !repo.is_empty() {
-                return Ok(Self { repo: Some(repo) });
-            }
+impl From<ApiIssue> for GitHubIssue {
+    fn from(issue: ApiIssue) -> Self {
+        GitHubIssue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            state: issue.state.to_lowercase(),
+            html_url: issue.html_url,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            milestone: issue.milestone.map(|m| m.title),
         }
-
-        Ok(Self { repo: None })
     }
+}
 
-    /// Check if gh CLI is authenticated
-    pub fn check_auth() -> Result<bool> {
-        let output = Command::new("gh").args(["auth", "status"]).output()?;
-
-        Ok(output.status.success())
-    }
+#[derive(Deserialize)]
+struct ApiMilestone {
+    number: i64,
+    title: String,
+    due_on: Option<String>,
+}
 
-    /// Get repo string for gh commands
-    fn repo_args(&self) -> Vec<String> {
-        match &self.repo {
-            Some(repo) => vec!["-R".to_string(), repo.clone()],
-            None => vec![],
+impl From<ApiMilestone> for Milestone {
+    fn from(milestone: ApiMilestone) -> Self {
+        Milestone {
+            id: milestone.number,
+            title: milestone.title,
+            due_on: milestone.due_on.map(|d| date_part(&d)),
         }
     }
+}
 
-    /// Create a new issue
-    pub fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<GitHubIssue> {
-        let mut args = vec!["issue", "create", "--title", title, "--body", body];
+#[derive(Deserialize)]
+struct ApiComment {
+    id: i64,
+    body: Option<String>,
+    user: CommentAuthor,
+    created_at: String,
+}
 
-        // Add labels
-        for label in labels {
-            args.push("--label");
-            args.push(label);
-        }
+#[derive(Deserialize)]
+struct ApiLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    items: Vec<ApiIssue>,
+}
 
-        let mut cmd = Command::new("gh");
-        cmd.args(&args);
+impl GitHubClient {
+    /// Create a new client, optionally with explicit repo
+    ///
+    /// Uses [`default_api_url`] and the token from [`default_token`].
+    pub fn new(repo: Option<String>) -> Self {
+        Self::with_api_url(repo, &default_api_url(), default_token())
+    }
 
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
+    /// Create a client for a specific API root and token
+    pub fn with_api_url(repo: Option<String>, api_url: &str, token: Option<String>) -> Self {
+        Self {
+            repo,
+            http: RestClient::new(
+                api_url,
+                token.map(|t| ("Authorization", format!("Bearer {}", t))),
+            ),
         }
+    }
 
-        let output = cmd.output()?;
+    /// Auto-detect repo from git remote
+    pub fn auto_detect() -> Result<Self> {
+        Ok(Self::new(detect_repo()))
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            if stderr.contains("not logged") || stderr.contains("auth") {
-                return Err(GitHubError::NotAuthenticated);
-            }
-            return Err(GitHubError::CommandFailed {
-                command: "gh issue create".to_string(),
-                stderr,
-            });
+    /// Check if a GitHub token is available and accepted
+    pub fn check_auth() -> Result<bool> {
+        Self::new(None).token_accepted()
+    }
+
+    /// Check this client's token against the API
+    ///
+    /// Reads the repository rather than `/user`, which a GitHub Actions
+    /// `GITHUB_TOKEN` may not access. A 401 means the token was rejected; a
+    /// 403 or 404 that it can't see the repository.
+    pub fn token_accepted(&self) -> Result<bool> {
+        if !self.http.has_auth() {
+            return Ok(false);
+        }
+        let path = self.repo_path("").unwrap_or_else(|_| "/user".to_string());
+        match self.http.request("GET", &path, None) {
+            Ok(response) if (200..300).contains(&response.status()) => Ok(true),
+            Ok(response) => Err(GitHubError::RequestFailed {
+                url: response.get_url().to_string(),
+                status: response.status(),
+                message: response.status_text().to_string(),
+            }),
+            Err(GitHubError::RequestFailed { status: 401, .. }) => Ok(false),
+            Err(GitHubError::RequestFailed {
+                url,
+                status: status @ (403 | 404),
+                ..
+            }) => Err(GitHubError::RequestFailed {
+                url,
+                status,
+                message: "token cannot access repository".to_string(),
+            }),
+            Err(e) => Err(e),
         }
+    }
 
-        // Parse the output URL to get issue number
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let url = stdout.trim();
+    /// API path under the repository
+    ///
+    /// Without an explicit repo, the `origin` remote is used.
+    fn repo_path(&self, rest: &str) -> Result<String> {
+        let repo = match &self.repo {
+            Some(repo) => repo.clone(),
+            None => detect_repo().ok_or_else(|| GitHubError::NotConfigured {
+                message: "no GitHub repository found; pass --repo owner/repo".to_string(),
+            })?,
+        };
+        Ok(format!("/repos/{}{}", repo, rest))
+    }
 
-        // Extract issue number from URL like "https://github.com/owner/repo/issues/42"
-        let number: i32 = url
-            .rsplit('/')
-            .next()
-            .and_then(|s| s.parse().ok())
-            .ok_or_else(|| GitHubError::ParseError {
-                message: format!("Could not parse issue number from URL: {}", url),
-            })?;
+    fn edit_issue(&self, number: i32, changes: serde_json::Value) -> Result<()> {
+        let path = self.repo_path(&format!("/issues/{}", number))?;
+        self.http
+            .send("PATCH", &path, &changes)
+            .map_err(not_found(number))
+    }
 
-        // Fetch the full issue details
-        self.get_issue(number)
+    /// Create a new issue
+    pub fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<GitHubIssue> {
+        let issue: ApiIssue = self.http.json(
+            "POST",
+            &self.repo_path("/issues")?,
+            Some(&json!({ "title": title, "body": body, "labels": labels })),
+        )?;
+        Ok(issue.into())
     }
 
     /// Get an issue by number
     pub fn get_issue(&self, number: i32) -> Result<GitHubIssue> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "issue",
-            "view",
-            &number.to_string(),
-            "--json",
-            "number,title,body,state,url,createdAt,updatedAt",
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            if stderr.contains("not found") || stderr.contains("Could not resolve") {
-                return Err(GitHubError::IssueNotFound { number });
-            }
-            if stderr.contains("rate limit") {
-                return Err(GitHubError::RateLimited);
-            }
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue view {}", number),
-                stderr,
-            });
-        }
+        let path = self.repo_path(&format!("/issues/{}", number))?;
+        let issue: ApiIssue = self
+            .http
+            .json("GET", &path, None)
+            .map_err(not_found(number))?;
+        Ok(issue.into())
+    }
 
-        let json_str = String::from_utf8_lossy(&output.stdout);
-
-        // Parse the JSON response
-        #[derive(Deserialize)]
-        struct IssueResponse {
-            number: i32,
-            title: String,
-            body: String,
-            state: String,
-            url: String,
-            #[serde(rename = "createdAt")]
-            created_at: String,
-            #[serde(rename = "updatedAt")]
-            updated_at: String,
+    /// Get an issue unless it is unchanged since `etag`
+    ///
+    /// A 304 answer doesn't count against the rate limit, so syncing with
+    /// cached ETags is cheap.
+    pub fn get_issue_if_changed(&self, number: i32, etag: Option<&str>) -> Result<IssueFetch> {
+        let path = self.repo_path(&format!("/issues/{}", number))?;
+        match self
+            .http
+            .get_if_changed::<ApiIssue>(&path, etag)
+            .map_err(not_found(number))?
+        {
+            None => Ok(IssueFetch::NotModified),
+            Some((issue, etag)) => Ok(IssueFetch::Modified {
+                issue: issue.into(),
+                etag,
+            }),
         }
-
-        let resp: IssueResponse =
-            serde_json::from_str(&json_str).map_err(|e| GitHubError::ParseError {
-                message: format!("JSON parse error: {} - Raw: {}", e, json_str),
-            })?;
-
-        Ok(GitHubIssue {
-            number: resp.number,
-            title: resp.title,
-            body: resp.body,
-            state: resp.state.to_lowercase(),
-            html_url: resp.url,
-            created_at: resp.created_at,
-            updated_at: resp.updated_at,
-        })
     }
 
     /// Update an issue's body
     pub fn update_issue_body(&self, number: i32, body: &str) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args(["issue", "edit", &number.to_string(), "--body", body]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue edit {}", number),
-                stderr,
-            });
-        }
-
-        Ok(())
+        self.edit_issue(number, json!({ "body": body }))
     }
 
     /// Update an issue's title
     pub fn update_issue_title(&self, number: i32, title: &str) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args(["issue", "edit", &number.to_string(), "--title", title]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue edit {} --title", number),
-                stderr,
-            });
-        }
-
-        Ok(())
+        self.edit_issue(number, json!({ "title": title }))
     }
 
     /// Close an issue
     pub fn close_issue(&self, number: i32) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args(["issue", "close", &number.to_string()]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue close {}", number),
-                stderr,
-            });
-        }
-
-        Ok(())
+        self.edit_issue(number, json!({ "state": "closed" }))
     }
 
     /// Reopen an issue
     pub fn reopen_issue(&self, number: i32) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args(["issue", "reopen", &number.to_string()]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue reopen {}", number),
-                stderr,
-            });
-        }
-
-        Ok(())
+        self.edit_issue(number, json!({ "state": "open" }))
     }
 
     /// Get comments on an issue
     pub fn get_issue_comments(&self, number: i32) -> Result<Vec<GitHubComment>> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "issue",
-            "view",
-            &number.to_string(),
-            "--json",
-            "comments",
-            "-q",
-            ".comments",
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue view {} --json comments", number),
-                stderr,
-            });
-        }
-
-        let json_str = String::from_utf8_lossy(&output.stdout);
-
-        // Handle empty comments (returns "null" or "[]")
-        if json_str.trim() == "null" || json_str.trim().is_empty() {
-            return Ok(vec![]);
-        }
-
-        let comments: Vec<GitHubComment> =
-            serde_json::from_str(&json_str).map_err(|e| GitHubError::ParseError {
-                message: format!("JSON parse error for comments: {} - Raw: {}", e, json_str),
-            })?;
-
-        Ok(comments)
+        let path = self.repo_path(&format!("/issues/{}/comments", number))?;
+        let comments: Vec<ApiComment> = self
+            .http
+            .get_all(&path, "per_page")
+            .map_err(not_found(number))?;
+        Ok(comments
+            .into_iter()
+            .map(|c| GitHubComment {
+                id: c.id,
+                body: c.body.unwrap_or_default(),
+                author: c.user,
+                created_at: c.created_at,
+                created_at_alt: None,
+            })
+            .collect())
     }
 
     /// Add a comment to an issue
     pub fn add_comment(&self, number: i32, body: &str) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args(["issue", "comment", &number.to_string(), "--body", body]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue comment {}", number),
-                stderr,
-            });
-        }
-
-        Ok(())
+        let path = self.repo_path(&format!("/issues/{}/comments", number))?;
+        self.http
+            .send("POST", &path, &json!({ "body": body }))
+            .map_err(not_found(number))
     }
 
-    /// List issues with a specific label
+    /// List issues with a specific label (open and closed, pull requests excluded)
     pub fn list_issues_with_label(&self, label: &str) -> Result<Vec<GitHubIssue>> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "issue",
-            "list",
-            "--label",
-            label,
-            "--state",
-            "all",
-            "--json",
-            "number,title,body,state,url,createdAt,updatedAt",
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh issue list --label {}", label),
-                stderr,
-            });
-        }
-
-        let json_str = String::from_utf8_lossy(&output.stdout);
-
-        #[derive(Deserialize)]
-        struct IssueListItem {
-            number: i32,
-            title: String,
-            body: String,
-            state: String,
-            url: String,
-            #[serde(rename = "createdAt")]
-            created_at: String,
-            #[serde(rename = "updatedAt")]
-            updated_at: String,
-        }
-
-        let items: Vec<IssueListItem> =
-            serde_json::from_str(&json_str).map_err(|e| GitHubError::ParseError {
-                message: format!("JSON parse error: {}", e),
-            })?;
-
-        Ok(items
+        let path = self.repo_path(&format!(
+            "/issues?{}",
+            query(&[("labels", label), ("state", "all")])
+        ))?;
+        let issues: Vec<ApiIssue> = self.http.get_all(&path, "per_page")?;
+        Ok(issues
             .into_iter()
-            .map(|item| GitHubIssue {
-                number: item.number,
-                title: item.title,
-                body: item.body,
-                state: item.state.to_lowercase(),
-                html_url: item.url,
-                created_at: item.created_at,
-                updated_at: item.updated_at,
-            })
+            .filter(|i| i.pull_request.is_none())
+            .map(GitHubIssue::from)
             .collect())
     }
 
-    /// Search for an issue by title
+    /// Search for an issue by exact title
     pub fn find_issue_by_title(&self, title: &str) -> Result<Option<GitHubIssue>> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "issue",
-            "list",
-            "--search",
-            &format!("\"{}\" in:title", title),
-            "--state",
-            "all",
-            "--json",
-            "number,title,body,state,url,createdAt,updatedAt",
-            "--limit",
-            "1",
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
-
-        let output = cmd.output()?;
+        let repo_path = self.repo_path("")?;
+        let repo = repo_path.trim_start_matches("/repos/");
+        let q = format!(
+            "\"{}\" in:title repo:{} type:issue",
+            title.replace('"', ""),
+            repo
+        );
+        let results: SearchResults = self.http.json(
+            "GET",
+            &format!("/search/issues?{}", query(&[("q", q.as_str())])),
+            None,
+        )?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: "gh issue list --search".to_string(),
-                stderr,
-            });
-        }
-
-        let json_str = String::from_utf8_lossy(&output.stdout);
-
-        #[derive(Deserialize)]
-        struct IssueListItem {
-            number: i32,
-            title: String,
-            body: String,
-            state: String,
-            url: String,
-            #[serde(rename = "createdAt")]
-            created_at: String,
-            #[serde(rename = "updatedAt")]
-            updated_at: String,
-        }
-
-        let items: Vec<IssueListItem> =
-            serde_json::from_str(&json_str).map_err(|e| GitHubError::ParseError {
-                message: format!("JSON parse error: {}", e),
-            })?;
-
-        if items.is_empty() {
-            return Ok(None);
-        }
-
-        let item = &items[0];
         // Check if title matches exactly (search is fuzzy)
-        if item.title.to_lowercase() == title.to_lowercase() {
-            Ok(Some(GitHubIssue {
-                number: item.number,
-                title: item.title.clone(),
-                body: item.body.clone(),
-                state: item.state.to_lowercase(),
-                html_url: item.url.clone(),
-                created_at: item.created_at.clone(),
-                updated_at: item.updated_at.clone(),
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(results
+            .items
+            .into_iter()
+            .find(|i| i.title.to_lowercase() == title.to_lowercase())
+            .map(GitHubIssue::from))
     }
 
     /// Get the repo name
@@ -532,71 +405,121 @@ impl GitHubClient {
 
     /// Check if a label exists
     pub fn label_exists(&self, name: &str) -> Result<bool> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "label",
-            "list",
-            "--search",
-            name,
-            "--json",
-            "name",
-            "-q",
-            &format!(".[] | select(.name == \"{}\")", name),
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
+        let labels: Vec<ApiLabel> = self.http.get_all(&self.repo_path("/labels")?, "per_page")?;
+        Ok(labels.iter().any(|l| l.name == name))
+    }
 
-        let output = cmd.output()?;
+    /// Create a label, updating its description and color if it already exists
+    pub fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()> {
+        let label = json!({ "name": name, "description": description, "color": color });
+        match self.http.send("POST", &self.repo_path("/labels")?, &label) {
+            // 422: a label with this name already exists
+            Err(GitHubError::RequestFailed { status: 422, .. }) => {
+                let path = self.repo_path(&format!("/labels/{}", encode_path_segment(name)))?;
+                self.http.send("PATCH", &path, &label)
+            }
+            result => result,
+        }
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: "gh label list".to_string(),
-                stderr,
-            });
+    /// Find or create a milestone, updating its due date if it differs
+    pub fn ensure_milestone(&self, title: &str, due_on: Option<&str>) -> Result<Milestone> {
+        let path = self.repo_path(&format!("/milestones?{}", query(&[("state", "all")])))?;
+        let existing: Vec<ApiMilestone> = self.http.get_all(&path, "per_page")?;
+        // GitHub stores due dates as timestamps
+        let due = due_on.map(|d| format!("{}T00:00:00Z", d));
+
+        match existing.into_iter().find(|m| m.title == title) {
+            Some(milestone) => {
+                let milestone = Milestone::from(milestone);
+                if due_on.is_none() || milestone.due_on.as_deref() == due_on {
+                    return Ok(milestone);
+                }
+                let updated: ApiMilestone = self.http.json(
+                    "PATCH",
+                    &self.repo_path(&format!("/milestones/{}", milestone.id))?,
+                    Some(&json!({ "due_on": due })),
+                )?;
+                Ok(updated.into())
+            }
+            None => {
+                let created: ApiMilestone = self.http.json(
+                    "POST",
+                    &self.repo_path("/milestones")?,
+                    Some(&json!({ "title": title, "due_on": due })),
+                )?;
+                Ok(created.into())
+            }
         }
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(!stdout.trim().is_empty())
+    /// Assign an issue to a milestone
+    pub fn set_issue_milestone(&self, number: i32, milestone: &Milestone) -> Result<()> {
+        self.edit_issue(number, json!({ "milestone": milestone.id }))
     }
+}
 
-    /// Create a label
-    pub fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()> {
-        let mut cmd = Command::new("gh");
-        cmd.args([
-            "label",
-            "create",
-            name,
-            "--description",
-            description,
-            "--color",
-            color,
-            "--force", // Update if exists
-        ]);
-
-        for arg in self.repo_args() {
-            cmd.arg(&arg);
-        }
+/// API root: `GITHUB_API_URL`, or api.github.com
+pub fn default_api_url() -> String {
+    std::env::var("GITHUB_API_URL")
+        .ok()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+}
 
-        let output = cmd.output()?;
+/// API token: `GITHUB_TOKEN`, then `GH_TOKEN`, then `gh auth token` if the
+/// CLI is installed and logged in
+pub fn default_token() -> Option<String> {
+    ["GITHUB_TOKEN", "GH_TOKEN"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|t| t.trim().to_string())
+        .find(|t| !t.is_empty())
+        .or_else(|| {
+            Command::new("gh")
+                .args(["auth", "token"])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .filter(|t| !t.is_empty())
+        })
+}
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Err(GitHubError::CommandFailed {
-                command: format!("gh label create {}", name),
-                stderr,
-            });
-        }
+/// "owner/repo" of the `origin` remote
+fn detect_repo() -> Option<String> {
+    origin_url().and_then(|url| repo_from_remote_url(&url))
+}
 
-        Ok(())
+/// Map a 404 to IssueNotFound
+fn not_found(number: i32) -> impl Fn(GitHubError) -> GitHubError {
+    move |e| match e {
+        GitHubError::RequestFailed { status: 404, .. } => GitHubError::IssueNotFound { number },
+        e => e,
     }
 }
 
+/// YYYY-MM-DD part of an API timestamp
+pub(crate) fn date_part(timestamp: &str) -> String {
+    timestamp.chars().take(10).collect()
+}
+
+/// Percent-encode a URL path segment
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
 /// Ensure the 'roadmap' label exists, creating it if needed
 /// Returns Ok(true) if label was created, Ok(false) if it already existed
-pub fn ensure_roadmap_label(client: &GitHubClient) -> Result<bool> {
+pub fn ensure_roadmap_label(client: &dyn IssueTracker) -> Result<bool> {
     match client.label_exists("roadmap") {
         Ok(true) => Ok(false), // Already exists
         Ok(false) => {
@@ -614,6 +537,27 @@ pub fn ensure_roadmap_label(client: &GitHubClient) -> Result<bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tracker::mock::{MockResponse, MockServer};
+
+    fn issue_json(number: i32, state: &str) -> serde_json::Value {
+        json!({
+            "number": number,
+            "title": "Add sync",
+            "body": null,
+            "state": state,
+            "html_url": format!("https://github.com/owner/repo/issues/{}", number),
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z"
+        })
+    }
+
+    fn client(server: &MockServer) -> GitHubClient {
+        GitHubClient::with_api_url(
+            Some("owner/repo".to_string()),
+            &server.url,
+            Some("secret".to_string()),
+        )
+    }
 
     #[test]
     fn test_client_creation() {
@@ -628,14 +572,177 @@ mod tests {
     }
 
     #[test]
-    fn test_repo_args() {
-        let client_with_repo = GitHubClient::new(Some("owner/repo".to_string()));
-        assert_eq!(client_with_repo.repo_args(), vec!["-R", "owner/repo"]);
+    fn test_repo_path() {
+        let client = GitHubClient::new(Some("owner/repo".to_string()));
+        assert_eq!(
+            client.repo_path("/issues/3").unwrap(),
+            "/repos/owner/repo/issues/3"
+        );
+    }
+
+    #[test]
+    fn test_create_issue() {
+        let server = MockServer::start(|_| MockResponse::json(201, issue_json(42, "open")));
+
+        let issue = client(&server)
+            .create_issue("Add sync", "Body", &["roadmap"])
+            .unwrap();
+        assert_eq!(issue.number, 42);
+        assert_eq!(issue.body, "");
+
+        let request = &server.requests()[0];
+        assert_eq!(request.line(), "POST /repos/owner/repo/issues");
+        assert_eq!(request.header("Authorization"), Some("Bearer secret"));
+        assert_eq!(request.json()["labels"], json!(["roadmap"]));
+    }
+
+    #[test]
+    fn test_list_issues_follows_link_header() {
+        let server = MockServer::start(|req| {
+            if req.url.contains("after=1") {
+                MockResponse::json(200, json!([issue_json(2, "closed")]))
+            } else {
+                let mut pr = issue_json(3, "open");
+                pr["pull_request"] = json!({"url": "https://api.github.com/pulls/3"});
+                // A short first page: only the Link header says there is more
+                let next =
+                    format!(
+                    "<{}/repos/owner/repo/issues?labels=roadmap&state=all&after=1>; rel=\"next\"",
+                    req.header("Host").map(|h| format!("http://{}", h)).unwrap_or_default()
+                );
+                MockResponse::json(200, json!([issue_json(1, "open"), pr])).header("Link", &next)
+            }
+        });
 
-        let client_without_repo = GitHubClient::new(None);
-        assert!(client_without_repo.repo_args().is_empty());
+        let issues = client(&server).list_issues_with_label("roadmap").unwrap();
+        let numbers: Vec<i32> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(server.requests().len(), 2);
     }
 
-    // Note: Integration tests would require actual gh CLI and authentication
-    // These are covered by manual testing
+    #[test]
+    fn test_get_issue_if_changed_uses_etag() {
+        let server = MockServer::start(|req| {
+            if req.header("If-None-Match") == Some("W/\"abc\"") {
+                MockResponse::empty(304)
+            } else {
+                MockResponse::json(200, issue_json(5, "OPEN")).header("ETag", "W/\"abc\"")
+            }
+        });
+        let github = client(&server);
+
+        let etag = match github.get_issue_if_changed(5, None).unwrap() {
+            IssueFetch::Modified { issue, etag } => {
+                assert_eq!(issue.state, "open");
+                etag
+            }
+            IssueFetch::NotModified => panic!("expected a fresh issue"),
+        };
+        assert_eq!(etag.as_deref(), Some("W/\"abc\""));
+        assert!(matches!(
+            github.get_issue_if_changed(5, etag.as_deref()).unwrap(),
+            IssueFetch::NotModified
+        ));
+    }
+
+    #[test]
+    fn test_missing_issue_and_auth() {
+        let server = MockServer::start(|req| {
+            if req.url == "/repos/owner/repo" {
+                MockResponse::json(401, json!({"message": "Bad credentials"}))
+            } else {
+                MockResponse::json(404, json!({"message": "Not Found"}))
+            }
+        });
+        let github = client(&server);
+
+        assert!(matches!(
+            github.get_issue(9),
+            Err(GitHubError::IssueNotFound { number: 9 })
+        ));
+        assert!(matches!(
+            github.close_issue(9),
+            Err(GitHubError::IssueNotFound { number: 9 })
+        ));
+        assert!(!github.token_accepted().unwrap());
+    }
+
+    #[test]
+    fn test_actions_token_accepted() {
+        // A GITHUB_TOKEN can read its repository but gets 403 from /user
+        let server = MockServer::start(|req| match req.url.as_str() {
+            "/repos/owner/repo" => MockResponse::json(200, json!({"full_name": "owner/repo"})),
+            _ => MockResponse::json(
+                403,
+                json!({"message": "Resource not accessible by integration"}),
+            ),
+        });
+        assert!(client(&server).token_accepted().unwrap());
+
+        // A token that can't see the repository isn't good enough
+        for status in [403, 404] {
+            let server = MockServer::start(move |_| {
+                MockResponse::json(status, json!({"message": "Not Found"}))
+            });
+            match client(&server).token_accepted() {
+                Err(GitHubError::RequestFailed {
+                    status: got,
+                    message,
+                    ..
+                }) => {
+                    assert_eq!(got, status);
+                    assert_eq!(message, "token cannot access repository");
+                }
+                other => panic!("expected an access error, got {:?}", other),
+            }
+        }
+
+        // Server errors aren't taken as an accepted token either
+        let server =
+            MockServer::start(|_| MockResponse::json(502, json!({"message": "Bad Gateway"})));
+        assert!(client(&server).token_accepted().is_err());
+    }
+
+    #[test]
+    fn test_create_label_updates_existing() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "POST" => MockResponse::json(422, json!({"message": "Validation Failed"})),
+            _ => MockResponse::json(200, json!({"name": "road map"})),
+        });
+
+        client(&server)
+            .create_label("road map", "Roadmap items", "0e8a16")
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!(
+            requests[1].line(),
+            "PATCH /repos/owner/repo/labels/road%20map"
+        );
+    }
+
+    #[test]
+    fn test_ensure_milestone_reuses_matching() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => MockResponse::json(
+                200,
+                json!([
+                    {"number": 1, "title": "v0.9", "due_on": null},
+                    {"number": 2, "title": "v1.0", "due_on": "2024-05-01T07:00:00Z"}
+                ]),
+            ),
+            _ => MockResponse::json(200, issue_json(8, "open")),
+        });
+        let github = client(&server);
+
+        let milestone = github.ensure_milestone("v1.0", Some("2024-05-01")).unwrap();
+        assert_eq!(milestone.id, 2);
+        assert_eq!(milestone.due_on.as_deref(), Some("2024-05-01"));
+        github.set_issue_milestone(8, &milestone).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].url.contains("/milestones?state=all"));
+        assert_eq!(requests[1].line(), "PATCH /repos/owner/repo/issues/8");
+        assert_eq!(requests[1].json()["milestone"], 2);
+    }
 }
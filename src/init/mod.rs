@@ -0,0 +1,1544 @@
+//! Project initialization for deciduous
+//!
+//! `deciduous init` creates all the files needed for decision graph tracking
+//! Supports multiple editors: Claude Code (--claude), Windsurf (--windsurf),
+//! OpenCode (--opencode), Codex (--codex), Cursor (--cursor), Zed (--zed),
+//! Aider (--aider) and Continue (--continue)
+
+mod templates;
+
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use templates::{DiffLine, Strategy};
+
+/// Editor environment for initialization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Editor {
+    Claude,
+    Windsurf,
+    Opencode,
+    Codex,
+    Cursor,
+    Zed,
+    Aider,
+    Continue,
+}
+
+impl Editor {
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Editor::Claude => "Claude Code",
+            Editor::Windsurf => "Windsurf",
+            Editor::Opencode => "OpenCode",
+            Editor::Codex => "Codex",
+            Editor::Cursor => "Cursor",
+            Editor::Zed => "Zed",
+            Editor::Aider => "Aider",
+            Editor::Continue => "Continue",
+        }
+    }
+
+    /// Key used for this editor in the template manifest
+    pub fn key(&self) -> &'static str {
+        match self {
+            Editor::Claude => "claude",
+            Editor::Windsurf => "windsurf",
+            Editor::Opencode => "opencode",
+            Editor::Codex => "codex",
+            Editor::Cursor => "cursor",
+            Editor::Zed => "zed",
+            Editor::Aider => "aider",
+            Editor::Continue => "continue",
+        }
+    }
+}
+
+/// Static HTML viewer for GitHub Pages (embedded at compile time)
+const PAGES_VIEWER_HTML: &str = include_str!("../pages_viewer.html");
+
+/// Default configuration file content
+const DEFAULT_CONFIG: &str = r#"# Deciduous Configuration
+# This file controls branch detection and grouping behavior
+
+[branch]
+# Branches considered "main" - nodes on these branches won't trigger feature-branch grouping
+# When working on feature branches, nodes are automatically tagged with the branch name
+main_branches = ["main", "master"]
+
+# Automatically detect and store git branch when creating nodes
+# Set to false to disable branch tracking entirely
+auto_detect = true
+
+# Severity overrides for `deciduous audit --lint` ("off", "warn" or "error")
+# [lint.rules]
+# action-without-commit = "off"
+# unreachable-outcome = "error"
+
+# Mirror decision links into git so plain `git log` shows them
+# [git]
+# notes = true      # write refs/notes/deciduous on linked commits
+# trailers = true   # Decision-Id trailers via `deciduous hooks install`
+
+# Sign exported patches with an SSH key and check them on apply
+# [patches]
+# signing_key = "~/.ssh/id_ed25519"
+# allowed_signers = ".deciduous/allowed_signers"  # "email ssh-ed25519 AAAA..." per line
+# require_signed = true
+
+# Issue tracker for `deciduous roadmap sync` (default: GitHub with GITHUB_TOKEN)
+# [tracker]
+# kind = "gitlab"                      # github, gitlab or gitea
+# url = "https://gitlab.example.com"   # required for gitea
+# repo = "group/project"               # default: from the origin remote
+# token_env = "GITLAB_TOKEN"           # env var holding the API token
+
+# Custom fields per node type (set with `add --field` / `edit --field`)
+# Types: string, number, integer, bool, enum[a,b,...]
+# [fields.action]
+# cost_estimate = "number"
+# risk = "enum[low,med,high]"
+"#;
+
+/// GitHub Pages deploy workflow (deploys to gh-pages branch, safe for project repos)
+const DEPLOY_PAGES_WORKFLOW: &str = r#"name: Deploy Decision Graph to Pages
+
+on:
+  push:
+    branches: [main]
+    paths:
+      - 'docs/**'
+  workflow_dispatch:
+
+permissions:
+  contents: write
+
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+
+      - name: Deploy to gh-pages branch
+        uses: peaceiris/actions-gh-pages@v4
+        with:
+          github_token: ${{ secrets.GITHUB_TOKEN }}
+          publish_dir: ./docs
+          publish_branch: gh-pages
+          force_orphan: true
+"#;
+
+const CLEANUP_WORKFLOW: &str = r#"name: Cleanup Decision Graph PNGs
+
+on:
+  pull_request:
+    types: [closed]
+
+jobs:
+  cleanup:
+    # Only run if PR was merged (not just closed)
+    if: github.event.pull_request.merged == true
+    runs-on: ubuntu-latest
+
+    steps:
+      - name: Checkout
+        uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+          token: ${{ secrets.GITHUB_TOKEN }}
+
+      - name: Find and remove decision graph PNGs
+        id: find-pngs
+        run: |
+          # Find decision graph PNGs (in docs/ or root)
+          PNGS=$(find . -name "decision-graph*.png" -o -name "deciduous-graph*.png" 2>/dev/null | grep -v node_modules || true)
+
+          if [ -z "$PNGS" ]; then
+            echo "No decision graph PNGs found"
+            echo "found=false" >> $GITHUB_OUTPUT
+          else
+            echo "Found PNGs to clean up:"
+            echo "$PNGS"
+            echo "found=true" >> $GITHUB_OUTPUT
+
+            # Remove the files
+            echo "$PNGS" | xargs rm -f
+
+            # Also remove corresponding .dot files
+            for png in $PNGS; do
+              dot_file="${png%.png}.dot"
+              if [ -f "$dot_file" ]; then
+                rm -f "$dot_file"
+                echo "Also removed: $dot_file"
+              fi
+            done
+          fi
+
+      - name: Create cleanup PR
+        if: steps.find-pngs.outputs.found == 'true'
+        run: |
+          # Check if there are changes to commit
+          if git diff --quiet && git diff --staged --quiet; then
+            echo "No changes to commit"
+            exit 0
+          fi
+
+          # Configure git
+          git config user.name "github-actions[bot]"
+          git config user.email "github-actions[bot]@users.noreply.github.com"
+
+          # Create branch and commit
+          BRANCH="cleanup/decision-graphs-pr-${{ github.event.pull_request.number }}"
+          git checkout -b "$BRANCH"
+          git add -A
+          git commit -m "chore: cleanup decision graph assets from PR #${{ github.event.pull_request.number }}"
+          git push origin "$BRANCH"
+
+          # Create and auto-merge PR
+          gh pr create \
+            --title "chore: cleanup decision graph assets from PR #${{ github.event.pull_request.number }}" \
+            --body "Automated cleanup of decision graph PNG/DOT files that were used in PR #${{ github.event.pull_request.number }}.
+
+          These files served their purpose for PR review and are no longer needed." \
+            --head "$BRANCH" \
+            --base main
+
+          # Auto-merge (requires auto-merge enabled on repo)
+          gh pr merge "$BRANCH" --auto --squash --delete-branch || echo "Auto-merge not enabled, PR created for manual merge"
+        env:
+          GH_TOKEN: ${{ secrets.GITHUB_TOKEN }}
+"#;
+
+/// Initialize deciduous in the current directory
+pub fn init_project(editor: Editor) -> Result<(), String> {
+    let cwd =
+        std::env::current_dir().map_err(|e| format!("Could not get current directory: {}", e))?;
+
+    let editor_name = editor.name();
+
+    println!(
+        "\n{}",
+        format!("Initializing Deciduous for {}...", editor_name)
+            .cyan()
+            .bold()
+    );
+    println!("   Directory: {}\n", cwd.display());
+
+    // 1. Create .deciduous directory (shared between all editors)
+    let deciduous_dir = cwd.join(".deciduous");
+    create_dir_if_missing(&deciduous_dir)?;
+
+    // 1b. Create default config.toml if it doesn't exist
+    let config_path = deciduous_dir.join("config.toml");
+    write_file_if_missing(&config_path, DEFAULT_CONFIG, ".deciduous/config.toml")?;
+
+    // 2. Initialize database by opening it (creates tables)
+    let db_path = deciduous_dir.join("deciduous.db");
+    if db_path.exists() {
+        println!(
+            "   {} .deciduous/deciduous.db (already exists, preserving data)",
+            "Skipping".yellow()
+        );
+    } else {
+        println!("   {} .deciduous/deciduous.db", "Creating".green());
+    }
+
+    // Set the env var so Database::open() uses this path
+    // Database::open() uses CREATE TABLE IF NOT EXISTS - safe for existing DBs
+    std::env::set_var("DECIDUOUS_DB_PATH", &db_path);
+
+    // 3. Create editor-specific configuration from the templates
+    let templates = templates::load(editor.key(), &deciduous_dir.join("templates"))?;
+    for template in &templates {
+        let path = cwd.join(&template.target);
+        if let Some(parent) = path.parent() {
+            create_dir_if_missing(parent)?;
+        }
+        match template.strategy {
+            Strategy::Section => append_config_md(&path, &template.section(), &template.target)?,
+            Strategy::Overwrite | Strategy::Create => {
+                write_file_if_missing(&path, &template.content, &template.target)?
+            }
+        }
+    }
+
+    match editor {
+        // Add Codex-specific entries to .gitignore (selective ignoring)
+        Editor::Codex => add_codex_to_gitignore(&cwd)?,
+        Editor::Aider => warn_if_aider_conf_skips_conventions(&cwd)?,
+        _ => {}
+    }
+
+    // 4. Add .deciduous to .gitignore if not already there
+    add_to_gitignore(&cwd)?;
+
+    // 5. Create GitHub workflows (if .git exists)
+    let git_dir = cwd.join(".git");
+    if git_dir.exists() {
+        let workflows_dir = cwd.join(".github").join("workflows");
+        create_dir_if_missing(&workflows_dir)?;
+
+        // Cleanup workflow for PR graph assets
+        let cleanup_path = workflows_dir.join("cleanup-decision-graphs.yml");
+        write_file_if_missing(
+            &cleanup_path,
+            CLEANUP_WORKFLOW,
+            ".github/workflows/cleanup-decision-graphs.yml",
+        )?;
+
+        // Deploy workflow for GitHub Pages
+        let deploy_path = workflows_dir.join("deploy-pages.yml");
+        write_file_if_missing(
+            &deploy_path,
+            DEPLOY_PAGES_WORKFLOW,
+            ".github/workflows/deploy-pages.yml",
+        )?;
+    }
+
+    // 6. Create docs/ directory for GitHub Pages
+    let docs_dir = cwd.join("docs");
+    create_dir_if_missing(&docs_dir)?;
+
+    // 7. Write static viewer HTML to docs/index.html
+    let viewer_path = docs_dir.join("index.html");
+    write_file_if_missing(&viewer_path, PAGES_VIEWER_HTML, "docs/index.html")?;
+
+    // 8. Create empty graph-data.json (will be populated by sync)
+    let graph_data_path = docs_dir.join("graph-data.json");
+    if !graph_data_path.exists() {
+        let empty_graph = r#"{"nodes":[],"edges":[]}"#;
+        fs::write(&graph_data_path, empty_graph)
+            .map_err(|e| format!("Could not write graph-data.json: {}", e))?;
+        println!("   {} docs/graph-data.json", "Creating".green());
+    }
+
+    // 9. Create .nojekyll for GitHub Pages (prevents Jekyll processing)
+    let nojekyll_path = docs_dir.join(".nojekyll");
+    if !nojekyll_path.exists() {
+        fs::write(&nojekyll_path, "").map_err(|e| format!("Could not write .nojekyll: {}", e))?;
+        println!("   {} docs/.nojekyll", "Creating".green());
+    }
+
+    println!(
+        "\n{}",
+        format!("Deciduous initialized for {}!", editor_name)
+            .green()
+            .bold()
+    );
+    println!("\nNext steps:");
+    println!(
+        "  1. Run {} to start the local graph viewer",
+        "deciduous serve".cyan()
+    );
+    println!(
+        "  2. Run {} to export graph for GitHub Pages",
+        "deciduous sync".cyan()
+    );
+
+    match editor {
+        Editor::Claude => {
+            println!(
+                "  3. Use {} or {} slash commands",
+                "/decision".cyan(),
+                "/context".cyan()
+            );
+        }
+        Editor::Windsurf => {
+            println!("  3. Rules created in {}", ".windsurf/rules/".cyan());
+            println!("     - {} (set to Always On)", "deciduous.md".cyan());
+            println!("     - {} (model-triggered)", "context.md".cyan());
+            println!("     - {} (auto-retrieved)", ".windsurf/memories.md".cyan());
+            println!();
+            println!(
+                "{}",
+                "  ⚠️  IMPORTANT: Verify rule activation in Windsurf:"
+                    .yellow()
+                    .bold()
+            );
+            println!("     Open Windsurf → Cascade → Customizations (gear icon)");
+            println!(
+                "     Ensure {} is set to {}",
+                "deciduous.md".cyan(),
+                "\"Always On\"".green()
+            );
+        }
+        Editor::Opencode => {
+            println!("  3. Commands created in {}", ".opencode/command/".cyan());
+            println!("     - {} (decision tracking)", "/decision".cyan());
+            println!("     - {} (context recovery)", "/context".cyan());
+            println!("     - {} (build & test)", "/build-test".cyan());
+            println!("     - {} (graph viewer)", "/serve-ui".cyan());
+            println!("     - {} (export graph)", "/sync-graph".cyan());
+            println!("  4. Instructions added to {}", "AGENTS.md".cyan());
+        }
+        Editor::Codex => {
+            println!("  3. Prompts created in {}", ".codex/prompts/".cyan());
+            println!("     - {} (decision tracking)", "/prompts:decision".cyan());
+            println!("     - {} (context recovery)", "/prompts:context".cyan());
+            println!("     - {} (build & test)", "/prompts:build-test".cyan());
+            println!("     - {} (graph viewer)", "/prompts:serve-ui".cyan());
+            println!("     - {} (export graph)", "/prompts:sync-graph".cyan());
+            println!("  4. Instructions added to {}", "AGENTS.md".cyan());
+            println!();
+            println!(
+                "{}",
+                "  Note: Set CODEX_HOME to use project-local prompts:"
+                    .yellow()
+                    .bold()
+            );
+            println!("     {}", "export CODEX_HOME=.codex".cyan());
+        }
+        Editor::Cursor => {
+            println!("  3. Rules created in {}", ".cursor/rules/".cyan());
+            println!("     - {} (always applied)", "deciduous.mdc".cyan());
+            println!("     - {} (agent requested)", "context.mdc".cyan());
+        }
+        Editor::Zed => {
+            println!("  3. Instructions added to {}", ".rules".cyan());
+            println!("     Zed's agent reads it at the start of every thread");
+        }
+        Editor::Aider => {
+            println!("  3. Instructions added to {}", "CONVENTIONS.md".cyan());
+            println!(
+                "     {} loads it as read-only context",
+                ".aider.conf.yml".cyan()
+            );
+        }
+        Editor::Continue => {
+            println!("  3. Rule created in {}", ".continue/rules/".cyan());
+            println!("     - {} (always applied)", "deciduous.md".cyan());
+            println!("  4. Prompts created in {}", ".continue/prompts/".cyan());
+            println!("     - {} (decision tracking)", "/decision".cyan());
+            println!("     - {} (context recovery)", "/context".cyan());
+        }
+    }
+
+    println!();
+    println!(
+        "  4. Commit and push: {}",
+        "git add docs/ .github/ && git push".cyan()
+    );
+    println!("  5. Enable GitHub Pages (Settings → Pages → Source: Deploy from branch, gh-pages)");
+    println!();
+    println!(
+        "Your graph will be live at: {}",
+        "https://<user>.github.io/<repo>/".cyan()
+    );
+    println!();
+
+    Ok(())
+}
+
+fn create_dir_if_missing(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        fs::create_dir_all(path)
+            .map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+        println!("   {} {}", "Creating".green(), path.display());
+    }
+    Ok(())
+}
+
+fn write_file_if_missing(path: &Path, content: &str, display_name: &str) -> Result<(), String> {
+    if path.exists() {
+        println!(
+            "   {} {} (already exists)",
+            "Skipping".yellow(),
+            display_name
+        );
+    } else {
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {}", display_name, e))?;
+        println!("   {} {}", "Creating".green(), display_name);
+    }
+    Ok(())
+}
+
+fn write_file_overwrite(path: &Path, content: &str, display_name: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Could not write {}: {}", display_name, e))?;
+    println!("   {} {}", "Updated".green(), display_name);
+    Ok(())
+}
+
+/// Contents of a config file (CLAUDE.md, AGENTS.md, ...) with its Decision Graph
+/// Workflow section replaced, or appended if it has none, plus what was done
+fn with_section_replaced(existing: Option<&str>, section_content: &str) -> (String, &'static str) {
+    // Look for either variant of our section header
+    let markers = [
+        "## Decision Graph Workflow",
+        "## ⚠️ MANDATORY: Decision Graph Workflow",
+    ];
+    // Our section ends when we hit another ## heading or end of file
+    let section_end_pattern = "\n## ";
+
+    let Some(existing) = existing else {
+        return (new_config_md(section_content), "created");
+    };
+
+    // Find the start of our section (try each marker)
+    let start_idx = markers.iter().filter_map(|m| existing.find(m)).min();
+
+    if let Some(start) = start_idx {
+        // Find the end of our section (next ## heading after our section starts)
+        // Need to skip past the marker properly - find the newline after it
+        let after_marker = existing[start..]
+            .find('\n')
+            .map(|i| start + i)
+            .unwrap_or(start + 10);
+        let end_idx = existing[after_marker..]
+            .find(section_end_pattern)
+            .map(|i| after_marker + i + 1) // +1 to keep the newline before next section
+            .unwrap_or(existing.len()); // If no next section, replace to end
+
+        // Rebuild the file: before our section + new section + after our section
+        let before = &existing[..start];
+        let after = &existing[end_idx..];
+
+        let new_content = if after.is_empty() {
+            format!("{}{}", before, section_content.trim_start())
+        } else {
+            format!(
+                "{}{}\n{}",
+                before,
+                section_content.trim(),
+                after.trim_start()
+            )
+        };
+        (new_content, "section replaced")
+    } else {
+        // No existing section, append
+        (
+            format!("{}\n{}\n", existing, section_content.trim()),
+            "section added",
+        )
+    }
+}
+
+fn replace_config_md_section(
+    path: &Path,
+    section_content: &str,
+    file_name: &str,
+) -> Result<(), String> {
+    let existing = if path.exists() {
+        Some(fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", file_name, e))?)
+    } else {
+        None
+    };
+
+    let (new_content, action) = with_section_replaced(existing.as_deref(), section_content);
+    fs::write(path, new_content).map_err(|e| format!("Could not write {}: {}", file_name, e))?;
+    if existing.is_some() {
+        println!("   {} {} ({})", "Updated".green(), file_name, action);
+    } else {
+        println!("   {} {}", "Creating".green(), file_name);
+    }
+    Ok(())
+}
+
+/// A file `deciduous update` would write
+struct PlannedUpdate {
+    path: std::path::PathBuf,
+    display_name: String,
+    strategy: Strategy,
+    /// Template content as written (the section, for Strategy::Section)
+    content: String,
+    /// Current file contents, if it exists
+    old: Option<String>,
+    /// File contents after the update
+    new: String,
+}
+
+/// Plan writing the default .deciduous/config.toml, if there is none
+///
+/// An existing config holds the user's settings (tracker, lint rules,
+/// fields, ...) so it's never replaced.
+fn plan_config_update(deciduous_dir: &Path) -> Option<PlannedUpdate> {
+    let config_path = deciduous_dir.join("config.toml");
+    if config_path.exists() {
+        return None;
+    }
+    Some(PlannedUpdate {
+        path: config_path,
+        display_name: ".deciduous/config.toml".to_string(),
+        strategy: Strategy::Create,
+        content: DEFAULT_CONFIG.to_string(),
+        old: None,
+        new: DEFAULT_CONFIG.to_string(),
+    })
+}
+
+/// Update tooling files to the latest templates
+///
+/// Shows a diff of every file that would change and asks before writing,
+/// unless `assume_yes` is set. With `dry_run` only the diff is shown.
+pub fn update_tooling(editor: Editor, assume_yes: bool, dry_run: bool) -> Result<(), String> {
+    let cwd =
+        std::env::current_dir().map_err(|e| format!("Could not get current directory: {}", e))?;
+
+    let editor_name = editor.name();
+
+    println!(
+        "\n{}",
+        format!("Updating Deciduous tooling for {}...", editor_name)
+            .cyan()
+            .bold()
+    );
+    println!("   Directory: {}\n", cwd.display());
+
+    let read_existing = |path: &Path, display_name: &str| -> Result<Option<String>, String> {
+        if path.exists() {
+            fs::read_to_string(path)
+                .map(Some)
+                .map_err(|e| format!("Could not read {}: {}", display_name, e))
+        } else {
+            Ok(None)
+        }
+    };
+
+    let mut plan = Vec::new();
+
+    // Restore config.toml if it's missing (only if .deciduous exists)
+    let deciduous_dir = cwd.join(".deciduous");
+    if deciduous_dir.exists() {
+        plan.extend(plan_config_update(&deciduous_dir));
+    } else {
+        println!(
+            "   {} .deciduous/ not found - run 'deciduous init' first",
+            "Warning:".yellow()
+        );
+    }
+
+    for template in templates::load(editor.key(), &deciduous_dir.join("templates"))? {
+        let path = cwd.join(&template.target);
+        let old = read_existing(&path, &template.target)?;
+        let (content, new) = match template.strategy {
+            Strategy::Overwrite => (template.content.clone(), template.content),
+            Strategy::Section => {
+                let section = template.section();
+                let (new, _) = with_section_replaced(old.as_deref(), &section);
+                (section, new)
+            }
+            Strategy::Create => {
+                if old.is_some() {
+                    // The file is the user's once it exists
+                    continue;
+                }
+                (template.content.clone(), template.content)
+            }
+        };
+        plan.push(PlannedUpdate {
+            path,
+            display_name: template.target,
+            strategy: template.strategy,
+            content,
+            old,
+            new,
+        });
+    }
+
+    // Show what would change
+    let mut changes = Vec::new();
+    for update in plan {
+        match &update.old {
+            Some(old) if *old == update.new => {
+                println!("   {} {}", "Unchanged".dimmed(), update.display_name);
+                continue;
+            }
+            Some(old) => {
+                println!("   {} {}", "Changed".yellow(), update.display_name);
+                print_diff(old, &update.new);
+            }
+            None => {
+                println!("   {} {} (new file)", "Create".green(), update.display_name);
+            }
+        }
+        changes.push(update);
+    }
+
+    if changes.is_empty() {
+        println!(
+            "\n{}",
+            format!("Tooling is already up to date for {}.", editor_name).green()
+        );
+        println!();
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "\n{} file(s) would change. Dry run - nothing written.\n",
+            changes.len()
+        );
+        return Ok(());
+    }
+
+    if !assume_yes {
+        use std::io::{self, BufRead, IsTerminal};
+        if !io::stdin().is_terminal() {
+            return Err(format!(
+                "{} file(s) would change; re-run with --yes to apply without confirmation",
+                changes.len()
+            ));
+        }
+        println!(
+            "\n{} Apply changes to {} file(s)? (y/N) ",
+            "Warning:".yellow(),
+            changes.len()
+        );
+        let response = io::stdin().lock().lines().next();
+        match response {
+            Some(Ok(line)) if line.trim().eq_ignore_ascii_case("y") => {}
+            _ => {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+    }
+
+    println!();
+    for update in &changes {
+        if let Some(parent) = update.path.parent() {
+            create_dir_if_missing(parent)?;
+        }
+        match update.strategy {
+            Strategy::Section => {
+                replace_config_md_section(&update.path, &update.content, &update.display_name)?
+            }
+            Strategy::Overwrite | Strategy::Create => {
+                write_file_overwrite(&update.path, &update.content, &update.display_name)?
+            }
+        }
+    }
+
+    println!(
+        "\n{}",
+        format!("Tooling updated for {}!", editor_name)
+            .green()
+            .bold()
+    );
+    println!("\nUpdated files contain the latest:");
+    println!("  - Branch-based grouping with config.toml");
+    println!("  - Graph integrity auditing workflows");
+    println!("  - Improved error messages and documentation");
+    println!();
+
+    Ok(())
+}
+
+/// Print a line diff of a file about to be updated
+fn print_diff(old: &str, new: &str) {
+    for line in templates::line_diff(old, new, 3) {
+        match line {
+            DiffLine::Context(text) => println!("      {}", format!("  {}", text).dimmed()),
+            DiffLine::Removed(text) => println!("      {}", format!("- {}", text).red()),
+            DiffLine::Added(text) => println!("      {}", format!("+ {}", text).green()),
+            DiffLine::Skipped(count) => {
+                println!(
+                    "      {}",
+                    format!("@@ {} unchanged lines @@", count).cyan()
+                )
+            }
+        }
+    }
+}
+
+/// A new config file (CLAUDE.md, AGENTS.md, ...) holding just the workflow section
+fn new_config_md(section_content: &str) -> String {
+    format!("# Project Instructions\n{}", section_content)
+}
+
+/// Append the Decision Graph Workflow section to a config file (CLAUDE.md or AGENTS.md)
+fn append_config_md(path: &Path, section_content: &str, file_name: &str) -> Result<(), String> {
+    let marker = "## Decision Graph Workflow";
+
+    if path.exists() {
+        let existing =
+            fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", file_name, e))?;
+
+        if existing.contains(marker) {
+            println!(
+                "   {} {} (workflow section already present)",
+                "Skipping".yellow(),
+                file_name
+            );
+            return Ok(());
+        }
+
+        // Append the section
+        let new_content = format!("{}\n{}", existing.trim_end(), section_content);
+        fs::write(path, new_content)
+            .map_err(|e| format!("Could not update {}: {}", file_name, e))?;
+        println!(
+            "   {} {} (added workflow section)",
+            "Updated".green(),
+            file_name
+        );
+    } else {
+        fs::write(path, new_config_md(section_content))
+            .map_err(|e| format!("Could not create {}: {}", file_name, e))?;
+        println!("   {} {}", "Creating".green(), file_name);
+    }
+
+    Ok(())
+}
+
+fn add_to_gitignore(cwd: &Path) -> Result<(), String> {
+    let gitignore_path = cwd.join(".gitignore");
+    let entry = ".deciduous/";
+
+    if gitignore_path.exists() {
+        let existing = fs::read_to_string(&gitignore_path)
+            .map_err(|e| format!("Could not read .gitignore: {}", e))?;
+
+        if existing
+            .lines()
+            .any(|line| line.trim() == entry || line.trim() == ".deciduous")
+        {
+            // Already in gitignore
+            return Ok(());
+        }
+
+        // Append
+        let new_content = format!(
+            "{}\n\n# Deciduous database (local)\n{}\n",
+            existing.trim_end(),
+            entry
+        );
+        fs::write(&gitignore_path, new_content)
+            .map_err(|e| format!("Could not update .gitignore: {}", e))?;
+        println!("   {} .gitignore (added .deciduous/)", "Updated".green());
+    } else {
+        // Create new .gitignore
+        let content = format!("# Deciduous database (local)\n{}\n", entry);
+        fs::write(&gitignore_path, content)
+            .map_err(|e| format!("Could not create .gitignore: {}", e))?;
+        println!("   {} .gitignore", "Creating".green());
+    }
+
+    Ok(())
+}
+
+/// Add Codex-specific entries to .gitignore (selective, not entire directory)
+/// Uses negation pattern to allow prompts/ to be committed while ignoring other files
+fn add_codex_to_gitignore(cwd: &Path) -> Result<(), String> {
+    let gitignore_path = cwd.join(".gitignore");
+
+    // The pattern we want to add:
+    // .codex/*           - Ignore everything in .codex
+    // !.codex/prompts/   - Except prompts directory
+    // !.codex/prompts/** - And its contents
+    let codex_entries = [".codex/*", "!.codex/prompts/", "!.codex/prompts/**"];
+
+    let existing = if gitignore_path.exists() {
+        fs::read_to_string(&gitignore_path)
+            .map_err(|e| format!("Could not read .gitignore: {}", e))?
+    } else {
+        String::new()
+    };
+
+    // Check if codex entries are already present
+    if existing.lines().any(|line| line.trim() == ".codex/*") {
+        // Already configured
+        return Ok(());
+    }
+
+    // Build the new content to append
+    let mut new_section = String::new();
+    if !existing.trim().is_empty() {
+        new_section.push('\n');
+    }
+    new_section.push_str("\n# Codex files (prompts/ should be committed)\n");
+    for entry in &codex_entries {
+        new_section.push_str(entry);
+        new_section.push('\n');
+    }
+
+    let new_content = format!("{}{}", existing.trim_end(), new_section);
+    fs::write(&gitignore_path, new_content)
+        .map_err(|e| format!("Could not update .gitignore: {}", e))?;
+
+    println!("   {} .gitignore (added Codex entries)", "Updated".green());
+    Ok(())
+}
+
+/// Whether an .aider.conf.yml loads CONVENTIONS.md (a missing file counts as yes)
+fn aider_conf_reads_conventions(cwd: &Path) -> Result<bool, String> {
+    let conf_path = cwd.join(".aider.conf.yml");
+    if !conf_path.exists() {
+        return Ok(true);
+    }
+    let existing = fs::read_to_string(&conf_path)
+        .map_err(|e| format!("Could not read .aider.conf.yml: {}", e))?;
+    Ok(existing.contains("CONVENTIONS.md"))
+}
+
+/// The user's .aider.conf.yml is left alone; if it doesn't read CONVENTIONS.md we say how to add it
+fn warn_if_aider_conf_skips_conventions(cwd: &Path) -> Result<(), String> {
+    if !aider_conf_reads_conventions(cwd)? {
+        println!(
+            "   {} .aider.conf.yml doesn't load CONVENTIONS.md; add {} to it",
+            "Warning:".yellow(),
+            "read: CONVENTIONS.md".cyan()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn template(source: &str) -> &'static str {
+        templates::builtin(source).unwrap()
+    }
+
+    // === Editor Enum Tests ===
+
+    #[test]
+    fn test_editor_equality() {
+        assert_eq!(Editor::Claude, Editor::Claude);
+        assert_eq!(Editor::Windsurf, Editor::Windsurf);
+        assert_eq!(Editor::Opencode, Editor::Opencode);
+        assert_ne!(Editor::Claude, Editor::Windsurf);
+        assert_ne!(Editor::Claude, Editor::Opencode);
+        assert_ne!(Editor::Windsurf, Editor::Opencode);
+    }
+
+    #[test]
+    fn test_editor_debug() {
+        assert_eq!(format!("{:?}", Editor::Claude), "Claude");
+        assert_eq!(format!("{:?}", Editor::Windsurf), "Windsurf");
+        assert_eq!(format!("{:?}", Editor::Opencode), "Opencode");
+    }
+
+    #[test]
+    fn test_every_editor_has_templates() {
+        let temp = TempDir::new().unwrap();
+        for editor in [
+            Editor::Claude,
+            Editor::Windsurf,
+            Editor::Opencode,
+            Editor::Codex,
+            Editor::Cursor,
+            Editor::Zed,
+            Editor::Aider,
+            Editor::Continue,
+        ] {
+            let loaded = templates::load(editor.key(), temp.path()).unwrap();
+            assert!(!loaded.is_empty(), "{} has no templates", editor.name());
+        }
+    }
+
+    // === Template Content Tests ===
+
+    #[test]
+    fn test_default_config_is_valid_toml() {
+        // Ensure the DEFAULT_CONFIG constant is valid TOML
+        let result: Result<toml::Value, _> = toml::from_str(DEFAULT_CONFIG);
+        assert!(result.is_ok(), "DEFAULT_CONFIG should be valid TOML");
+    }
+
+    #[test]
+    fn test_decision_md_has_required_frontmatter() {
+        assert!(
+            template("claude/decision.md").starts_with("---"),
+            "decision.md should start with frontmatter"
+        );
+        assert!(
+            template("claude/decision.md").contains("description:"),
+            "decision.md should have description"
+        );
+        assert!(
+            template("claude/decision.md").contains("allowed-tools:"),
+            "decision.md should have allowed-tools"
+        );
+    }
+
+    #[test]
+    fn test_context_md_has_required_frontmatter() {
+        assert!(
+            template("claude/context.md").starts_with("---"),
+            "context.md should start with frontmatter"
+        );
+        assert!(
+            template("claude/context.md").contains("description:"),
+            "context.md should have description"
+        );
+    }
+
+    #[test]
+    fn test_claude_md_section_contains_workflow() {
+        assert!(template("claude/workflow-section.md").contains("Decision Graph Workflow"));
+        assert!(template("claude/workflow-section.md").contains("deciduous add"));
+        assert!(template("claude/workflow-section.md").contains("deciduous link"));
+    }
+
+    #[test]
+    fn test_windsurf_rule_has_required_frontmatter() {
+        assert!(
+            template("windsurf/deciduous.md").starts_with("---"),
+            "windsurf rule should start with frontmatter"
+        );
+        assert!(
+            template("windsurf/deciduous.md").contains("alwaysApply:"),
+            "windsurf rule should have alwaysApply"
+        );
+    }
+
+    // === OpenCode Template Tests ===
+
+    #[test]
+    fn test_opencode_decision_cmd_has_required_frontmatter() {
+        assert!(
+            template("opencode/decision.md").starts_with("---"),
+            "opencode decision cmd should start with frontmatter"
+        );
+        assert!(
+            template("opencode/decision.md").contains("description:"),
+            "opencode decision cmd should have description"
+        );
+    }
+
+    #[test]
+    fn test_opencode_context_cmd_has_required_frontmatter() {
+        assert!(
+            template("opencode/context.md").starts_with("---"),
+            "opencode context cmd should start with frontmatter"
+        );
+        assert!(
+            template("opencode/context.md").contains("description:"),
+            "opencode context cmd should have description"
+        );
+    }
+
+    #[test]
+    fn test_opencode_decision_cmd_contains_workflow() {
+        assert!(template("opencode/decision.md").contains("Decision Graph Management"));
+        assert!(template("opencode/decision.md").contains("deciduous add"));
+        assert!(template("opencode/decision.md").contains("deciduous link"));
+        assert!(template("opencode/decision.md").contains("$ARGUMENTS"));
+    }
+
+    #[test]
+    fn test_opencode_context_cmd_contains_recovery() {
+        assert!(template("opencode/context.md").contains("Context Recovery"));
+        assert!(template("opencode/context.md").contains("deciduous nodes"));
+        assert!(template("opencode/context.md").contains("deciduous edges"));
+        assert!(template("opencode/context.md").contains("$ARGUMENTS"));
+    }
+
+    #[test]
+    fn test_opencode_build_test_cmd_has_frontmatter() {
+        assert!(template("opencode/build-test.md").starts_with("---"));
+        assert!(template("opencode/build-test.md").contains("description:"));
+        assert!(template("opencode/build-test.md").contains("cargo test"));
+    }
+
+    #[test]
+    fn test_opencode_serve_ui_cmd_has_frontmatter() {
+        assert!(template("opencode/serve-ui.md").starts_with("---"));
+        assert!(template("opencode/serve-ui.md").contains("description:"));
+        assert!(template("opencode/serve-ui.md").contains("deciduous serve"));
+    }
+
+    #[test]
+    fn test_opencode_sync_graph_cmd_has_frontmatter() {
+        assert!(template("opencode/sync-graph.md").starts_with("---"));
+        assert!(template("opencode/sync-graph.md").contains("description:"));
+        assert!(template("opencode/sync-graph.md").contains("deciduous sync"));
+    }
+
+    // === File Helper Tests (with tempdir) ===
+
+    #[test]
+    fn test_create_dir_if_missing_creates_new() {
+        let temp = TempDir::new().unwrap();
+        let new_dir = temp.path().join("new_dir");
+
+        assert!(!new_dir.exists());
+        create_dir_if_missing(&new_dir).unwrap();
+        assert!(new_dir.exists());
+    }
+
+    #[test]
+    fn test_create_dir_if_missing_skips_existing() {
+        let temp = TempDir::new().unwrap();
+        // temp.path() already exists
+        let result = create_dir_if_missing(temp.path());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_write_file_if_missing_creates_new() {
+        let temp = TempDir::new().unwrap();
+        let file_path = temp.path().join("test.txt");
+
+        assert!(!file_path.exists());
+        write_file_if_missing(&file_path, "hello world", "test.txt").unwrap();
+        assert!(file_path.exists());
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "hello world");
+    }
+
+    #[test]
+    fn test_write_file_if_missing_preserves_existing() {
+        let temp = TempDir::new().unwrap();
+        let file_path = temp.path().join("test.txt");
+
+        // Create file with original content
+        fs::write(&file_path, "original").unwrap();
+
+        // Try to write new content - should be skipped
+        write_file_if_missing(&file_path, "new content", "test.txt").unwrap();
+
+        // Should still have original content
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "original");
+    }
+
+    #[test]
+    fn test_write_file_overwrite_replaces_existing() {
+        let temp = TempDir::new().unwrap();
+        let file_path = temp.path().join("test.txt");
+
+        // Create file with original content
+        fs::write(&file_path, "original").unwrap();
+
+        // Overwrite with new content
+        write_file_overwrite(&file_path, "new content", "test.txt").unwrap();
+
+        // Should have new content
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "new content");
+    }
+
+    #[test]
+    fn test_add_to_gitignore_creates_new() {
+        let temp = TempDir::new().unwrap();
+        let gitignore_path = temp.path().join(".gitignore");
+
+        add_to_gitignore(temp.path()).unwrap();
+
+        assert!(gitignore_path.exists());
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert!(content.contains(".deciduous/"));
+    }
+
+    #[test]
+    fn test_add_to_gitignore_appends_to_existing() {
+        let temp = TempDir::new().unwrap();
+        let gitignore_path = temp.path().join(".gitignore");
+
+        // Create existing gitignore
+        fs::write(&gitignore_path, "node_modules/\n*.log").unwrap();
+
+        add_to_gitignore(temp.path()).unwrap();
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert!(content.contains("node_modules/"));
+        assert!(content.contains(".deciduous/"));
+    }
+
+    #[test]
+    fn test_add_to_gitignore_skips_if_present() {
+        let temp = TempDir::new().unwrap();
+        let gitignore_path = temp.path().join(".gitignore");
+
+        // Create gitignore with .deciduous/ already present
+        let original = "node_modules/\n.deciduous/\n*.log";
+        fs::write(&gitignore_path, original).unwrap();
+
+        add_to_gitignore(temp.path()).unwrap();
+
+        // Content should be unchanged
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, original);
+    }
+
+    #[test]
+    fn test_append_config_md_creates_new() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("CLAUDE.md");
+
+        append_config_md(
+            &md_path,
+            template("claude/workflow-section.md"),
+            "CLAUDE.md",
+        )
+        .unwrap();
+
+        assert!(md_path.exists());
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("Decision Graph Workflow"));
+    }
+
+    #[test]
+    fn test_append_config_md_appends_to_existing() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("CLAUDE.md");
+
+        // Create existing file
+        fs::write(&md_path, "# My Project\n\nSome instructions.").unwrap();
+
+        append_config_md(
+            &md_path,
+            template("claude/workflow-section.md"),
+            "CLAUDE.md",
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("# My Project"));
+        assert!(content.contains("Decision Graph Workflow"));
+    }
+
+    #[test]
+    fn test_append_config_md_skips_if_present() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("CLAUDE.md");
+
+        // Create file with workflow section already
+        let original = "# My Project\n\n## Decision Graph Workflow\n\nAlready here.";
+        fs::write(&md_path, original).unwrap();
+
+        append_config_md(
+            &md_path,
+            template("claude/workflow-section.md"),
+            "CLAUDE.md",
+        )
+        .unwrap();
+
+        // Should be unchanged
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert_eq!(content, original);
+    }
+
+    #[test]
+    fn test_replace_config_md_section_replaces_existing() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("CLAUDE.md");
+
+        // Create file with old workflow section
+        let original = r#"# My Project
+
+## Decision Graph Workflow
+
+Old workflow content here.
+More old content.
+
+## Other Section
+
+This should be preserved."#;
+        fs::write(&md_path, original).unwrap();
+
+        let new_section = "\n## Decision Graph Workflow\n\nNew workflow content!\n";
+        replace_config_md_section(&md_path, new_section, "CLAUDE.md").unwrap();
+
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("New workflow content!"));
+        assert!(!content.contains("Old workflow content"));
+        assert!(content.contains("## Other Section"));
+        assert!(content.contains("This should be preserved"));
+    }
+
+    // === Workflow YAML Tests ===
+
+    #[test]
+    fn test_cleanup_workflow_is_valid_yaml() {
+        let result: Result<serde_yaml::Value, _> = serde_yaml::from_str(CLEANUP_WORKFLOW);
+        assert!(result.is_ok(), "CLEANUP_WORKFLOW should be valid YAML");
+    }
+
+    #[test]
+    fn test_deploy_workflow_is_valid_yaml() {
+        let result: Result<serde_yaml::Value, _> = serde_yaml::from_str(DEPLOY_PAGES_WORKFLOW);
+        assert!(result.is_ok(), "DEPLOY_PAGES_WORKFLOW should be valid YAML");
+    }
+
+    // === Codex Template Tests ===
+
+    #[test]
+    fn test_editor_codex_equality() {
+        assert_eq!(Editor::Codex, Editor::Codex);
+        assert_ne!(Editor::Claude, Editor::Codex);
+        assert_ne!(Editor::Windsurf, Editor::Codex);
+        assert_ne!(Editor::Opencode, Editor::Codex);
+    }
+
+    #[test]
+    fn test_editor_codex_debug() {
+        assert_eq!(format!("{:?}", Editor::Codex), "Codex");
+    }
+
+    #[test]
+    fn test_codex_decision_prompt_has_required_frontmatter() {
+        assert!(
+            template("codex/decision.md").starts_with("---"),
+            "codex decision prompt should start with frontmatter"
+        );
+        assert!(
+            template("codex/decision.md").contains("description:"),
+            "codex decision prompt should have description"
+        );
+        assert!(
+            template("codex/decision.md").contains("argument-hint:"),
+            "codex decision prompt should have argument-hint"
+        );
+    }
+
+    #[test]
+    fn test_codex_context_prompt_has_required_frontmatter() {
+        assert!(
+            template("codex/context.md").starts_with("---"),
+            "codex context prompt should start with frontmatter"
+        );
+        assert!(
+            template("codex/context.md").contains("description:"),
+            "codex context prompt should have description"
+        );
+    }
+
+    #[test]
+    fn test_codex_decision_prompt_contains_workflow() {
+        assert!(template("codex/decision.md").contains("Decision Graph Management"));
+        assert!(template("codex/decision.md").contains("deciduous add"));
+        assert!(template("codex/decision.md").contains("deciduous link"));
+        assert!(template("codex/decision.md").contains("$ARGUMENTS"));
+    }
+
+    #[test]
+    fn test_codex_context_prompt_contains_recovery() {
+        assert!(template("codex/context.md").contains("Context Recovery"));
+        assert!(template("codex/context.md").contains("deciduous nodes"));
+        assert!(template("codex/context.md").contains("deciduous edges"));
+    }
+
+    #[test]
+    fn test_codex_build_test_prompt_has_frontmatter() {
+        assert!(template("codex/build-test.md").starts_with("---"));
+        assert!(template("codex/build-test.md").contains("description:"));
+        assert!(template("codex/build-test.md").contains("cargo test"));
+    }
+
+    #[test]
+    fn test_codex_serve_ui_prompt_has_frontmatter() {
+        assert!(template("codex/serve-ui.md").starts_with("---"));
+        assert!(template("codex/serve-ui.md").contains("description:"));
+        assert!(template("codex/serve-ui.md").contains("deciduous serve"));
+    }
+
+    #[test]
+    fn test_codex_sync_graph_prompt_has_frontmatter() {
+        assert!(template("codex/sync-graph.md").starts_with("---"));
+        assert!(template("codex/sync-graph.md").contains("description:"));
+        assert!(template("codex/sync-graph.md").contains("deciduous sync"));
+    }
+
+    // === Codex Gitignore Tests ===
+
+    #[test]
+    fn test_add_codex_to_gitignore_creates_new() {
+        let temp = TempDir::new().unwrap();
+
+        add_codex_to_gitignore(temp.path()).unwrap();
+
+        let gitignore_path = temp.path().join(".gitignore");
+        assert!(gitignore_path.exists());
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+
+        // Check all Codex entries are present
+        assert!(content.contains(".codex/*"));
+        assert!(content.contains("!.codex/prompts/"));
+        assert!(content.contains("!.codex/prompts/**"));
+    }
+
+    #[test]
+    fn test_add_codex_to_gitignore_preserves_existing() {
+        let temp = TempDir::new().unwrap();
+        let gitignore_path = temp.path().join(".gitignore");
+
+        // Create existing gitignore
+        fs::write(&gitignore_path, "node_modules/\n*.log").unwrap();
+
+        add_codex_to_gitignore(temp.path()).unwrap();
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert!(content.contains("node_modules/"));
+        assert!(content.contains(".codex/*"));
+    }
+
+    #[test]
+    fn test_add_codex_to_gitignore_idempotent() {
+        let temp = TempDir::new().unwrap();
+
+        // Run twice
+        add_codex_to_gitignore(temp.path()).unwrap();
+        add_codex_to_gitignore(temp.path()).unwrap();
+
+        let gitignore_path = temp.path().join(".gitignore");
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+
+        // Should only appear once
+        let count = content.matches(".codex/*").count();
+        assert_eq!(count, 1, "Entry should only appear once");
+    }
+
+    // === Cursor, Zed, Aider and Continue Template Tests ===
+
+    #[test]
+    fn test_editor_new_targets_debug() {
+        assert_eq!(format!("{:?}", Editor::Cursor), "Cursor");
+        assert_eq!(format!("{:?}", Editor::Zed), "Zed");
+        assert_eq!(format!("{:?}", Editor::Aider), "Aider");
+        assert_eq!(format!("{:?}", Editor::Continue), "Continue");
+        assert_ne!(Editor::Cursor, Editor::Windsurf);
+        assert_ne!(Editor::Zed, Editor::Aider);
+    }
+
+    #[test]
+    fn test_cursor_rule_has_required_frontmatter() {
+        assert!(
+            template("cursor/deciduous.mdc").starts_with("---"),
+            "cursor rule should start with frontmatter"
+        );
+        assert!(
+            template("cursor/deciduous.mdc").contains("description:"),
+            "cursor rule should have description"
+        );
+        assert!(
+            template("cursor/deciduous.mdc").contains("alwaysApply: true"),
+            "cursor workflow rule should always apply"
+        );
+    }
+
+    #[test]
+    fn test_cursor_context_rule_has_required_frontmatter() {
+        assert!(template("cursor/context.mdc").starts_with("---"));
+        assert!(template("cursor/context.mdc").contains("description:"));
+        assert!(template("cursor/context.mdc").contains("alwaysApply: false"));
+        assert!(template("cursor/context.mdc").contains("deciduous nodes"));
+    }
+
+    #[test]
+    fn test_cursor_rule_contains_workflow() {
+        assert!(template("cursor/deciduous.mdc").contains("Decision Graph Workflow"));
+        assert!(template("cursor/deciduous.mdc").contains("deciduous add"));
+        assert!(template("cursor/deciduous.mdc").contains("deciduous link"));
+    }
+
+    #[test]
+    fn test_zed_rules_is_plain_markdown() {
+        // Zed shows .rules to the model verbatim, so no frontmatter
+        let temp = TempDir::new().unwrap();
+        let rules_path = temp.path().join(".rules");
+
+        append_config_md(
+            &rules_path,
+            template("shared/workflow-section.md"),
+            ".rules",
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&rules_path).unwrap();
+        assert!(!content.starts_with("---"));
+        assert!(content.contains("Decision Graph Workflow"));
+        assert!(content.contains("deciduous add"));
+    }
+
+    #[test]
+    fn test_aider_conf_is_valid_yaml() {
+        let conf: serde_yaml::Value = serde_yaml::from_str(template("aider/aider.conf.yml"))
+            .expect("aider.conf.yml should be valid YAML");
+        let read = conf["read"].as_sequence().expect("read should be a list");
+        assert!(read.iter().any(|f| f.as_str() == Some("CONVENTIONS.md")));
+    }
+
+    #[test]
+    fn test_continue_rule_has_required_frontmatter() {
+        assert!(
+            template("continue/rules/deciduous.md").starts_with("---"),
+            "continue rule should start with frontmatter"
+        );
+        assert!(
+            template("continue/rules/deciduous.md").contains("name:"),
+            "continue rule should have name"
+        );
+        assert!(
+            template("continue/rules/deciduous.md").contains("alwaysApply: true"),
+            "continue rule should always apply"
+        );
+        assert!(template("continue/rules/deciduous.md").contains("deciduous add"));
+    }
+
+    #[test]
+    fn test_continue_prompts_have_required_frontmatter() {
+        for prompt in [
+            template("continue/prompts/decision.md"),
+            template("continue/prompts/context.md"),
+        ] {
+            assert!(prompt.starts_with("---"));
+            assert!(prompt.contains("name:"));
+            assert!(prompt.contains("description:"));
+            assert!(
+                prompt.contains("invokable: true"),
+                "continue prompts must be invokable to show up as slash commands"
+            );
+            assert!(prompt.contains("{{{ input }}}"));
+            assert!(!prompt.contains("$ARGUMENTS"));
+        }
+        assert!(template("continue/prompts/decision.md").contains("Decision Graph Management"));
+        assert!(template("continue/prompts/context.md").contains("Context Recovery"));
+    }
+
+    #[test]
+    fn test_aider_conf_reads_conventions() {
+        let temp = TempDir::new().unwrap();
+        let conf_path = temp.path().join(".aider.conf.yml");
+
+        // No config yet: init creates one from the template
+        assert!(aider_conf_reads_conventions(temp.path()).unwrap());
+
+        fs::write(&conf_path, "model: sonnet\nauto-commits: false\n").unwrap();
+        assert!(!aider_conf_reads_conventions(temp.path()).unwrap());
+
+        fs::write(&conf_path, "read: CONVENTIONS.md\n").unwrap();
+        assert!(aider_conf_reads_conventions(temp.path()).unwrap());
+    }
+
+    #[test]
+    fn test_with_section_replaced() {
+        let section = "\n## Decision Graph Workflow\n\nNew content\n";
+
+        let (created, action) = with_section_replaced(None, section);
+        assert_eq!(
+            created,
+            "# Project Instructions\n\n## Decision Graph Workflow\n\nNew content\n"
+        );
+        assert_eq!(action, "created");
+
+        let (appended, action) = with_section_replaced(Some("# Project\n"), section);
+        assert_eq!(
+            appended,
+            "# Project\n\n## Decision Graph Workflow\n\nNew content\n"
+        );
+        assert_eq!(action, "section added");
+
+        // Replacing with the same section is a no-op, so update reports it unchanged
+        let (replaced, action) = with_section_replaced(Some(&appended), section);
+        assert_eq!(replaced, appended);
+        assert_eq!(action, "section replaced");
+    }
+
+    #[test]
+    fn test_update_keeps_existing_config() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("config.toml");
+        let config = "[tracker]\nkind = \"gitlab\"\nrepo = \"group/app\"\n";
+        fs::write(&config_path, config).unwrap();
+
+        assert!(plan_config_update(temp.path()).is_none());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), config);
+
+        fs::remove_file(&config_path).unwrap();
+        let planned = plan_config_update(temp.path()).unwrap();
+        assert_eq!(planned.old, None);
+        assert_eq!(planned.new, DEFAULT_CONFIG);
+    }
+}
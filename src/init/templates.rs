@@ -0,0 +1,440 @@
+//! Editor templates for `deciduous init` / `deciduous update`
+//!
+//! The files each editor gets are listed in `templates/manifest.toml` and
+//! embedded at compile time. A project can change them without rebuilding:
+//!
+//! - `.deciduous/templates/<source>` replaces the built-in template at the
+//!   same source path (e.g. `.deciduous/templates/claude/decision.md`)
+//! - `.deciduous/templates/manifest.toml` adds templates, or replaces the
+//!   built-in entry with the same editor and target; its sources are read
+//!   from `.deciduous/templates/`
+
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path};
+
+/// Built-in manifest
+const MANIFEST: &str = include_str!("templates/manifest.toml");
+
+/// Built-in template files, by source path
+const BUILTIN: &[(&str, &str)] = &[
+    (
+        "aider/aider.conf.yml",
+        include_str!("templates/aider/aider.conf.yml"),
+    ),
+    (
+        "claude/context.md",
+        include_str!("templates/claude/context.md"),
+    ),
+    (
+        "claude/decision.md",
+        include_str!("templates/claude/decision.md"),
+    ),
+    (
+        "claude/workflow-section.md",
+        include_str!("templates/claude/workflow-section.md"),
+    ),
+    (
+        "codex/build-test.md",
+        include_str!("templates/codex/build-test.md"),
+    ),
+    (
+        "codex/context.md",
+        include_str!("templates/codex/context.md"),
+    ),
+    (
+        "codex/decision.md",
+        include_str!("templates/codex/decision.md"),
+    ),
+    (
+        "codex/serve-ui.md",
+        include_str!("templates/codex/serve-ui.md"),
+    ),
+    (
+        "codex/sync-graph.md",
+        include_str!("templates/codex/sync-graph.md"),
+    ),
+    (
+        "continue/prompts/context.md",
+        include_str!("templates/continue/prompts/context.md"),
+    ),
+    (
+        "continue/prompts/decision.md",
+        include_str!("templates/continue/prompts/decision.md"),
+    ),
+    (
+        "continue/rules/deciduous.md",
+        include_str!("templates/continue/rules/deciduous.md"),
+    ),
+    (
+        "cursor/context.mdc",
+        include_str!("templates/cursor/context.mdc"),
+    ),
+    (
+        "cursor/deciduous.mdc",
+        include_str!("templates/cursor/deciduous.mdc"),
+    ),
+    (
+        "opencode/build-test.md",
+        include_str!("templates/opencode/build-test.md"),
+    ),
+    (
+        "opencode/context.md",
+        include_str!("templates/opencode/context.md"),
+    ),
+    (
+        "opencode/decision.md",
+        include_str!("templates/opencode/decision.md"),
+    ),
+    (
+        "opencode/serve-ui.md",
+        include_str!("templates/opencode/serve-ui.md"),
+    ),
+    (
+        "opencode/sync-graph.md",
+        include_str!("templates/opencode/sync-graph.md"),
+    ),
+    (
+        "shared/workflow-section.md",
+        include_str!("templates/shared/workflow-section.md"),
+    ),
+    (
+        "windsurf/context.md",
+        include_str!("templates/windsurf/context.md"),
+    ),
+    (
+        "windsurf/deciduous.md",
+        include_str!("templates/windsurf/deciduous.md"),
+    ),
+    (
+        "windsurf/memories.md",
+        include_str!("templates/windsurf/memories.md"),
+    ),
+];
+
+/// How a template is written over an existing file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// `update` replaces the whole file
+    Overwrite,
+    /// The template is a `## Decision Graph Workflow` section; `init` appends
+    /// it and `update` replaces just that section
+    Section,
+    /// Only written when the file doesn't exist
+    Create,
+}
+
+/// One manifest entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateEntry {
+    /// Editor key (`claude`, `cursor`, ...)
+    pub editor: String,
+    /// Template file, relative to the templates directory
+    pub source: String,
+    /// Where it is written, relative to the project root
+    pub target: String,
+    pub strategy: Strategy,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default, rename = "template")]
+    templates: Vec<TemplateEntry>,
+}
+
+/// A template ready to write
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub target: String,
+    pub strategy: Strategy,
+    pub content: String,
+}
+
+impl Template {
+    /// Content as passed to the CLAUDE.md/AGENTS.md section helpers, which
+    /// expect a leading blank line
+    pub fn section(&self) -> String {
+        format!("\n{}", self.content.trim_start())
+    }
+}
+
+/// Built-in template content for a source path
+pub fn builtin(source: &str) -> Option<&'static str> {
+    BUILTIN
+        .iter()
+        .find(|(path, _)| *path == source)
+        .map(|(_, content)| *content)
+}
+
+fn parse_manifest(text: &str, origin: &str) -> Result<Vec<TemplateEntry>, String> {
+    let manifest: Manifest =
+        toml::from_str(text).map_err(|e| format!("Invalid {}: {}", origin, e))?;
+    for entry in &manifest.templates {
+        for path in [&entry.source, &entry.target] {
+            let relative = Path::new(path)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            if path.is_empty() || !relative {
+                return Err(format!(
+                    "Invalid {}: '{}' must be a relative path inside the project",
+                    origin, path
+                ));
+            }
+        }
+    }
+    Ok(manifest.templates)
+}
+
+/// Templates for an editor, with project overrides from `overrides`
+/// (normally `.deciduous/templates/`) applied
+pub fn load(editor: &str, overrides: &Path) -> Result<Vec<Template>, String> {
+    let mut entries = parse_manifest(MANIFEST, "built-in template manifest")?;
+
+    let custom_manifest = overrides.join("manifest.toml");
+    if custom_manifest.exists() {
+        let text = fs::read_to_string(&custom_manifest)
+            .map_err(|e| format!("Could not read {}: {}", custom_manifest.display(), e))?;
+        for entry in parse_manifest(&text, ".deciduous/templates/manifest.toml")? {
+            match entries
+                .iter_mut()
+                .find(|e| e.editor == entry.editor && e.target == entry.target)
+            {
+                Some(existing) => *existing = entry,
+                None => entries.push(entry),
+            }
+        }
+    }
+
+    entries
+        .into_iter()
+        .filter(|e| e.editor == editor)
+        .map(|entry| {
+            let custom = overrides.join(&entry.source);
+            let content = if custom.is_file() {
+                fs::read_to_string(&custom)
+                    .map_err(|e| format!("Could not read {}: {}", custom.display(), e))?
+            } else {
+                builtin(&entry.source)
+                    .ok_or_else(|| {
+                        format!(
+                            "Template '{}' not found in .deciduous/templates/",
+                            entry.source
+                        )
+                    })?
+                    .to_string()
+            };
+            Ok(Template {
+                target: entry.target,
+                strategy: entry.strategy,
+                content,
+            })
+        })
+        .collect()
+}
+
+/// A line of a diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Removed(String),
+    Added(String),
+    /// Unchanged lines left out between hunks
+    Skipped(usize),
+}
+
+/// Line diff of `old` to `new`, keeping `context` unchanged lines around
+/// each change
+pub fn line_diff(old: &str, new: &str, context: usize) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut full = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            full.push(DiffLine::Context(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            // Removals first, so a replaced line reads -old then +new
+            full.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            full.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+
+    // Keep only context near a change
+    let mut near_change = vec![false; full.len()];
+    for (k, _) in full
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Context(_)))
+    {
+        let end = (k + context).min(full.len() - 1);
+        near_change[k.saturating_sub(context)..=end].fill(true);
+    }
+
+    let mut out = Vec::new();
+    let mut skipped = 0;
+    for (k, line) in full.into_iter().enumerate() {
+        if matches!(line, DiffLine::Context(_)) && !near_change[k] {
+            skipped += 1;
+            continue;
+        }
+        if skipped > 0 {
+            out.push(DiffLine::Skipped(skipped));
+            skipped = 0;
+        }
+        out.push(line);
+    }
+    if skipped > 0 {
+        out.push(DiffLine::Skipped(skipped));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const EDITORS: [&str; 8] = [
+        "claude", "windsurf", "opencode", "codex", "cursor", "zed", "aider", "continue",
+    ];
+
+    #[test]
+    fn test_builtin_manifest_sources_exist() {
+        let entries = parse_manifest(MANIFEST, "manifest").unwrap();
+        for entry in &entries {
+            assert!(
+                builtin(&entry.source).is_some(),
+                "{} is not embedded",
+                entry.source
+            );
+            assert!(
+                EDITORS.contains(&entry.editor.as_str()),
+                "unknown editor {}",
+                entry.editor
+            );
+        }
+        for editor in EDITORS {
+            assert!(
+                entries.iter().any(|e| e.editor == editor),
+                "{} has no templates",
+                editor
+            );
+        }
+        // Every embedded file is used
+        for (source, _) in BUILTIN {
+            assert!(entries.iter().any(|e| e.source == *source), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_load_builtin() {
+        let temp = TempDir::new().unwrap();
+        let templates = load("claude", &temp.path().join("templates")).unwrap();
+        let targets: Vec<&str> = templates.iter().map(|t| t.target.as_str()).collect();
+        assert_eq!(
+            targets,
+            vec![
+                ".claude/commands/decision.md",
+                ".claude/commands/context.md",
+                "CLAUDE.md"
+            ]
+        );
+        assert_eq!(templates[2].strategy, Strategy::Section);
+        assert!(templates[2]
+            .section()
+            .starts_with("\n## Decision Graph Workflow"));
+    }
+
+    #[test]
+    fn test_load_with_overrides() {
+        let temp = TempDir::new().unwrap();
+        let overrides = temp.path();
+        fs::create_dir_all(overrides.join("claude")).unwrap();
+        fs::write(overrides.join("claude/decision.md"), "custom decision").unwrap();
+        fs::write(overrides.join("review.md"), "review checklist").unwrap();
+        fs::write(
+            overrides.join("manifest.toml"),
+            r#"
+[[template]]
+editor = "claude"
+source = "review.md"
+target = ".claude/commands/review.md"
+strategy = "create"
+
+[[template]]
+editor = "claude"
+source = "claude/workflow-section.md"
+target = "CLAUDE.md"
+strategy = "create"
+"#,
+        )
+        .unwrap();
+
+        let templates = load("claude", overrides).unwrap();
+        let find = |target: &str| templates.iter().find(|t| t.target == target).unwrap();
+        assert_eq!(
+            find(".claude/commands/decision.md").content,
+            "custom decision"
+        );
+        assert_eq!(
+            find(".claude/commands/review.md").content,
+            "review checklist"
+        );
+        assert_eq!(find("CLAUDE.md").strategy, Strategy::Create);
+        assert_eq!(templates.len(), 4);
+    }
+
+    #[test]
+    fn test_manifest_rejects_paths_outside_project() {
+        for target in ["../outside.md", "/etc/passwd"] {
+            let text = format!(
+                "[[template]]\neditor = \"claude\"\nsource = \"a.md\"\ntarget = \"{}\"\nstrategy = \"overwrite\"\n",
+                target
+            );
+            assert!(parse_manifest(&text, "manifest").is_err(), "{}", target);
+        }
+        let unknown = "[[template]]\neditor = \"claude\"\nsource = \"a.md\"\ntarget = \"a.md\"\nstrategy = \"merge\"\n";
+        assert!(parse_manifest(unknown, "manifest").is_err());
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\n";
+        let new = "a\nb\nc\nD\ne\nf\ng\nh\n";
+        assert_eq!(
+            line_diff(old, new, 1),
+            vec![
+                DiffLine::Skipped(2),
+                DiffLine::Context("c".to_string()),
+                DiffLine::Removed("d".to_string()),
+                DiffLine::Added("D".to_string()),
+                DiffLine::Context("e".to_string()),
+                DiffLine::Skipped(1),
+                DiffLine::Context("g".to_string()),
+                DiffLine::Added("h".to_string()),
+            ]
+        );
+        assert!(line_diff(old, old, 3)
+            .iter()
+            .all(|l| matches!(l, DiffLine::Skipped(_))));
+    }
+}
@@ -39,33 +39,50 @@
 //! ```
 
 pub mod acp;
+pub mod blame;
 pub mod config;
 pub mod context;
 pub mod db;
 pub mod diff;
 pub mod export;
+pub mod fields;
 pub mod github;
 pub mod init;
+pub mod lint;
 pub mod lock;
+pub mod notes;
+pub mod report;
 pub mod roadmap;
 pub mod schema;
 pub mod serve;
+pub mod signing;
+pub mod tracker;
 pub mod tui;
 
-pub use config::Config;
+pub use config::{
+    Config, GitConfig, LintConfig, LintLevel, PatchesConfig, TrackerConfig, TrackerKind,
+};
 pub use context::{ActiveState, ContextError, ContextInfo, ContextManager, ContextSession};
 pub use db::{
-    build_metadata_json, get_current_git_branch, get_current_git_commit, CheckboxState, CommandLog,
-    Database, DbRecord, DbSummary, DecisionContext, DecisionEdge, DecisionGraph, DecisionNode,
-    DecisionSession, GitHubIssueCache, RoadmapConflict, RoadmapItem, RoadmapSyncState,
-    CURRENT_SCHEMA,
+    build_metadata_json, get_current_actor, get_current_git_branch, get_current_git_commit,
+    normalize_tag, parse_tags,
+    CheckboxState, CommandLog, ConflictResolution, Database, DbRecord, DbSummary, DecisionContext,
+    DecisionEdge,
+    DecisionGraph, DecisionNode, DecisionSession, GitHubIssueCache, MergeResult, NodeAlias,
+    OperationKind, OperationLog, OperationScope, RoadmapConflict, RoadmapItem, RoadmapItemHistory,
+    RoadmapSyncState, CURRENT_SCHEMA,
+};
+pub use diff::{
+    ApplyResult, GraphContents, GraphPatch, PatchEdge, PatchNode, PatchVerification,
 };
-pub use diff::{ApplyResult, GraphPatch, PatchEdge, PatchNode};
 pub use export::{
     filter_graph_by_ids, filter_graph_from_roots, generate_pr_writeup, graph_to_dot,
     parse_node_range, DotConfig, WriteupConfig,
 };
+pub use fields::{FieldSchema, FieldType};
+pub use lint::{lint_graph, LintFinding, LintReport, LintRule};
 pub use lock::{acquire_lock, force_unlock, is_locked, lock_info, LockError, LockGuard};
+pub use tracker::{open_tracker, IssueTracker};
 
 // Re-export TS trait for downstream use
 #[cfg(feature = "ts-rs")]
@@ -0,0 +1,601 @@
+//! Graph lint and integrity checks
+//!
+//! Powers `deciduous audit --lint`. Each rule has a default severity that can
+//! be overridden (or turned off) in the `[lint.rules]` table of
+//! .deciduous/config.toml.
+
+use crate::config::{LintConfig, LintLevel};
+use crate::db::{DecisionEdge, DecisionNode};
+use std::collections::{HashMap, HashSet};
+
+/// A lint rule that can be checked against the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// Decision nodes with no outgoing edge to an option
+    DecisionWithoutOptions,
+    /// Option nodes never marked `chosen` or `rejected`
+    UnresolvedOption,
+    /// Action nodes with no commit in their metadata
+    ActionWithoutCommit,
+    /// Outcome nodes that can't be reached from any goal
+    UnreachableOutcome,
+    /// Cycles in the graph
+    Cycle,
+    /// Edges whose from/to node doesn't exist
+    DanglingEdge,
+    /// Edges whose stored change_id doesn't match their node
+    ChangeIdMismatch,
+    /// Nodes whose metadata_json isn't a JSON object
+    InvalidMetadata,
+}
+
+impl LintRule {
+    /// Every rule, in reporting order
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::DanglingEdge,
+        LintRule::ChangeIdMismatch,
+        LintRule::InvalidMetadata,
+        LintRule::Cycle,
+        LintRule::DecisionWithoutOptions,
+        LintRule::UnresolvedOption,
+        LintRule::ActionWithoutCommit,
+        LintRule::UnreachableOutcome,
+    ];
+
+    /// Rule name as used in config.toml and lint output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintRule::DecisionWithoutOptions => "decision-without-options",
+            LintRule::UnresolvedOption => "unresolved-option",
+            LintRule::ActionWithoutCommit => "action-without-commit",
+            LintRule::UnreachableOutcome => "unreachable-outcome",
+            LintRule::Cycle => "cycle",
+            LintRule::DanglingEdge => "dangling-edge",
+            LintRule::ChangeIdMismatch => "change-id-mismatch",
+            LintRule::InvalidMetadata => "invalid-metadata",
+        }
+    }
+
+    /// Parse a rule name
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|r| r.as_str() == s)
+    }
+
+    /// Severity used when config.toml doesn't override it
+    ///
+    /// Structural problems are errors; graph hygiene issues are warnings.
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            LintRule::Cycle
+            | LintRule::DanglingEdge
+            | LintRule::ChangeIdMismatch
+            | LintRule::InvalidMetadata => LintLevel::Error,
+            LintRule::DecisionWithoutOptions
+            | LintRule::UnresolvedOption
+            | LintRule::ActionWithoutCommit
+            | LintRule::UnreachableOutcome => LintLevel::Warn,
+        }
+    }
+
+    /// Effective severity given the lint config
+    pub fn level(&self, config: &LintConfig) -> LintLevel {
+        config
+            .rules
+            .get(self.as_str())
+            .copied()
+            .unwrap_or_else(|| self.default_level())
+    }
+}
+
+/// A single lint violation
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintFinding {
+    /// Rule name (see `LintRule::as_str`)
+    pub rule: &'static str,
+    pub level: LintLevel,
+    /// Offending node, if the finding is about a node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<i32>,
+    /// Offending edge, if the finding is about an edge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edge_id: Option<i32>,
+    pub message: String,
+}
+
+/// Result of linting a graph
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct LintReport {
+    pub errors: usize,
+    pub warnings: usize,
+    pub findings: Vec<LintFinding>,
+    /// Rule names in config.toml that don't match any known rule
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unknown_rules: Vec<String>,
+}
+
+impl LintReport {
+    /// True if any error-level finding was reported
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    fn push(
+        &mut self,
+        rule: LintRule,
+        level: LintLevel,
+        node_id: Option<i32>,
+        edge_id: Option<i32>,
+        message: String,
+    ) {
+        match level {
+            LintLevel::Off => return,
+            LintLevel::Warn => self.warnings += 1,
+            LintLevel::Error => self.errors += 1,
+        }
+        self.findings.push(LintFinding {
+            rule: rule.as_str(),
+            level,
+            node_id,
+            edge_id,
+            message,
+        });
+    }
+}
+
+/// Lint a decision graph
+pub fn lint_graph(
+    nodes: &[DecisionNode],
+    edges: &[DecisionEdge],
+    config: &LintConfig,
+) -> LintReport {
+    let mut report = LintReport {
+        unknown_rules: config
+            .rules
+            .keys()
+            .filter(|name| LintRule::parse(name).is_none())
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    let by_id: HashMap<i32, &DecisionNode> = nodes.iter().map(|n| (n.id, n)).collect();
+    // Only edges whose endpoints both exist take part in graph-shape rules
+    let live_edges: Vec<&DecisionEdge> = edges
+        .iter()
+        .filter(|e| by_id.contains_key(&e.from_node_id) && by_id.contains_key(&e.to_node_id))
+        .collect();
+
+    for rule in LintRule::ALL {
+        let level = rule.level(config);
+        if level == LintLevel::Off {
+            continue;
+        }
+
+        match rule {
+            LintRule::DanglingEdge => {
+                for edge in edges {
+                    for (end, id) in [("from", edge.from_node_id), ("to", edge.to_node_id)] {
+                        if !by_id.contains_key(&id) {
+                            report.push(
+                                *rule,
+                                level,
+                                None,
+                                Some(edge.id),
+                                format!("edge {} {} node {} does not exist", edge.id, end, id),
+                            );
+                        }
+                    }
+                }
+            }
+            LintRule::ChangeIdMismatch => {
+                for edge in edges {
+                    let ends = [
+                        ("from", edge.from_node_id, &edge.from_change_id),
+                        ("to", edge.to_node_id, &edge.to_change_id),
+                    ];
+                    for (end, id, change_id) in ends {
+                        let Some(node) = by_id.get(&id) else {
+                            continue;
+                        };
+                        if change_id.as_deref() != Some(node.change_id.as_str()) {
+                            report.push(
+                                *rule,
+                                level,
+                                Some(id),
+                                Some(edge.id),
+                                format!(
+                                    "edge {} {}_change_id is {} but node {} has change_id {}",
+                                    edge.id,
+                                    end,
+                                    change_id.as_deref().unwrap_or("NULL"),
+                                    id,
+                                    node.change_id
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            LintRule::InvalidMetadata => {
+                for node in nodes {
+                    let Some(meta) = node.metadata_json.as_deref() else {
+                        continue;
+                    };
+                    let problem = match serde_json::from_str::<serde_json::Value>(meta) {
+                        Ok(v) if v.is_object() => None,
+                        Ok(_) => Some("is not a JSON object".to_string()),
+                        Err(e) => Some(format!("is not valid JSON ({})", e)),
+                    };
+                    if let Some(problem) = problem {
+                        report.push(
+                            *rule,
+                            level,
+                            Some(node.id),
+                            None,
+                            format!("node {} metadata_json {}", node.id, problem),
+                        );
+                    }
+                }
+            }
+            LintRule::Cycle => {
+                for cycle in find_cycles(nodes, &live_edges) {
+                    let path = cycle
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" -> ");
+                    report.push(
+                        *rule,
+                        level,
+                        cycle.first().copied(),
+                        None,
+                        format!("cycle through nodes {}", path),
+                    );
+                }
+            }
+            LintRule::DecisionWithoutOptions => {
+                for node in nodes.iter().filter(|n| n.node_type == "decision") {
+                    let has_option = live_edges.iter().any(|e| {
+                        e.from_node_id == node.id && by_id[&e.to_node_id].node_type == "option"
+                    });
+                    if !has_option {
+                        report.push(
+                            *rule,
+                            level,
+                            Some(node.id),
+                            None,
+                            format!("decision {} \"{}\" has no options", node.id, node.title),
+                        );
+                    }
+                }
+            }
+            LintRule::UnresolvedOption => {
+                for node in nodes.iter().filter(|n| n.node_type == "option") {
+                    let resolved = live_edges.iter().any(|e| {
+                        (e.from_node_id == node.id || e.to_node_id == node.id)
+                            && (e.edge_type == "chosen" || e.edge_type == "rejected")
+                    });
+                    if !resolved {
+                        report.push(
+                            *rule,
+                            level,
+                            Some(node.id),
+                            None,
+                            format!(
+                                "option {} \"{}\" is neither chosen nor rejected",
+                                node.id, node.title
+                            ),
+                        );
+                    }
+                }
+            }
+            LintRule::ActionWithoutCommit => {
+                for node in nodes.iter().filter(|n| n.node_type == "action") {
+                    let has_commit = node
+                        .metadata_json
+                        .as_deref()
+                        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                        .and_then(|v| {
+                            v.get("commit")
+                                .and_then(|c| c.as_str())
+                                .map(|s| !s.is_empty())
+                        })
+                        .unwrap_or(false);
+                    if !has_commit {
+                        report.push(
+                            *rule,
+                            level,
+                            Some(node.id),
+                            None,
+                            format!("action {} \"{}\" has no commit", node.id, node.title),
+                        );
+                    }
+                }
+            }
+            LintRule::UnreachableOutcome => {
+                let reachable = reachable_from_goals(nodes, &live_edges);
+                for node in nodes.iter().filter(|n| n.node_type == "outcome") {
+                    if !reachable.contains(&node.id) {
+                        report.push(
+                            *rule,
+                            level,
+                            Some(node.id),
+                            None,
+                            format!(
+                                "outcome {} \"{}\" is not reachable from any goal",
+                                node.id, node.title
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Node ids reachable by following edges forward from any goal
+fn reachable_from_goals(nodes: &[DecisionNode], edges: &[&DecisionEdge]) -> HashSet<i32> {
+    let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+    for edge in edges {
+        children
+            .entry(edge.from_node_id)
+            .or_default()
+            .push(edge.to_node_id);
+    }
+
+    let mut seen: HashSet<i32> = HashSet::new();
+    let mut stack: Vec<i32> = nodes
+        .iter()
+        .filter(|n| n.node_type == "goal")
+        .map(|n| n.id)
+        .collect();
+
+    while let Some(id) = stack.pop() {
+        if seen.insert(id) {
+            if let Some(next) = children.get(&id) {
+                stack.extend(next.iter().copied());
+            }
+        }
+    }
+
+    seen
+}
+
+/// Find cycles as strongly connected components (Tarjan's algorithm)
+///
+/// Each returned cycle lists its node ids in ascending order. Self-loops are
+/// reported as single-node cycles.
+fn find_cycles(nodes: &[DecisionNode], edges: &[&DecisionEdge]) -> Vec<Vec<i32>> {
+    struct Tarjan {
+        children: HashMap<i32, Vec<i32>>,
+        index: HashMap<i32, usize>,
+        lowlink: HashMap<i32, usize>,
+        on_stack: HashSet<i32>,
+        stack: Vec<i32>,
+        next_index: usize,
+        self_loops: HashSet<i32>,
+        cycles: Vec<Vec<i32>>,
+    }
+
+    impl Tarjan {
+        fn visit(&mut self, v: i32) {
+            self.index.insert(v, self.next_index);
+            self.lowlink.insert(v, self.next_index);
+            self.next_index += 1;
+            self.stack.push(v);
+            self.on_stack.insert(v);
+
+            let children = self.children.get(&v).cloned().unwrap_or_default();
+            for w in children {
+                if !self.index.contains_key(&w) {
+                    self.visit(w);
+                    let low = self.lowlink[&v].min(self.lowlink[&w]);
+                    self.lowlink.insert(v, low);
+                } else if self.on_stack.contains(&w) {
+                    let low = self.lowlink[&v].min(self.index[&w]);
+                    self.lowlink.insert(v, low);
+                }
+            }
+
+            if self.lowlink[&v] == self.index[&v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack.remove(&w);
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 || self.self_loops.contains(&v) {
+                    component.sort_unstable();
+                    self.cycles.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        children: HashMap::new(),
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        on_stack: HashSet::new(),
+        stack: Vec::new(),
+        next_index: 0,
+        self_loops: HashSet::new(),
+        cycles: Vec::new(),
+    };
+    for edge in edges {
+        if edge.from_node_id == edge.to_node_id {
+            tarjan.self_loops.insert(edge.from_node_id);
+        }
+        tarjan
+            .children
+            .entry(edge.from_node_id)
+            .or_default()
+            .push(edge.to_node_id);
+    }
+
+    for node in nodes {
+        if !tarjan.index.contains_key(&node.id) {
+            tarjan.visit(node.id);
+        }
+    }
+
+    tarjan.cycles.sort();
+    tarjan.cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32, node_type: &str, metadata: Option<&str>) -> DecisionNode {
+        DecisionNode {
+            id,
+            change_id: format!("cid-{}", id),
+            node_type: node_type.to_string(),
+            title: format!("Node {}", id),
+            description: None,
+            status: "pending".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata_json: metadata.map(|m| m.to_string()),
+        }
+    }
+
+    fn edge(id: i32, from: i32, to: i32, edge_type: &str) -> DecisionEdge {
+        DecisionEdge {
+            id,
+            from_node_id: from,
+            to_node_id: to,
+            from_change_id: Some(format!("cid-{}", from)),
+            to_change_id: Some(format!("cid-{}", to)),
+            edge_type: edge_type.to_string(),
+            weight: Some(1.0),
+            rationale: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn rules(report: &LintReport) -> Vec<&str> {
+        report.findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_graph_has_no_findings() {
+        let nodes = vec![
+            node(1, "goal", None),
+            node(2, "decision", None),
+            node(3, "option", None),
+            node(4, "action", Some(r#"{"commit": "abc123"}"#)),
+            node(5, "outcome", None),
+        ];
+        let edges = vec![
+            edge(1, 1, 2, "leads_to"),
+            edge(2, 2, 3, "leads_to"),
+            edge(3, 3, 4, "chosen"),
+            edge(4, 4, 5, "leads_to"),
+        ];
+
+        let report = lint_graph(&nodes, &edges, &LintConfig::default());
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn test_hygiene_rules_warn_by_default() {
+        let nodes = vec![
+            node(1, "decision", None),
+            node(2, "option", None),
+            node(3, "action", None),
+            node(4, "outcome", None),
+        ];
+
+        let report = lint_graph(&nodes, &[], &LintConfig::default());
+        assert_eq!(
+            rules(&report),
+            vec![
+                "decision-without-options",
+                "unresolved-option",
+                "action-without-commit",
+                "unreachable-outcome",
+            ]
+        );
+        assert_eq!(report.warnings, 4);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn test_structural_rules_are_errors() {
+        let nodes = vec![
+            node(1, "goal", Some("not json")),
+            node(2, "goal", Some("[1, 2]")),
+        ];
+        let mut mismatched = edge(2, 1, 2, "leads_to");
+        mismatched.to_change_id = Some("stale".to_string());
+        let edges = vec![edge(1, 1, 99, "leads_to"), mismatched];
+
+        let report = lint_graph(&nodes, &edges, &LintConfig::default());
+        assert_eq!(
+            rules(&report),
+            vec![
+                "dangling-edge",
+                "change-id-mismatch",
+                "invalid-metadata",
+                "invalid-metadata",
+            ]
+        );
+        assert_eq!(report.errors, 4);
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let nodes = vec![
+            node(1, "goal", None),
+            node(2, "goal", None),
+            node(3, "goal", None),
+            node(4, "goal", None),
+        ];
+        let edges = vec![
+            edge(1, 1, 2, "leads_to"),
+            edge(2, 2, 3, "leads_to"),
+            edge(3, 3, 1, "leads_to"),
+            edge(4, 4, 4, "leads_to"),
+        ];
+
+        let report = lint_graph(&nodes, &edges, &LintConfig::default());
+        let cycles: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.rule == "cycle")
+            .map(|f| f.message.as_str())
+            .collect();
+        assert_eq!(
+            cycles,
+            vec!["cycle through nodes 1 -> 2 -> 3", "cycle through nodes 4"]
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_levels() {
+        let mut config = LintConfig::default();
+        config
+            .rules
+            .insert("action-without-commit".to_string(), LintLevel::Error);
+        config
+            .rules
+            .insert("unreachable-outcome".to_string(), LintLevel::Off);
+        config
+            .rules
+            .insert("no-such-rule".to_string(), LintLevel::Warn);
+
+        let nodes = vec![node(1, "action", None), node(2, "outcome", None)];
+        let report = lint_graph(&nodes, &[], &config);
+
+        assert_eq!(rules(&report), vec!["action-without-commit"]);
+        assert!(report.has_errors());
+        assert_eq!(report.unknown_rules, vec!["no-such-rule".to_string()]);
+    }
+}
@@ -2,13 +2,19 @@ use chrono::Local;
 use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
 use deciduous::context::ContextManager;
-use deciduous::github::{ensure_roadmap_label, GitHubClient};
+use deciduous::github::ensure_roadmap_label;
+use deciduous::report::{ReportFormat, RoadmapReport};
 use deciduous::roadmap::{
-    generate_issue_body, parse_roadmap, write_roadmap_with_metadata, RoadmapSection,
+    append_item, generate_issue_body, milestone_progress, parse_roadmap, reconcile_goal,
+    reconcile_section, roadmap_key, section_task_keys, set_issue_body_checkbox,
+    write_roadmap_with_metadata, GoalSync, RoadmapSection,
 };
+use deciduous::tracker::{open_tracker, Issue, IssueFetch, IssueTracker, Milestone, TrackerError};
+use deciduous::tui::views::conflicts::{ConflictChoice, ConflictEntry};
 use deciduous::{
-    filter_graph_by_ids, generate_pr_writeup, graph_to_dot, parse_node_range, Config, Database,
-    DotConfig, WriteupConfig,
+    filter_graph_by_ids, generate_pr_writeup, graph_to_dot, lint_graph, parse_node_range,
+    CheckboxState, Config, Database, DecisionNode, DotConfig, LintLevel, OperationScope,
+    RoadmapConflict, WriteupConfig,
 };
 use std::path::PathBuf;
 use std::process::Command as ProcessCommand;
@@ -45,6 +51,22 @@ enum Command {
         /// Initialize for Codex (creates .codex/prompts/ and AGENTS.md)
         #[arg(long, group = "editor")]
         codex: bool,
+
+        /// Initialize for Cursor (creates .cursor/rules/)
+        #[arg(long, group = "editor")]
+        cursor: bool,
+
+        /// Initialize for Zed (creates .rules)
+        #[arg(long, group = "editor")]
+        zed: bool,
+
+        /// Initialize for Aider (creates CONVENTIONS.md and .aider.conf.yml)
+        #[arg(long, group = "editor")]
+        aider: bool,
+
+        /// Initialize for Continue (creates .continue/rules/ and .continue/prompts/)
+        #[arg(long = "continue", group = "editor")]
+        continue_: bool,
     },
 
     /// Update tooling files to latest version (overwrites existing)
@@ -64,6 +86,30 @@ enum Command {
         /// Update Codex files (.codex/prompts/, AGENTS.md)
         #[arg(long, group = "editor")]
         codex: bool,
+
+        /// Update Cursor files (.cursor/rules/)
+        #[arg(long, group = "editor")]
+        cursor: bool,
+
+        /// Update Zed files (.rules)
+        #[arg(long, group = "editor")]
+        zed: bool,
+
+        /// Update Aider files (CONVENTIONS.md)
+        #[arg(long, group = "editor")]
+        aider: bool,
+
+        /// Update Continue files (.continue/rules/, .continue/prompts/)
+        #[arg(long = "continue", group = "editor")]
+        continue_: bool,
+
+        /// Apply changes without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Add a new node to the decision graph
@@ -105,6 +151,14 @@ enum Command {
         /// Skip auto-detection of git branch
         #[arg(long)]
         no_branch: bool,
+
+        /// Tags to attach (comma-separated, e.g. "security,tech-debt")
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Custom field value as name=value (repeatable, see [fields.<type>] in config.toml)
+        #[arg(long = "field")]
+        fields: Vec<String>,
     },
 
     /// Add an edge between nodes
@@ -119,11 +173,24 @@ enum Command {
         #[arg(short, long)]
         rationale: Option<String>,
 
-        /// Edge type: leads_to, requires, chosen, rejected, blocks, enables
+        /// Edge type: leads_to, requires, chosen, rejected, blocks, enables, supersedes
         #[arg(short = 't', long, default_value = "leads_to")]
         edge_type: String,
     },
 
+    /// Merge duplicate nodes into one, rewiring their edges
+    ///
+    /// Dropped nodes are deleted; their change_ids are kept as aliases so
+    /// patches that reference them still apply to the kept node.
+    Merge {
+        /// Node ID to keep
+        keep: i32,
+
+        /// Node IDs to merge into it
+        #[arg(required = true)]
+        drop: Vec<i32>,
+    },
+
     /// Update node status
     Status {
         /// Node ID
@@ -142,6 +209,53 @@ enum Command {
         prompt: Option<String>,
     },
 
+    /// Set or clear custom fields on an existing node
+    Edit {
+        /// Node ID
+        id: i32,
+
+        /// Field value as name=value (repeatable)
+        #[arg(long = "field")]
+        fields: Vec<String>,
+
+        /// Field to remove (repeatable)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+
+    /// Undo recent graph changes (add, link, status, prompt, edit)
+    ///
+    /// Only your own changes are undone: those recorded under $DECIDUOUS_SESSION
+    /// if it is set, otherwise those made by the current git user.
+    Undo {
+        /// Number of operations to undo
+        #[arg(default_value = "1")]
+        count: usize,
+
+        /// Show what would be undone without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Include changes made by other users and sessions
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Redo changes previously reverted with `undo`
+    Redo {
+        /// Number of operations to redo
+        #[arg(default_value = "1")]
+        count: usize,
+
+        /// Show what would be redone without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Include changes made by other users and sessions
+        #[arg(long)]
+        all: bool,
+    },
+
     /// List all nodes
     Nodes {
         /// Filter by git branch
@@ -151,6 +265,73 @@ enum Command {
         /// Filter by node type (goal, decision, action, etc.)
         #[arg(short = 't', long)]
         node_type: Option<String>,
+
+        /// Only nodes that touched a file, directory or glob (e.g. "src/*.rs")
+        #[arg(short, long)]
+        file: Option<String>,
+
+        /// Only nodes with this tag (repeat to require several)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Add, remove or list node tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+
+    /// List files associated with a node
+    Files {
+        /// Node ID
+        id: i32,
+    },
+
+    /// Show the goal, decision and action behind each line of a file
+    Blame {
+        /// File to blame
+        file: PathBuf,
+
+        /// Output annotated hunks as JSON (for editor inlay hints)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show git history annotated with linked decisions
+    Log {
+        /// Revision range passed to git log (e.g. main..HEAD)
+        range: Option<String>,
+
+        /// Only show nodes recorded on this branch
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Only show nodes of this type (goal, decision, action, etc.)
+        #[arg(short = 't', long)]
+        node_type: Option<String>,
+
+        /// Draw the commit graph alongside decision chains
+        #[arg(long)]
+        graph: bool,
+
+        /// Include commits with no matching decisions
+        #[arg(long)]
+        all: bool,
+
+        /// Maximum number of commits to read
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Report tracked source files that have no linked decisions
+    Coverage {
+        /// Include all tracked files, not just source code
+        #[arg(long)]
+        all: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// List all edges
@@ -262,12 +443,49 @@ enum Command {
     /// Migrate database to add change_id columns (for multi-user sync)
     Migrate,
 
+    /// Mirror node-commit links into git notes, or rebuild links from them
+    Notes {
+        #[command(subcommand)]
+        action: NotesAction,
+    },
+
+    /// Install git hooks that add Decision-Id trailers to commits
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
     /// Audit and maintain graph data quality
     Audit {
         /// Associate commits with nodes by matching titles to commit messages
         #[arg(long)]
         associate_commits: bool,
 
+        /// Fill in node files from linked commits, or suggest them from
+        /// commits made around the time the node was created
+        #[arg(long)]
+        associate_files: bool,
+
+        /// Find groups of near-duplicate nodes and offer to merge or link them
+        #[arg(long)]
+        find_duplicates: bool,
+
+        /// With --find-duplicates --yes, merge each group instead of linking it
+        #[arg(long, requires_all = ["find_duplicates", "yes"])]
+        merge: bool,
+
+        /// Check graph integrity and hygiene rules (configure in [lint.rules]
+        /// of .deciduous/config.toml). Exits non-zero on error-level findings.
+        #[arg(
+            long,
+            conflicts_with_all = ["associate_commits", "associate_files", "find_duplicates"]
+        )]
+        lint: bool,
+
+        /// Output lint results as JSON
+        #[arg(long, requires = "lint")]
+        json: bool,
+
         /// Minimum keyword match score (0-100, default 50)
         #[arg(long, default_value = "50")]
         min_score: u8,
@@ -286,6 +504,10 @@ enum Command {
         /// Optional database path (default: auto-discover)
         #[arg(short, long)]
         db: Option<PathBuf>,
+
+        /// Only show roadmap items from this roadmap file
+        #[arg(short, long)]
+        file: Option<PathBuf>,
     },
 
     /// Manage ROADMAP.md sync with GitHub Issues
@@ -384,6 +606,76 @@ enum ContextAction {
     Current,
 }
 
+#[derive(Subcommand, Debug)]
+enum TagAction {
+    /// Add tags to a node
+    Add {
+        /// Node ID
+        id: i32,
+
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Remove tags from a node
+    Rm {
+        /// Node ID
+        id: i32,
+
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// List a node's tags, or every tag with its node count
+    Ls {
+        /// Node ID (omit to list all tags)
+        id: Option<i32>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum NotesAction {
+    /// Write a refs/notes/deciduous note on every linked commit
+    Export {
+        /// Show which notes would be written without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Link nodes to commits from refs/notes/deciduous
+    /// (fetch first with: git fetch origin refs/notes/deciduous:refs/notes/deciduous)
+    Import {
+        /// Show which links would be made without making them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HooksAction {
+    /// Install the prepare-commit-msg and post-commit hooks
+    Install,
+
+    /// Hook entry point: add Decision-Id trailers to a commit message file
+    #[command(hide = true)]
+    PrepareCommitMsg {
+        /// Commit message file
+        file: PathBuf,
+
+        /// Message source (message, template, merge, squash or commit)
+        source: Option<String>,
+
+        /// Commit being amended, for source "commit"
+        sha: Option<String>,
+    },
+
+    /// Hook entry point: link nodes referenced by HEAD's message to HEAD
+    #[command(hide = true)]
+    PostCommit,
+}
+
 #[derive(Subcommand, Debug)]
 enum DiffAction {
     /// Export nodes as a patch file
@@ -407,6 +699,14 @@ enum DiffAction {
         /// Git commit hash at time of export
         #[arg(long)]
         base_commit: Option<String>,
+
+        /// Sign the patch (automatic when [patches] signing_key is set)
+        #[arg(long)]
+        sign: bool,
+
+        /// SSH private key to sign with (overrides [patches] signing_key)
+        #[arg(long)]
+        key: Option<PathBuf>,
     },
 
     /// Apply a patch file to local database
@@ -431,6 +731,58 @@ enum DiffAction {
         /// Patch file(s) to validate
         files: Vec<PathBuf>,
     },
+
+    /// Merge patch files into one, dropping duplicate nodes and edges
+    Squash {
+        /// Patch files to merge
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Delete the input files after writing the squashed patch
+        #[arg(long)]
+        delete: bool,
+
+        /// Sign the squashed patch (automatic when [patches] signing_key is set)
+        #[arg(long)]
+        sign: bool,
+
+        /// SSH private key to sign with (overrides [patches] signing_key)
+        #[arg(long)]
+        key: Option<PathBuf>,
+    },
+
+    /// Delete patch files whose contents are already in a base branch's graph-data.json
+    Gc {
+        /// Branch (or any revision) holding the synced graph
+        #[arg(long, default_value = "main")]
+        base: String,
+
+        /// Path of the synced graph within the repository
+        #[arg(long, default_value = "docs/graph-data.json")]
+        graph: PathBuf,
+
+        /// Directory to scan for patches (default: .deciduous/patches/)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+
+        /// List the patches that would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check patch signatures against the allowed signers file
+    Verify {
+        /// Patch file(s) to verify
+        files: Vec<PathBuf>,
+
+        /// allowed_signers file (default: [patches] allowed_signers)
+        #[arg(long)]
+        allowed_signers: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -449,13 +801,15 @@ enum RoadmapAction {
         path: Option<PathBuf>,
     },
 
-    /// Sync ROADMAP.md with GitHub Issues (dry-run by default, use --execute to apply)
+    /// Sync ROADMAP.md with the issue tracker (dry-run by default, use --execute to apply)
+    ///
+    /// Uses GitHub unless `[tracker]` in config.toml selects GitLab or Gitea.
     Sync {
         /// Path to ROADMAP.md (default: ROADMAP.md)
         #[arg(short, long)]
         path: Option<PathBuf>,
 
-        /// GitHub repo in owner/repo format (auto-detected from git remote)
+        /// Repository in owner/repo format (default: tracker.repo, then the git remote)
         #[arg(short, long)]
         repo: Option<String>,
 
@@ -463,7 +817,7 @@ enum RoadmapAction {
         #[arg(long)]
         execute: bool,
 
-        /// Create GitHub issues for new sections
+        /// Create issues for new sections
         #[arg(long, default_value = "true")]
         create_issues: bool,
     },
@@ -504,9 +858,13 @@ enum RoadmapAction {
 
     /// Show sync conflicts
     Conflicts {
-        /// Resolve conflicts interactively
+        /// Resolve conflicts interactively (applies choices to ROADMAP.md and the issue)
         #[arg(long)]
         resolve: bool,
+
+        /// Path to ROADMAP.md (default: ROADMAP.md)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
     },
 
     /// Show sync status summary
@@ -530,6 +888,45 @@ enum RoadmapAction {
         #[arg(long)]
         complete: bool,
     },
+
+    /// Add an in-progress goal to ROADMAP.md as an item tracking it
+    Promote {
+        /// Goal node ID
+        goal_id: i32,
+
+        /// Section to add the item to (created if missing)
+        #[arg(short, long, default_value = "In Progress")]
+        section: String,
+
+        /// Path to ROADMAP.md (default: ROADMAP.md)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+    },
+
+    /// Create a goal node for a roadmap item and start tracking it
+    Start {
+        /// Roadmap item change_id or title (partial match)
+        item: String,
+
+        /// Path to ROADMAP.md (default: ROADMAP.md)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+    },
+
+    /// Report weekly progress: tasks checked, outcomes linked, issues closed
+    Report {
+        /// Number of weeks to cover, ending with the current one
+        #[arg(short, long, default_value = "12")]
+        weeks: usize,
+
+        /// Output format: text, csv or html (burndown chart as SVG)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 fn main() {
@@ -541,6 +938,10 @@ fn main() {
         windsurf,
         opencode,
         codex,
+        cursor,
+        zed,
+        aider,
+        continue_,
     } = args.command
     {
         // Determine editor type: default to Claude if none specified
@@ -550,6 +951,14 @@ fn main() {
             deciduous::init::Editor::Opencode
         } else if codex {
             deciduous::init::Editor::Codex
+        } else if cursor {
+            deciduous::init::Editor::Cursor
+        } else if zed {
+            deciduous::init::Editor::Zed
+        } else if aider {
+            deciduous::init::Editor::Aider
+        } else if continue_ {
+            deciduous::init::Editor::Continue
         } else {
             deciduous::init::Editor::Claude
         };
@@ -567,6 +976,12 @@ fn main() {
         windsurf,
         opencode,
         codex,
+        cursor,
+        zed,
+        aider,
+        continue_,
+        yes,
+        dry_run,
     } = args.command
     {
         // Determine editor type: default to Claude if none specified
@@ -576,11 +991,19 @@ fn main() {
             deciduous::init::Editor::Opencode
         } else if codex {
             deciduous::init::Editor::Codex
+        } else if cursor {
+            deciduous::init::Editor::Cursor
+        } else if zed {
+            deciduous::init::Editor::Zed
+        } else if aider {
+            deciduous::init::Editor::Aider
+        } else if continue_ {
+            deciduous::init::Editor::Continue
         } else {
             deciduous::init::Editor::Claude
         };
 
-        if let Err(e) = deciduous::init::update_tooling(editor) {
+        if let Err(e) = deciduous::init::update_tooling(editor, yes, dry_run) {
             eprintln!("{} {}", "Error:".red(), e);
             std::process::exit(1);
         }
@@ -588,8 +1011,8 @@ fn main() {
     }
 
     // Handle TUI separately - it has its own event loop
-    if let Command::Tui { db } = args.command {
-        if let Err(e) = deciduous::tui::run(db) {
+    if let Command::Tui { db, file } = args.command {
+        if let Err(e) = deciduous::tui::run(db, file.map(roadmap_key)) {
             eprintln!("{} {}", "Error:".red(), e);
             std::process::exit(1);
         }
@@ -894,7 +1317,34 @@ fn main() {
             files,
             branch,
             no_branch,
+            tags,
+            fields,
         } => {
+            // Reject bad tags and field values before anything is created
+            let tags = tags
+                .as_deref()
+                .map(deciduous::parse_tags)
+                .unwrap_or_default();
+            if let Some(bad) = tags.iter().find(|t| deciduous::normalize_tag(t).is_none()) {
+                eprintln!("{} Invalid tag: '{}'", "Error:".red(), bad);
+                std::process::exit(1);
+            }
+            let field_values = if fields.is_empty() {
+                serde_json::Map::new()
+            } else {
+                match deciduous::fields::parse_field_values(
+                    &Config::load().fields,
+                    &node_type,
+                    &fields,
+                ) {
+                    Ok(values) => values,
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                }
+            };
+
             // Handle prompt from stdin if requested
             let effective_prompt = if prompt_stdin {
                 use std::io::{self, Read};
@@ -970,8 +1420,29 @@ fn main() {
                         .as_ref()
                         .map(|b| format!(" [branch: {}]", b))
                         .unwrap_or_default();
+                    let tags_str = match db.add_node_tags(id, &tags) {
+                        Ok(_) if !tags.is_empty() => format!(" [tags: {}]", tags.join(", ")),
+                        Ok(_) => String::new(),
+                        Err(e) => {
+                            eprintln!("{} Could not tag node {}: {}", "Warning:".yellow(), id, e);
+                            String::new()
+                        }
+                    };
+                    let fields_str = if field_values.is_empty() {
+                        String::new()
+                    } else if let Err(e) = db.update_node_fields(id, &field_values, &[]) {
+                        eprintln!(
+                            "{} Could not set fields on node {}: {}",
+                            "Warning:".yellow(),
+                            id,
+                            e
+                        );
+                        String::new()
+                    } else {
+                        format!(" [fields: {}]", field_values.len())
+                    };
                     println!(
-                        "{} node {} (type: {}, title: {}){}{}{}{}{}",
+                        "{} node {} (type: {}, title: {}){}{}{}{}{}{}{}",
                         "Created".green(),
                         id,
                         node_type,
@@ -980,8 +1451,22 @@ fn main() {
                         commit_str,
                         prompt_str,
                         files_str,
-                        branch_str
+                        branch_str,
+                        tags_str,
+                        fields_str
                     );
+
+                    if let Some(c) = effective_commit.as_deref() {
+                        if Config::load().git.notes {
+                            if let Err(e) = mirror_commit_notes(&db, &[c]) {
+                                eprintln!(
+                                    "{} Could not write git note: {}",
+                                    "Warning:".yellow(),
+                                    e
+                                );
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
                     eprintln!("{} {}", "Error:".red(), e);
@@ -1012,6 +1497,25 @@ fn main() {
             }
         },
 
+        Command::Merge { keep, drop } => match db.merge_nodes(keep, &drop) {
+            Ok(result) => {
+                println!(
+                    "{} {} node(s) into {}",
+                    "Merged".green(),
+                    result.nodes_merged,
+                    keep
+                );
+                println!(
+                    "  {} edge(s) rewired, {} duplicate edge(s) removed",
+                    result.edges_rewired, result.edges_removed
+                );
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red(), e);
+                std::process::exit(1);
+            }
+        },
+
         Command::Status { id, status } => match db.update_node_status(id, &status) {
             Ok(()) => println!("{} node {} status to '{}'", "Updated".green(), id, status),
             Err(e) => {
@@ -1020,6 +1524,64 @@ fn main() {
             }
         },
 
+        Command::Edit { id, fields, unset } => {
+            if fields.is_empty() && unset.is_empty() {
+                eprintln!(
+                    "{} Nothing to change. Use --field name=value or --unset name",
+                    "Error:".red()
+                );
+                std::process::exit(1);
+            }
+
+            let node = match db.get_all_nodes() {
+                Ok(nodes) => nodes.into_iter().find(|n| n.id == id),
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let Some(node) = node else {
+                eprintln!("{} Node {} not found", "Error:".red(), id);
+                std::process::exit(1);
+            };
+
+            let values = match deciduous::fields::parse_field_values(
+                &Config::load().fields,
+                &node.node_type,
+                &fields,
+            ) {
+                Ok(values) => values,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+
+            match db.update_node_fields(id, &values, &unset) {
+                Ok(()) => {
+                    println!("{} node {}", "Updated".green(), id);
+                    let current = deciduous::fields::node_fields(
+                        db.get_all_nodes()
+                            .ok()
+                            .and_then(|nodes| nodes.into_iter().find(|n| n.id == id))
+                            .and_then(|n| n.metadata_json)
+                            .as_deref(),
+                    );
+                    for (name, value) in &current {
+                        println!(
+                            "  {}: {}",
+                            name.cyan(),
+                            deciduous::fields::format_field_value(value)
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
         Command::Prompt { id, prompt } => {
             // Read prompt from stdin if not provided as argument
             let effective_prompt = match prompt {
@@ -1064,25 +1626,90 @@ fn main() {
             }
         }
 
-        Command::Nodes { branch, node_type } => {
-            match db.get_all_nodes() {
-                Ok(nodes) => {
-                    // Filter nodes by branch and/or type
-                    let filtered: Vec<_> = nodes
-                        .into_iter()
-                        .filter(|n| {
-                            // Filter by branch if specified
+        Command::Undo {
+            count,
+            dry_run,
+            all,
+        } => {
+            let scope = if all {
+                OperationScope::All
+            } else {
+                OperationScope::current()
+            };
+
+            match db.undo_operations(count, &scope, dry_run) {
+                Ok(ops) if ops.is_empty() => println!("Nothing to undo ({})", scope),
+                Ok(ops) => {
+                    let label = if dry_run { "Would undo:" } else { "Undone:" };
+                    println!("{}", label.green());
+                    for op in ops {
+                        println!("  {}", op.describe());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Command::Redo {
+            count,
+            dry_run,
+            all,
+        } => {
+            let scope = if all {
+                OperationScope::All
+            } else {
+                OperationScope::current()
+            };
+
+            match db.redo_operations(count, &scope, dry_run) {
+                Ok(ops) if ops.is_empty() => println!("Nothing to redo ({})", scope),
+                Ok(ops) => {
+                    let label = if dry_run { "Would redo:" } else { "Redone:" };
+                    println!("{}", label.green());
+                    for op in ops {
+                        println!("  {}", op.describe());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Command::Nodes {
+            branch,
+            node_type,
+            file,
+            tags,
+        } => {
+            let nodes = match &file {
+                Some(f) => db.find_nodes_by_file(f),
+                None => db.get_all_nodes(),
+            };
+            let node_tags = match db.get_all_node_tags() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let tags: Vec<String> = tags
+                .iter()
+                .filter_map(|t| deciduous::normalize_tag(t))
+                .collect();
+            match nodes {
+                Ok(nodes) => {
+                    // Filter nodes by branch, type and/or tags
+                    let filtered: Vec<_> = nodes
+                        .into_iter()
+                        .filter(|n| {
+                            // Filter by branch if specified
                             let branch_match = match &branch {
-                                Some(b) => n.metadata_json.as_ref().is_some_and(|meta| {
-                                    serde_json::from_str::<serde_json::Value>(meta)
-                                        .ok()
-                                        .and_then(|v| {
-                                            v.get("branch")
-                                                .and_then(|br| br.as_str())
-                                                .map(|s| s.to_string())
-                                        })
-                                        .is_some_and(|node_branch| node_branch == *b)
-                                }),
+                                Some(b) => node_on_branch(n, b),
                                 None => true,
                             };
                             // Filter by type if specified
@@ -1090,12 +1717,20 @@ fn main() {
                                 Some(t) => n.node_type == *t,
                                 None => true,
                             };
-                            branch_match && type_match
+                            // Node must carry every requested tag
+                            let tag_match = tags
+                                .iter()
+                                .all(|t| node_tags.get(&n.id).is_some_and(|nt| nt.contains(t)));
+                            branch_match && type_match && tag_match
                         })
                         .collect();
 
                     if filtered.is_empty() {
-                        if branch.is_some() || node_type.is_some() {
+                        if branch.is_some()
+                            || node_type.is_some()
+                            || file.is_some()
+                            || !tags.is_empty()
+                        {
                             println!("No nodes found matching filters.");
                         } else {
                             println!(
@@ -1103,11 +1738,14 @@ fn main() {
                             );
                         }
                     } else {
-                        let header = match &branch {
-                            Some(b) => {
+                        let header = match (&branch, &file) {
+                            (Some(b), _) => {
                                 format!("Nodes on branch '{}' ({} total):", b, filtered.len())
                             }
-                            None => format!("{} nodes:", filtered.len()),
+                            (None, Some(f)) => {
+                                format!("Nodes touching '{}' ({} total):", f, filtered.len())
+                            }
+                            (None, None) => format!("{} nodes:", filtered.len()),
                         };
                         println!("{}", header.cyan());
                         println!("{:<5} {:<12} {:<10} TITLE", "ID", "TYPE", "STATUS");
@@ -1121,9 +1759,17 @@ fn main() {
                                 "observation" => n.node_type.magenta(),
                                 _ => n.node_type.white(),
                             };
+                            let tag_str = node_tags
+                                .get(&n.id)
+                                .map(|t| {
+                                    let tags: Vec<String> =
+                                        t.iter().map(|t| format!("#{}", t)).collect();
+                                    format!(" {}", tags.join(" ").magenta())
+                                })
+                                .unwrap_or_default();
                             println!(
-                                "{:<5} {:<12} {:<10} {}",
-                                n.id, type_colored, n.status, n.title
+                                "{:<5} {:<12} {:<10} {}{}",
+                                n.id, type_colored, n.status, n.title, tag_str
                             );
                         }
                     }
@@ -1135,6 +1781,284 @@ fn main() {
             }
         }
 
+        Command::Tag { action } => match action {
+            TagAction::Add { id, tags } => match db.add_node_tags(id, &tags) {
+                Ok(added) => {
+                    println!("{} {} tag(s) to node {}", "Added".green(), added, id);
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            },
+            TagAction::Rm { id, tags } => match db.remove_node_tags(id, &tags) {
+                Ok(removed) => {
+                    println!("{} {} tag(s) from node {}", "Removed".green(), removed, id);
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            },
+            TagAction::Ls { id: Some(id) } => match db.get_node_tags(id) {
+                Ok(tags) if tags.is_empty() => println!("Node {} has no tags", id),
+                Ok(tags) => {
+                    for tag in tags {
+                        println!("{}", tag);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            },
+            TagAction::Ls { id: None } => match db.get_tag_counts() {
+                Ok(counts) if counts.is_empty() => {
+                    println!("No tags yet. Add one with: deciduous tag add <id> <tag>")
+                }
+                Ok(counts) => {
+                    println!("{:<24} NODES", "TAG");
+                    println!("{}", "-".repeat(32));
+                    for (tag, count) in counts {
+                        println!("{:<24} {}", tag, count);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            },
+        },
+
+        Command::Files { id } => match db.get_node_files(id) {
+            Ok(files) => {
+                if files.is_empty() {
+                    println!(
+                        "No files linked to node {}. Add them with: deciduous audit --associate-files",
+                        id
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        format!("{} file(s) for node {}:", files.len(), id).cyan()
+                    );
+                    for f in files {
+                        println!("  {}", f);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red(), e);
+                std::process::exit(1);
+            }
+        },
+
+        Command::Blame { file, json } => {
+            let lines = match deciduous::blame::run_git_blame(&file) {
+                Ok(l) => l,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let graph = match db.get_graph() {
+                Ok(g) => g,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let hunks = deciduous::blame::annotate_blame(&lines, &graph.nodes, &graph.edges);
+
+            if json {
+                let annotated: Vec<_> = hunks.iter().filter(|h| h.is_annotated()).collect();
+                let out = serde_json::json!({
+                    "file": file.display().to_string(),
+                    "hunks": annotated,
+                });
+                match serde_json::to_string_pretty(&out) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                for hunk in &hunks {
+                    if hunk.is_annotated() {
+                        let context = hunk.context_line();
+                        let label = if context.is_empty() {
+                            hunk.nodes
+                                .iter()
+                                .map(|n| format!("{} #{}: {}", n.node_type, n.id, n.title))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        } else {
+                            context
+                        };
+                        println!(
+                            "{} {} {}",
+                            "──".dimmed(),
+                            &hunk.commit[..7.min(hunk.commit.len())],
+                            label.cyan()
+                        );
+                    }
+                    for (i, content) in hunk.lines.iter().enumerate() {
+                        let gutter = if hunk.is_annotated() { "│" } else { " " };
+                        println!(
+                            "{:>5} {} {}",
+                            (hunk.start_line + i).to_string().dimmed(),
+                            gutter.cyan(),
+                            content
+                        );
+                    }
+                }
+            }
+        }
+
+        Command::Log {
+            range,
+            branch,
+            node_type,
+            graph,
+            all,
+            limit,
+        } => {
+            let lines = match get_git_log(range.as_deref(), limit, graph) {
+                Ok(l) => l,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let dag = match db.get_graph() {
+                Ok(g) => g,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+            let index = deciduous::blame::CommitIndex::new(&dag.nodes);
+
+            // Decision chains for each commit, after applying node filters
+            let chains: Vec<Vec<String>> = lines
+                .iter()
+                .map(|line| match &line.commit {
+                    Some(commit) => index
+                        .nodes_for(&commit.hash)
+                        .into_iter()
+                        .filter(|n| {
+                            branch.as_deref().map_or(true, |b| node_on_branch(n, b))
+                                && node_type.as_deref().map_or(true, |t| n.node_type == t)
+                        })
+                        .map(|n| decision_chain(n, &dag.nodes, &dag.edges))
+                        .collect(),
+                    None => Vec::new(),
+                })
+                .collect();
+
+            if graph {
+                let width = lines
+                    .iter()
+                    .map(|l| l.left_column().chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .min(72);
+                for (line, chains) in lines.iter().zip(&chains) {
+                    let left = truncate(&line.left_column(), width);
+                    let pad = " ".repeat(width.saturating_sub(left.chars().count()));
+                    match chains.split_first() {
+                        Some((first, rest)) => {
+                            println!("{}{}  {}", left, pad, first.cyan());
+                            let cont = line.continuation();
+                            let pad = " ".repeat(width.saturating_sub(cont.chars().count()));
+                            for chain in rest {
+                                println!("{}{}  {}", cont, pad, chain.cyan());
+                            }
+                        }
+                        None => println!("{}", left),
+                    }
+                }
+            } else {
+                let mut shown = 0;
+                for (line, chains) in lines.iter().zip(&chains) {
+                    let Some(commit) = &line.commit else {
+                        continue;
+                    };
+                    if chains.is_empty() && !all {
+                        continue;
+                    }
+                    shown += 1;
+                    println!(
+                        "{} {} {}",
+                        commit.short_hash.yellow(),
+                        commit.date.dimmed(),
+                        commit.subject
+                    );
+                    for chain in chains {
+                        println!("    {} {}", "└".dimmed(), chain.cyan());
+                    }
+                }
+                if shown == 0 {
+                    println!("No commits with linked decisions found.");
+                }
+            }
+        }
+
+        Command::Coverage { all, json } => {
+            let tracked = get_git_tracked_files();
+            if tracked.is_empty() {
+                eprintln!("{} No git-tracked files found", "Error:".red());
+                std::process::exit(1);
+            }
+            let linked: std::collections::HashSet<String> = match db.get_all_linked_files() {
+                Ok(files) => files.into_iter().collect(),
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    std::process::exit(1);
+                }
+            };
+
+            let files: Vec<String> = tracked
+                .into_iter()
+                .filter(|f| all || is_source_file(f))
+                .collect();
+            let report = file_coverage(&files, &linked);
+
+            if json {
+                match serde_json::to_string_pretty(&report) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                println!(
+                    "{} {}/{} files have linked decisions ({:.1}%)",
+                    "Coverage:".cyan(),
+                    report.covered_files,
+                    report.total_files,
+                    report.coverage_percent
+                );
+                for dir in report
+                    .directories
+                    .iter()
+                    .filter(|d| !d.uncovered.is_empty())
+                {
+                    println!(
+                        "\n{} {}/{} covered",
+                        format!("{}/", dir.directory).bold(),
+                        dir.covered,
+                        dir.total
+                    );
+                    for f in &dir.uncovered {
+                        println!("  {} {}", "○".yellow(), f);
+                    }
+                }
+            }
+        }
+
         Command::Edges => match db.get_all_edges() {
             Ok(edges) => {
                 if edges.is_empty() {
@@ -1611,26 +2535,41 @@ fn main() {
                     branch,
                     author,
                     base_commit,
+                    sign,
+                    key,
                 } => {
                     // Parse node IDs if provided
                     let node_ids = nodes.as_ref().map(|n| parse_node_range(n));
 
                     match db.export_patch(node_ids, branch.as_deref(), author, base_commit) {
-                        Ok(patch) => match patch.save(&output) {
-                            Ok(()) => {
-                                println!(
-                                    "{} Exported {} nodes and {} edges to {}",
-                                    "Success:".green(),
-                                    patch.nodes.len(),
-                                    patch.edges.len(),
-                                    output.display()
-                                );
-                            }
-                            Err(e) => {
+                        Ok(mut patch) => {
+                            if let Err(e) = sign_patch(&mut patch, sign, key) {
                                 eprintln!("{} {}", "Error:".red(), e);
                                 std::process::exit(1);
                             }
-                        },
+
+                            match patch.save(&output) {
+                                Ok(()) => {
+                                    let signed_str = patch
+                                        .signer
+                                        .as_ref()
+                                        .map(|s| format!(" (signed by {})", s))
+                                        .unwrap_or_default();
+                                    println!(
+                                        "{} Exported {} nodes and {} edges to {}{}",
+                                        "Success:".green(),
+                                        patch.nodes.len(),
+                                        patch.edges.len(),
+                                        output.display(),
+                                        signed_str
+                                    );
+                                }
+                                Err(e) => {
+                                    eprintln!("{} {}", "Error:".red(), e);
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
                         Err(e) => {
                             eprintln!("{} {}", "Error:".red(), e);
                             std::process::exit(1);
@@ -1639,57 +2578,106 @@ fn main() {
                 }
 
                 DiffAction::Apply { files, dry_run } => {
+                    let patches_config = Config::load().patches;
                     let mut total_added = 0;
                     let mut total_skipped = 0;
                     let mut total_edges_added = 0;
                     let mut total_edges_skipped = 0;
+                    let mut rejected = 0;
 
                     for file in files {
-                        match deciduous::GraphPatch::load(&file) {
-                            Ok(patch) => match db.apply_patch(&patch, dry_run) {
-                                Ok(result) => {
-                                    if dry_run {
-                                        println!(
-                                            "{} {} (dry run)",
-                                            "Would apply:".cyan(),
-                                            file.display()
-                                        );
-                                    } else {
-                                        println!("{} {}", "Applied:".green(), file.display());
-                                    }
+                        let patch = match deciduous::GraphPatch::load(&file) {
+                            Ok(patch) => patch,
+                            Err(e) => {
+                                eprintln!("{} Loading {}: {}", "Error:".red(), file.display(), e);
+                                continue;
+                            }
+                        };
+
+                        // A bad signature is always refused; a missing or untrusted
+                        // one only when the config asks for signed patches
+                        let signer = match patch.verify(&patches_config.allowed_signers) {
+                            deciduous::PatchVerification::Verified(signer) => Some(signer),
+                            deciduous::PatchVerification::Unsigned
+                                if !patches_config.require_signed =>
+                            {
+                                None
+                            }
+                            deciduous::PatchVerification::Unsigned => {
+                                eprintln!(
+                                    "{} {}: patch is unsigned (require_signed is set)",
+                                    "Rejected:".red(),
+                                    file.display()
+                                );
+                                rejected += 1;
+                                continue;
+                            }
+                            deciduous::PatchVerification::UnknownSigner(reason)
+                                if !patches_config.require_signed =>
+                            {
+                                eprintln!(
+                                    "{} {}: {}",
+                                    "Unverified:".yellow(),
+                                    file.display(),
+                                    reason
+                                );
+                                None
+                            }
+                            deciduous::PatchVerification::UnknownSigner(reason) => {
+                                eprintln!(
+                                    "{} {}: {} (require_signed is set)",
+                                    "Rejected:".red(),
+                                    file.display(),
+                                    reason
+                                );
+                                rejected += 1;
+                                continue;
+                            }
+                            deciduous::PatchVerification::Invalid(reason) => {
+                                eprintln!("{} {}: {}", "Rejected:".red(), file.display(), reason);
+                                rejected += 1;
+                                continue;
+                            }
+                        };
+
+                        match db.apply_patch_signed_by(&patch, dry_run, signer.as_deref()) {
+                            Ok(result) => {
+                                if dry_run {
                                     println!(
-                                        "  Nodes: {} added, {} skipped",
-                                        result.nodes_added, result.nodes_skipped
+                                        "{} {} (dry run)",
+                                        "Would apply:".cyan(),
+                                        file.display()
                                     );
+                                } else {
+                                    println!("{} {}", "Applied:".green(), file.display());
+                                }
+                                println!(
+                                    "  Nodes: {} added, {} skipped",
+                                    result.nodes_added, result.nodes_skipped
+                                );
+                                println!(
+                                    "  Edges: {} added, {} skipped",
+                                    result.edges_added, result.edges_skipped
+                                );
+                                if result.tags_added > 0 {
+                                    println!("  Tags: {} added", result.tags_added);
+                                }
+                                if !result.edges_failed.is_empty() {
                                     println!(
-                                        "  Edges: {} added, {} skipped",
-                                        result.edges_added, result.edges_skipped
+                                        "  {} edges failed (missing nodes):",
+                                        result.edges_failed.len()
                                     );
-                                    if !result.edges_failed.is_empty() {
-                                        println!(
-                                            "  {} edges failed (missing nodes):",
-                                            result.edges_failed.len()
-                                        );
-                                        for msg in &result.edges_failed {
-                                            println!("    - {}", msg);
-                                        }
+                                    for msg in &result.edges_failed {
+                                        println!("    - {}", msg);
                                     }
-                                    total_added += result.nodes_added;
-                                    total_skipped += result.nodes_skipped;
-                                    total_edges_added += result.edges_added;
-                                    total_edges_skipped += result.edges_skipped;
-                                }
-                                Err(e) => {
-                                    eprintln!(
-                                        "{} Applying {}: {}",
-                                        "Error:".red(),
-                                        file.display(),
-                                        e
-                                    );
                                 }
-                            },
+                                total_added += result.nodes_added;
+                                total_skipped += result.nodes_skipped;
+                                total_edges_added += result.edges_added;
+                                total_edges_skipped += result.edges_skipped;
+                            }
                             Err(e) => {
-                                eprintln!("{} Loading {}: {}", "Error:".red(), file.display(), e);
+                                eprintln!("{} Applying {}: {}", "Error:".red(), file.display(), e);
                             }
                         }
                     }
@@ -1704,19 +2692,209 @@ fn main() {
                             total_edges_skipped
                         );
                     }
+                    if rejected > 0 {
+                        eprintln!("{} {} patch(es) rejected", "Error:".red(), rejected);
+                        std::process::exit(1);
+                    }
                 }
 
-                DiffAction::Status { path } => {
-                    let patches_dir = path.unwrap_or_else(|| PathBuf::from(".deciduous/patches"));
-                    if !patches_dir.exists() {
-                        println!(
-                            "{} No patches directory found at {}",
-                            "Info:".cyan(),
-                            patches_dir.display()
-                        );
-                        println!("Create one with: mkdir -p {}", patches_dir.display());
-                        return;
-                    }
+                DiffAction::Squash {
+                    files,
+                    output,
+                    delete,
+                    sign,
+                    key,
+                } => {
+                    let mut patches = Vec::new();
+                    for file in &files {
+                        match deciduous::GraphPatch::load(file) {
+                            Ok(patch) => patches.push(patch),
+                            Err(e) => {
+                                eprintln!("{} Loading {}: {}", "Error:".red(), file.display(), e);
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+
+                    let mut squashed = deciduous::GraphPatch::squash(&patches);
+                    if let Err(e) = sign_patch(&mut squashed, sign, key) {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                    if let Err(e) = squashed.save(&output) {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+
+                    let total_nodes: usize = patches.iter().map(|p| p.nodes.len()).sum();
+                    let total_edges: usize = patches.iter().map(|p| p.edges.len()).sum();
+                    println!(
+                        "{} Squashed {} patches into {}: {} nodes, {} edges ({} duplicates dropped)",
+                        "Success:".green(),
+                        patches.len(),
+                        output.display(),
+                        squashed.nodes.len(),
+                        squashed.edges.len(),
+                        total_nodes + total_edges - squashed.nodes.len() - squashed.edges.len()
+                    );
+
+                    if delete {
+                        let output = output.canonicalize().unwrap_or(output);
+                        for file in &files {
+                            if file.canonicalize().ok().as_ref() == Some(&output) {
+                                continue;
+                            }
+                            if let Err(e) = std::fs::remove_file(file) {
+                                eprintln!(
+                                    "{} Could not delete {}: {}",
+                                    "Warning:".yellow(),
+                                    file.display(),
+                                    e
+                                );
+                            }
+                        }
+                    }
+                }
+
+                DiffAction::Gc {
+                    base,
+                    graph,
+                    path,
+                    dry_run,
+                } => {
+                    let patches_dir = path.unwrap_or_else(|| PathBuf::from(".deciduous/patches"));
+                    let contents = match git_show_file(&base, &graph)
+                        .and_then(|json| deciduous::GraphContents::from_json(&json))
+                    {
+                        Ok(contents) => contents,
+                        Err(e) => {
+                            eprintln!(
+                                "{} Reading {} from {}: {}",
+                                "Error:".red(),
+                                graph.display(),
+                                base,
+                                e
+                            );
+                            std::process::exit(1);
+                        }
+                    };
+
+                    let mut entries: Vec<PathBuf> = match std::fs::read_dir(&patches_dir) {
+                        Ok(entries) => entries
+                            .flatten()
+                            .map(|e| e.path())
+                            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+                            .collect(),
+                        Err(e) => {
+                            eprintln!(
+                                "{} Reading {}: {}",
+                                "Error:".red(),
+                                patches_dir.display(),
+                                e
+                            );
+                            std::process::exit(1);
+                        }
+                    };
+                    entries.sort();
+
+                    let mut removed = 0;
+                    for path in entries {
+                        let Ok(patch) = deciduous::GraphPatch::load(&path) else {
+                            continue;
+                        };
+                        if !patch.is_contained_in(&contents) {
+                            continue;
+                        }
+                        if dry_run {
+                            println!("{} {}", "Would delete:".cyan(), path.display());
+                        } else if let Err(e) = std::fs::remove_file(&path) {
+                            eprintln!(
+                                "{} Could not delete {}: {}",
+                                "Warning:".yellow(),
+                                path.display(),
+                                e
+                            );
+                            continue;
+                        } else {
+                            println!("{} {}", "Deleted:".green(), path.display());
+                        }
+                        removed += 1;
+                    }
+
+                    println!(
+                        "{} {} patch(es) fully present in {}",
+                        "Total:".cyan(),
+                        removed,
+                        base
+                    );
+                }
+
+                DiffAction::Verify {
+                    files,
+                    allowed_signers,
+                } => {
+                    let patches_config = Config::load().patches;
+                    let allowed_signers = allowed_signers.unwrap_or(patches_config.allowed_signers);
+                    let mut failed = 0;
+
+                    for file in &files {
+                        let patch = match deciduous::GraphPatch::load(file) {
+                            Ok(patch) => patch,
+                            Err(e) => {
+                                eprintln!("{} Loading {}: {}", "Error:".red(), file.display(), e);
+                                failed += 1;
+                                continue;
+                            }
+                        };
+                        match patch.verify(&allowed_signers) {
+                            deciduous::PatchVerification::Verified(signer) => {
+                                println!(
+                                    "{} {} (signed by {})",
+                                    "OK:".green(),
+                                    file.display(),
+                                    signer
+                                )
+                            }
+                            deciduous::PatchVerification::Unsigned => {
+                                if patches_config.require_signed {
+                                    failed += 1;
+                                }
+                                println!("{} {}", "Unsigned:".yellow(), file.display())
+                            }
+                            deciduous::PatchVerification::UnknownSigner(reason) => {
+                                if patches_config.require_signed {
+                                    failed += 1;
+                                }
+                                println!(
+                                    "{} {}: {}",
+                                    "Unverified:".yellow(),
+                                    file.display(),
+                                    reason
+                                )
+                            }
+                            deciduous::PatchVerification::Invalid(reason) => {
+                                failed += 1;
+                                println!("{} {}: {}", "Invalid:".red(), file.display(), reason)
+                            }
+                        }
+                    }
+
+                    if failed > 0 {
+                        std::process::exit(1);
+                    }
+                }
+
+                DiffAction::Status { path } => {
+                    let patches_dir = path.unwrap_or_else(|| PathBuf::from(".deciduous/patches"));
+                    if !patches_dir.exists() {
+                        println!(
+                            "{} No patches directory found at {}",
+                            "Info:".cyan(),
+                            patches_dir.display()
+                        );
+                        println!("Create one with: mkdir -p {}", patches_dir.display());
+                        return;
+                    }
 
                     // List all .json files in the directory
                     let entries = match std::fs::read_dir(&patches_dir) {
@@ -1734,13 +2912,20 @@ fn main() {
                             if let Ok(patch) = deciduous::GraphPatch::load(&path) {
                                 let author = patch.author.as_deref().unwrap_or("unknown");
                                 let branch = patch.branch.as_deref().unwrap_or("unknown");
+                                let signed_str = patch
+                                    .signer
+                                    .as_ref()
+                                    .filter(|_| patch.signature.is_some())
+                                    .map(|s| format!(", signed by: {}", s))
+                                    .unwrap_or_default();
                                 println!(
-                                    "  {} - {} nodes, {} edges (author: {}, branch: {})",
+                                    "  {} - {} nodes, {} edges (author: {}, branch: {}{})",
                                     path.file_name().unwrap_or_default().to_string_lossy(),
                                     patch.nodes.len(),
                                     patch.edges.len(),
                                     author,
-                                    branch
+                                    branch,
+                                    signed_str
                                 );
                             }
                         }
@@ -1845,18 +3030,80 @@ fn main() {
 
         Command::Audit {
             associate_commits,
+            associate_files,
+            find_duplicates,
+            merge,
+            lint,
+            json,
             min_score,
             dry_run,
             yes,
         } => {
-            if !associate_commits {
+            if !associate_commits && !associate_files && !find_duplicates && !lint {
                 eprintln!(
-                    "{} No audit action specified. Use --associate-commits",
+                    "{} No audit action specified. Use --associate-commits, --associate-files, --find-duplicates or --lint",
                     "Error:".red()
                 );
                 std::process::exit(1);
             }
 
+            if lint {
+                let graph = match db.get_graph() {
+                    Ok(g) => g,
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                };
+                let report = lint_graph(&graph.nodes, &graph.edges, &Config::load().lint);
+
+                if json {
+                    match serde_json::to_string_pretty(&report) {
+                        Ok(out) => println!("{}", out),
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    for name in &report.unknown_rules {
+                        eprintln!(
+                            "{} Unknown lint rule '{}' in config.toml",
+                            "Warning:".yellow(),
+                            name
+                        );
+                    }
+                    for f in &report.findings {
+                        let level = match f.level {
+                            LintLevel::Error => "error".red(),
+                            _ => "warning".yellow(),
+                        };
+                        println!("{}[{}] {}", level, f.rule, f.message);
+                    }
+                    println!(
+                        "\n{} {} error(s), {} warning(s)",
+                        "Lint:".cyan(),
+                        report.errors,
+                        report.warnings
+                    );
+                }
+
+                if report.has_errors() {
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            if find_duplicates {
+                audit_find_duplicates(&db, min_score, dry_run, yes, merge);
+            }
+            if associate_files {
+                audit_associate_files(&db, min_score, dry_run, yes);
+            }
+            if !associate_commits {
+                return;
+            }
+
             // Get all nodes
             let nodes = match db.get_all_nodes() {
                 Ok(n) => n,
@@ -2033,119 +3280,386 @@ fn main() {
                 applied,
                 failed
             );
-        }
 
-        Command::Roadmap { action } => {
-            match action {
-                RoadmapAction::Init { path } => {
-                    let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
+            if applied > 0 && Config::load().git.notes {
+                let hashes: Vec<&str> = matches.iter().map(|m| m.commit_hash.as_str()).collect();
+                if let Err(e) = mirror_commit_notes(&db, &hashes) {
+                    eprintln!("{} Could not write git notes: {}", "Warning:".yellow(), e);
+                }
+            }
+        }
 
-                    if !roadmap_path.exists() {
-                        eprintln!(
-                            "{} File not found: {}",
-                            "Error:".red(),
-                            roadmap_path.display()
-                        );
+        Command::Notes { action } => match action {
+            NotesAction::Export { dry_run } => {
+                let nodes = match db.get_all_nodes() {
+                    Ok(n) => n,
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
                         std::process::exit(1);
                     }
-
-                    // Parse the roadmap
-                    let parsed = match parse_roadmap(&roadmap_path) {
-                        Ok(p) => p,
-                        Err(e) => {
-                            eprintln!("{} Parsing roadmap: {}", "Error:".red(), e);
-                            std::process::exit(1);
+                };
+                let index = deciduous::blame::CommitIndex::new(&nodes);
+
+                let mut seen = std::collections::HashSet::new();
+                let mut written = 0;
+                let mut missing = 0;
+                for hash in extract_commit_hashes(&nodes) {
+                    let full = match deciduous::notes::resolve_commit(&hash) {
+                        Ok(f) => f,
+                        Err(_) => {
+                            missing += 1;
+                            continue;
                         }
                     };
+                    if !seen.insert(full.clone()) {
+                        continue;
+                    }
+                    let linked = index.nodes_for(&full);
+                    if dry_run {
+                        println!(
+                            "{} {} ({} nodes)",
+                            "Would write:".cyan(),
+                            &full[..7],
+                            linked.len()
+                        );
+                        written += 1;
+                        continue;
+                    }
+                    let note = deciduous::notes::format_note(&linked);
+                    match deciduous::notes::write_note(&full, &note) {
+                        Ok(()) => written += 1,
+                        Err(e) => {
+                            eprintln!("{} {}: {}", "Failed:".red(), &full[..7], e);
+                        }
+                    }
+                }
 
+                println!(
+                    "{} {} notes {} to {}{}",
+                    if dry_run {
+                        "Dry run:".yellow()
+                    } else {
+                        "Done:".green()
+                    },
+                    written,
+                    if dry_run {
+                        "would be written"
+                    } else {
+                        "written"
+                    },
+                    deciduous::notes::NOTES_REF,
+                    if missing > 0 {
+                        format!(" ({} commits not in this repository)", missing)
+                    } else {
+                        String::new()
+                    }
+                );
+                if !dry_run && written > 0 {
                     println!(
-                        "{} Found {} sections in {}",
-                        "Parsed:".green(),
-                        parsed.sections.len(),
-                        roadmap_path.display()
+                        "Share them with: git push origin {}",
+                        deciduous::notes::NOTES_REF
                     );
+                }
+            }
 
-                    // Read original content for rewriting
-                    let content = match std::fs::read_to_string(&roadmap_path) {
-                        Ok(c) => c,
-                        Err(e) => {
-                            eprintln!("{} Reading file: {}", "Error:".red(), e);
-                            std::process::exit(1);
-                        }
-                    };
-
-                    // Write back with metadata
-                    let updated = match write_roadmap_with_metadata(
-                        &roadmap_path,
-                        &parsed.sections,
-                        &content,
-                    ) {
-                        Ok(u) => u,
-                        Err(e) => {
-                            eprintln!("{} Writing metadata: {}", "Error:".red(), e);
-                            std::process::exit(1);
-                        }
-                    };
-                    if let Err(e) = std::fs::write(&roadmap_path, &updated) {
-                        eprintln!("{} Writing file: {}", "Error:".red(), e);
+            NotesAction::Import { dry_run } => {
+                let notes = match deciduous::notes::read_notes() {
+                    Ok(n) => n,
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
                         std::process::exit(1);
                     }
+                };
+                let nodes = match db.get_all_nodes() {
+                    Ok(n) => n,
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+                };
 
-                    // Track current level-2 parent section for grouping
-                    let mut current_l2_parent: Option<String> = None;
-
-                    // Store sections in database
-                    for section in &parsed.sections {
-                        // Level 2 headers (## Section) are top-level groupings
-                        // Level 3 headers (### Subsection) contain the actual tasks
-                        let (section_parent, items_section) = if section.level == 2 {
-                            current_l2_parent = Some(section.title.clone());
-                            // Level 2 sections have no parent, their items go under them
-                            (None, Some(section.title.as_str()))
-                        } else {
-                            // Level 3 sections belong to the current L2 parent
-                            // Their items belong directly to this L3 section
-                            (current_l2_parent.as_deref(), Some(section.title.as_str()))
+                let mut linked = 0;
+                let mut unchanged = 0;
+                let mut conflicts = 0;
+                let mut unknown = 0;
+                for (commit, text) in &notes {
+                    for change_id in deciduous::notes::parse_decision_ids(text) {
+                        let change_id = db.resolve_change_id(&change_id).unwrap_or(change_id);
+                        let Some(node) = nodes.iter().find(|n| n.change_id == change_id) else {
+                            unknown += 1;
+                            continue;
                         };
 
-                        // Create the section header entry (checkbox_state = "none")
-                        if let Err(e) = db.create_roadmap_item(
-                            &section.title,
-                            section.description.as_deref(),
-                            section_parent,
-                            None, // parent_id - we don't track hierarchy by ID yet
-                            "none",
-                        ) {
-                            eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
-                        }
-
-                        // Create items for checkboxes - they belong to THIS section
-                        for item in &section.items {
-                            let state = if item.checked { "checked" } else { "unchecked" };
-                            if let Err(e) = db.create_roadmap_item(
-                                &item.text,
-                                None,
-                                items_section, // Items belong to the section that contains them
-                                None,          // parent_id
-                                state,
-                            ) {
-                                eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
+                        match node_commit(node) {
+                            Some(existing) if commit.starts_with(&existing) => {
+                                unchanged += 1;
+                            }
+                            Some(existing) => {
+                                conflicts += 1;
+                                println!(
+                                    "{} Node #{} is linked to {}, note says {}",
+                                    "Skipped:".yellow(),
+                                    node.id,
+                                    &existing[..7.min(existing.len())],
+                                    &commit[..7]
+                                );
+                            }
+                            None if dry_run => {
+                                linked += 1;
+                                println!(
+                                    "{} Node #{} <- {}",
+                                    "Would link:".cyan(),
+                                    node.id,
+                                    &commit[..7]
+                                );
                             }
+                            None => match db.update_node_commit(node.id, commit) {
+                                Ok(()) => {
+                                    linked += 1;
+                                    println!(
+                                        "{} Node #{} <- {}",
+                                        "Linked:".green(),
+                                        node.id,
+                                        &commit[..7]
+                                    );
+                                }
+                                Err(e) => {
+                                    eprintln!("{} Node #{}: {}", "Failed:".red(), node.id, e);
+                                }
+                            },
                         }
                     }
+                }
 
-                    // Count items
-                    let total_items: usize = parsed.sections.iter().map(|s| s.items.len()).sum();
+                println!(
+                    "\n{} {} linked, {} already linked, {} conflicting, {} unknown nodes ({} notes)",
+                    if dry_run { "Dry run:".yellow() } else { "Done:".green() },
+                    linked,
+                    unchanged,
+                    conflicts,
+                    unknown,
+                    notes.len()
+                );
+            }
+        },
+
+        Command::Hooks { action } => match action {
+            HooksAction::Install => {
+                for hook in ["prepare-commit-msg", "post-commit"] {
+                    match deciduous::notes::install_hook(hook) {
+                        Ok(path) => println!("{} {}", "Installed:".green(), path.display()),
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                if !Config::load().git.trailers {
                     println!(
-                        "{} Initialized {} sections with {} items",
-                        "Success:".green(),
-                        parsed.sections.len(),
-                        total_items
+                        "Commits referencing nodes (Decision-Id:, Refs-Node: or node#<id>) will be linked.\n\
+                         To add Decision-Id trailers automatically, set in .deciduous/config.toml:\n\n  [git]\n  trailers = true"
                     );
-                    println!("  Metadata comments added to {}", roadmap_path.display());
                 }
+            }
 
-                RoadmapAction::Refresh { path } => {
+            HooksAction::PostCommit => {
+                // A failing hook shouldn't get in the way of committing
+                let Some(commit) = deciduous::get_current_git_commit() else {
+                    return;
+                };
+                let Some(message) = get_git_commit_info(&commit).map(|c| c.message) else {
+                    return;
+                };
+                let refs = deciduous::notes::parse_commit_refs(&message);
+                if refs.is_empty() {
+                    return;
+                }
+                let Ok(nodes) = db.get_all_nodes() else {
+                    return;
+                };
+
+                let mut ids: Vec<i32> = Vec::new();
+                for change_id in &refs.change_ids {
+                    let change_id = db
+                        .resolve_change_id(change_id)
+                        .unwrap_or_else(|_| change_id.clone());
+                    if let Some(node) = nodes.iter().find(|n| n.change_id == change_id) {
+                        ids.push(node.id);
+                    }
+                }
+                for id in &refs.node_ids {
+                    if nodes.iter().any(|n| n.id == *id) {
+                        ids.push(*id);
+                    }
+                }
+                ids.sort_unstable();
+                ids.dedup();
+                if ids.is_empty() {
+                    return;
+                }
+
+                let changed = get_commit_changed_files(&commit);
+                let mut linked = Vec::new();
+                for id in &ids {
+                    let existing = nodes.iter().find(|n| n.id == *id).and_then(node_commit);
+                    match existing {
+                        // A node keeps the commit it was first linked to
+                        Some(existing) if !commit.starts_with(&existing) => {
+                            eprintln!(
+                                "{} Node #{} is already linked to {}, not relinking",
+                                "deciduous:".yellow(),
+                                id,
+                                &existing[..7.min(existing.len())]
+                            );
+                            continue;
+                        }
+                        Some(_) => {}
+                        None => {
+                            if let Err(e) = db.update_node_commit(*id, &commit) {
+                                eprintln!("{} Node #{}: {}", "deciduous:".yellow(), id, e);
+                                continue;
+                            }
+                        }
+                    }
+                    if !changed.is_empty() {
+                        let mut files = db.get_node_files(*id).unwrap_or_default();
+                        for f in &changed {
+                            if !files.contains(f) {
+                                files.push(f.clone());
+                            }
+                        }
+                        if let Err(e) = db.update_node_files(*id, &files) {
+                            eprintln!("{} Node #{}: {}", "deciduous:".yellow(), id, e);
+                        }
+                    }
+                    linked.push(format!("#{}", id));
+                }
+
+                if !linked.is_empty() {
+                    println!(
+                        "{} linked {} to {} ({} files)",
+                        "deciduous:".cyan(),
+                        linked.join(", "),
+                        commit,
+                        changed.len()
+                    );
+                    if Config::load().git.notes {
+                        if let Err(e) = mirror_commit_notes(&db, &[commit.as_str()]) {
+                            eprintln!("{} Could not write git note: {}", "Warning:".yellow(), e);
+                        }
+                    }
+                }
+            }
+
+            HooksAction::PrepareCommitMsg {
+                file,
+                source,
+                sha: _,
+            } => {
+                // Merges and squashes describe other people's work
+                if !Config::load().git.trailers
+                    || matches!(source.as_deref(), Some("merge") | Some("squash"))
+                {
+                    return;
+                }
+                let Ok(nodes) = db.get_all_nodes() else {
+                    return;
+                };
+                let branch = deciduous::get_current_git_branch();
+                let since = deciduous::notes::head_commit_time();
+                let ids: Vec<String> = deciduous::notes::pending_trailer_nodes(
+                    &nodes,
+                    branch.as_deref(),
+                    since.as_deref(),
+                )
+                .iter()
+                .map(|n| n.change_id.clone())
+                .collect();
+                if ids.is_empty() {
+                    return;
+                }
+
+                let Ok(message) = std::fs::read_to_string(&file) else {
+                    return;
+                };
+                let updated = deciduous::notes::add_trailers(&message, &ids);
+                if let Err(e) = std::fs::write(&file, updated) {
+                    eprintln!("{} {}", "Warning:".yellow(), e);
+                }
+            }
+        },
+
+        Command::Roadmap { action } => {
+            match action {
+                RoadmapAction::Init { path } => {
+                    let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
+
+                    if !roadmap_path.exists() {
+                        eprintln!(
+                            "{} File not found: {}",
+                            "Error:".red(),
+                            roadmap_path.display()
+                        );
+                        std::process::exit(1);
+                    }
+
+                    // Parse the roadmap
+                    let parsed = match parse_roadmap(&roadmap_path) {
+                        Ok(p) => p,
+                        Err(e) => {
+                            eprintln!("{} Parsing roadmap: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+
+                    println!(
+                        "{} Found {} sections in {}",
+                        "Parsed:".green(),
+                        parsed.sections.len(),
+                        roadmap_path.display()
+                    );
+
+                    // Read original content for rewriting
+                    let content = match std::fs::read_to_string(&roadmap_path) {
+                        Ok(c) => c,
+                        Err(e) => {
+                            eprintln!("{} Reading file: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+
+                    // Write back with metadata
+                    let updated = match write_roadmap_with_metadata(
+                        &roadmap_path,
+                        &parsed.sections,
+                        &content,
+                    ) {
+                        Ok(u) => u,
+                        Err(e) => {
+                            eprintln!("{} Writing metadata: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    if let Err(e) = std::fs::write(&roadmap_path, &updated) {
+                        eprintln!("{} Writing file: {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+
+                    // Store sections in database
+                    store_roadmap_sections(&db, &roadmap_path, &parsed.sections);
+
+                    // Count items
+                    let total_items: usize = parsed.sections.iter().map(|s| s.items.len()).sum();
+                    println!(
+                        "{} Initialized {} sections with {} items",
+                        "Success:".green(),
+                        parsed.sections.len(),
+                        total_items
+                    );
+                    println!("  Metadata comments added to {}", roadmap_path.display());
+                }
+
+                RoadmapAction::Refresh { path } => {
                     let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
 
                     if !roadmap_path.exists() {
@@ -2157,8 +3671,20 @@ fn main() {
                         std::process::exit(1);
                     }
 
-                    // Clear existing roadmap items
-                    let cleared = match db.clear_roadmap_items() {
+                    // Keep checkbox sync bases across the re-import, and note
+                    // the old checkbox states for the progress history
+                    let previous = db.get_all_roadmap_items().unwrap_or_default();
+                    let previous_checkboxes: std::collections::HashMap<String, String> = previous
+                        .iter()
+                        .map(|i| (i.change_id.clone(), i.checkbox_state.clone()))
+                        .collect();
+                    let sync_bases: Vec<_> = previous
+                        .into_iter()
+                        .filter(|i| i.synced_checkbox_state.is_some())
+                        .collect();
+
+                    // Clear this file's roadmap items; other files keep theirs
+                    let cleared = match db.clear_roadmap_items_in(&roadmap_key(&roadmap_path)) {
                         Ok(n) => n,
                         Err(e) => {
                             eprintln!("{} Clearing roadmap items: {}", "Error:".red(), e);
@@ -2180,40 +3706,42 @@ fn main() {
                         }
                     };
 
-                    // Track current level-2 parent section for grouping
-                    let mut current_l2_parent: Option<String> = None;
-
                     // Store sections in database
-                    for section in &parsed.sections {
-                        let (section_parent, items_section) = if section.level == 2 {
-                            current_l2_parent = Some(section.title.clone());
-                            (None, Some(section.title.as_str()))
-                        } else {
-                            (current_l2_parent.as_deref(), Some(section.title.as_str()))
-                        };
-
-                        // Create the section header entry
-                        if let Err(e) = db.create_roadmap_item(
-                            &section.title,
-                            section.description.as_deref(),
-                            section_parent,
-                            None,
-                            "none",
-                        ) {
-                            eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
-                        }
+                    store_roadmap_sections(&db, &roadmap_path, &parsed.sections);
 
-                        // Create items for checkboxes
-                        for item in &section.items {
-                            let state = if item.checked { "checked" } else { "unchecked" };
+                    for item in parsed.sections.iter().flat_map(|s| &s.items) {
+                        let state = CheckboxState::from_bool(item.checked).as_str();
+                        let old = previous_checkboxes.get(&item.change_id).map(String::as_str);
+                        // New items start a history only once they are done
+                        if old.map_or(item.checked, |old| old != state) {
                             if let Err(e) =
-                                db.create_roadmap_item(&item.text, None, items_section, None, state)
+                                db.record_roadmap_checkbox_change(&item.change_id, old, state)
                             {
-                                eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
+                                eprintln!("{} Recording history: {}", "Warning:".yellow(), e);
+                            }
+                        }
+                    }
+
+                    for item in &sync_bases {
+                        if let (Some(state), Some(synced_at), Ok(Some(_))) = (
+                            &item.synced_checkbox_state,
+                            &item.last_synced_at,
+                            db.get_roadmap_item_by_change_id(&item.change_id),
+                        ) {
+                            if let Err(e) = db.set_roadmap_item_sync_base(
+                                &item.change_id,
+                                &item.title,
+                                item.section.as_deref(),
+                                state,
+                                synced_at,
+                            ) {
+                                eprintln!("{} Restoring sync state: {}", "Warning:".yellow(), e);
                             }
                         }
                     }
 
+                    sync_goal_completion(&db, &roadmap_path);
+
                     let total_items: usize = parsed.sections.iter().map(|s| s.items.len()).sum();
                     println!(
                         "{} Refreshed {} sections with {} items",
@@ -2242,29 +3770,42 @@ fn main() {
                         std::process::exit(1);
                     }
 
-                    // Initialize GitHub client
-                    let gh_client = match repo {
-                        Some(r) => GitHubClient::new(Some(r)),
-                        None => match GitHubClient::auto_detect() {
-                            Ok(c) => c,
-                            Err(e) => {
-                                eprintln!("{} Auto-detecting repo: {}", "Error:".red(), e);
+                    // Initialize the configured issue tracker
+                    let tracker = match open_tracker(&Config::load().tracker, repo) {
+                        Ok(t) => t,
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            if !matches!(e, TrackerError::NotConfigured { .. }) {
                                 eprintln!("Specify repo with --repo owner/repo");
-                                std::process::exit(1);
                             }
-                        },
+                            std::process::exit(1);
+                        }
                     };
 
                     // Check auth
-                    match GitHubClient::check_auth() {
+                    match tracker.check_auth() {
                         Ok(true) => {}
-                        Ok(false) | Err(_) => {
-                            eprintln!("{} Not authenticated with GitHub", "Error:".red());
-                            eprintln!("Run 'gh auth login' first");
+                        Ok(false) => {
+                            eprintln!(
+                                "{} Not authenticated with {}",
+                                "Error:".red(),
+                                tracker.name()
+                            );
+                            eprintln!("{}", tracker.auth_hint());
+                            std::process::exit(1);
+                        }
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            eprintln!("{}", tracker.auth_hint());
                             std::process::exit(1);
                         }
                     }
 
+                    // Completed goals tick their items before the issues are updated
+                    if !dry_run {
+                        sync_goal_completion(&db, &roadmap_path);
+                    }
+
                     // Parse roadmap
                     let parsed = match parse_roadmap(&roadmap_path) {
                         Ok(p) => p,
@@ -2292,13 +3833,13 @@ fn main() {
                         );
                     }
 
-                    if let Some(repo_name) = gh_client.repo_name() {
-                        println!("  Repository: {}", repo_name);
+                    if let Some(repo_name) = tracker.repo_name() {
+                        println!("  {}: {}", tracker.name(), repo_name);
                     }
 
                     // Ensure 'roadmap' label exists if we're creating issues
                     if !dry_run && create_issues {
-                        match ensure_roadmap_label(&gh_client) {
+                        match ensure_roadmap_label(tracker.as_ref()) {
                             Ok(true) => println!("  {} Created 'roadmap' label", "✓".green()),
                             Ok(false) => {} // Label already exists
                             Err(e) => eprintln!(
@@ -2312,15 +3853,124 @@ fn main() {
                     let mut created = 0;
                     let mut updated = 0;
                     let mut skipped = 0;
+                    let mut pulled = 0;
+                    let mut conflicts = 0;
+
+                    // Sections whose ROADMAP.md metadata or checkboxes changed
+                    let mut changed_sections: std::collections::HashMap<String, RoadmapSection> =
+                        std::collections::HashMap::new();
+
+                    // Tracker milestones, looked up on first use; a milestone
+                    // spanning several sections is due on the earliest date
+                    let mut milestones: std::collections::HashMap<String, Milestone> =
+                        std::collections::HashMap::new();
+                    let milestone_due: std::collections::HashMap<String, Option<String>> =
+                        milestone_progress(&parsed.sections)
+                            .into_iter()
+                            .map(|m| (m.name, m.due.map(|d| d.format("%Y-%m-%d").to_string())))
+                            .collect();
+
+                    // Conflicts already waiting for a decision aren't recorded twice
+                    let known_conflicts: std::collections::HashSet<(String, String)> = db
+                        .get_unresolved_conflicts()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|c| (c.item_change_id, c.conflict_type))
+                        .collect();
 
                     for section in &syncable_sections {
                         // Check if section already has an issue
-                        if section.github_issue_number.is_some() {
-                            // Update existing issue
-                            let issue_num = section.github_issue_number.unwrap();
-                            let body = generate_issue_body(section);
+                        if let Some(issue_num) = section.github_issue_number {
+                            let remote = match fetch_issue(tracker.as_ref(), &db, issue_num) {
+                                Ok(issue) => issue,
+                                Err(e) => {
+                                    eprintln!(
+                                        "  {} Fetching issue #{}: {}",
+                                        "✗".red(),
+                                        issue_num,
+                                        e
+                                    );
+                                    continue;
+                                }
+                            };
 
-                            if dry_run {
+                            // Three-way merge of checkboxes against the last sync
+                            let sync = reconcile_section(section, &remote.body, |change_id| {
+                                db.get_roadmap_item_by_change_id(change_id)
+                                    .ok()
+                                    .flatten()
+                                    .and_then(|i| i.synced_checkbox_state)
+                                    .map(|state| CheckboxState::parse(&state).is_checked())
+                            });
+
+                            for item in sync.pulled() {
+                                let verb = if item.remote == Some(true) {
+                                    "Tick"
+                                } else {
+                                    "Untick"
+                                };
+                                if dry_run {
+                                    println!(
+                                        "  {} Would {} '{}' (changed in issue #{})",
+                                        "[DRY]".yellow(),
+                                        verb.to_lowercase(),
+                                        item.text,
+                                        issue_num
+                                    );
+                                } else {
+                                    println!(
+                                        "  {} {}ed '{}' from issue #{}",
+                                        "↓".green(),
+                                        verb,
+                                        item.text,
+                                        issue_num
+                                    );
+                                }
+                                pulled += 1;
+                            }
+
+                            for item in sync.conflicts() {
+                                println!(
+                                    "  {} Conflict on '{}': {} locally, {} in issue #{}",
+                                    "!".yellow(),
+                                    item.text,
+                                    CheckboxState::from_bool(item.local).as_str(),
+                                    CheckboxState::from_bool(item.remote == Some(true)).as_str(),
+                                    issue_num
+                                );
+                                conflicts += 1;
+
+                                let key = (item.change_id.clone(), "checkbox".to_string());
+                                if !dry_run && !known_conflicts.contains(&key) {
+                                    if let Err(e) = db.create_roadmap_conflict(
+                                        &item.change_id,
+                                        "checkbox",
+                                        Some(CheckboxState::from_bool(item.local).as_str()),
+                                        Some(
+                                            CheckboxState::from_bool(item.remote == Some(true))
+                                                .as_str(),
+                                        ),
+                                    ) {
+                                        eprintln!(
+                                            "    {} Recording conflict: {}",
+                                            "Warning:".yellow(),
+                                            e
+                                        );
+                                    }
+                                }
+                            }
+
+                            let body = generate_issue_body(&sync.remote);
+                            let pushed = if body.trim() == remote.body.trim() {
+                                println!(
+                                    "  {} Unchanged issue #{}: {}",
+                                    "-".dimmed(),
+                                    issue_num,
+                                    section.title
+                                );
+                                skipped += 1;
+                                true
+                            } else if dry_run {
                                 println!(
                                     "  {} Would update issue #{}: {}",
                                     "[DRY]".yellow(),
@@ -2328,8 +3978,9 @@ fn main() {
                                     section.title
                                 );
                                 updated += 1;
+                                false
                             } else {
-                                match gh_client.update_issue_body(issue_num, &body) {
+                                match tracker.update_issue_body(issue_num, &body) {
                                     Ok(()) => {
                                         println!(
                                             "  {} Updated issue #{}: {}",
@@ -2338,6 +3989,7 @@ fn main() {
                                             section.title
                                         );
                                         updated += 1;
+                                        true
                                     }
                                     Err(e) => {
                                         eprintln!(
@@ -2346,9 +3998,40 @@ fn main() {
                                             issue_num,
                                             e
                                         );
+                                        false
+                                    }
+                                }
+                            };
+
+                            if !dry_run {
+                                // Both sides now agree on every non-conflicted item
+                                if pushed {
+                                    for item in &sync.items {
+                                        if let Some(checked) = item.resolved() {
+                                            record_sync_base(
+                                                &db,
+                                                &section.title,
+                                                &item.change_id,
+                                                &item.text,
+                                                checked,
+                                            );
+                                        }
                                     }
                                 }
+                                if sync.pulled().next().is_some() {
+                                    changed_sections.insert(section.change_id.clone(), sync.local);
+                                }
                             }
+
+                            assign_milestone(
+                                tracker.as_ref(),
+                                &mut milestones,
+                                &milestone_due,
+                                section,
+                                issue_num,
+                                remote.milestone.as_deref(),
+                                dry_run,
+                            );
                         } else if create_issues {
                             // Create new issue
                             let body = generate_issue_body(section);
@@ -2361,7 +4044,7 @@ fn main() {
                                 );
                                 created += 1;
                             } else {
-                                match gh_client.create_issue(&section.title, &body, &["roadmap"]) {
+                                match tracker.create_issue(&section.title, &body, &["roadmap"]) {
                                     Ok(issue) => {
                                         println!(
                                             "  {} Created issue #{}: {}",
@@ -2384,8 +4067,19 @@ fn main() {
                                             );
                                         }
 
+                                        // The new issue lists every item as it is locally
+                                        for item in &section.items {
+                                            record_sync_base(
+                                                &db,
+                                                &section.title,
+                                                &item.change_id,
+                                                &item.text,
+                                                item.checked,
+                                            );
+                                        }
+
                                         // Cache issue for TUI/Web display
-                                        if let Some(repo_name) = gh_client.repo_name() {
+                                        if let Some(repo_name) = tracker.repo_name() {
                                             if let Err(e) = db.cache_github_issue(
                                                 issue.number,
                                                 repo_name,
@@ -2395,6 +4089,8 @@ fn main() {
                                                 &issue.html_url,
                                                 &issue.created_at,
                                                 &issue.updated_at,
+                                                None,
+                                                issue.milestone.as_deref(),
                                             ) {
                                                 eprintln!(
                                                     "    {} Caching issue: {}",
@@ -2403,6 +4099,22 @@ fn main() {
                                                 );
                                             }
                                         }
+
+                                        assign_milestone(
+                                            tracker.as_ref(),
+                                            &mut milestones,
+                                            &milestone_due,
+                                            section,
+                                            issue.number,
+                                            None,
+                                            dry_run,
+                                        );
+
+                                        // Record the issue in ROADMAP.md metadata
+                                        let mut linked = (*section).clone();
+                                        linked.github_issue_number = Some(issue.number);
+                                        linked.github_issue_state = Some(issue.state.clone());
+                                        changed_sections.insert(section.change_id.clone(), linked);
                                     }
                                     Err(e) => {
                                         eprintln!(
@@ -2420,11 +4132,15 @@ fn main() {
                         }
                     }
 
-                    // Write updated roadmap with issue metadata
-                    if !dry_run && created > 0 {
+                    // Write updated roadmap with issue metadata and pulled checkboxes
+                    if !dry_run && !changed_sections.is_empty() {
+                        let sections: Vec<RoadmapSection> = parsed
+                            .sections
+                            .iter()
+                            .map(|s| changed_sections.get(&s.change_id).unwrap_or(s).clone())
+                            .collect();
                         let content = std::fs::read_to_string(&roadmap_path).unwrap_or_default();
-                        match write_roadmap_with_metadata(&roadmap_path, &parsed.sections, &content)
-                        {
+                        match write_roadmap_with_metadata(&roadmap_path, &sections, &content) {
                             Ok(updated_content) => {
                                 if let Err(e) = std::fs::write(&roadmap_path, &updated_content) {
                                     eprintln!("{} Writing roadmap: {}", "Warning:".yellow(), e);
@@ -2434,6 +4150,23 @@ fn main() {
                         }
                     }
 
+                    if !dry_run {
+                        let unresolved = db.get_unresolved_conflicts().map_or(0, |c| c.len());
+                        let state = db.get_or_create_sync_state(&roadmap_key(&roadmap_path));
+                        if let Err(e) = state.and_then(|state| {
+                            db.update_sync_state(
+                                state.id,
+                                Some(&parsed.content_hash),
+                                tracker.repo_name(),
+                                true,
+                                true,
+                                unresolved as i32,
+                            )
+                        }) {
+                            eprintln!("{} Saving sync state: {}", "Warning:".yellow(), e);
+                        }
+                    }
+
                     println!(
                         "\n{} {} created, {} updated, {} skipped",
                         if dry_run {
@@ -2445,6 +4178,15 @@ fn main() {
                         updated,
                         skipped
                     );
+                    if pulled > 0 || conflicts > 0 {
+                        println!(
+                            "  {} checkboxes changed from issues, {} conflicts",
+                            pulled, conflicts
+                        );
+                    }
+                    if conflicts > 0 {
+                        println!("  Run 'deciduous roadmap conflicts' to review them");
+                    }
                 }
 
                 RoadmapAction::List {
@@ -2499,7 +4241,11 @@ fn main() {
                         return;
                     }
 
-                    println!("{} ({} sections)\n", "ROADMAP.md".cyan(), filtered.len());
+                    println!(
+                        "{} ({} sections)\n",
+                        roadmap_path.display().to_string().cyan(),
+                        filtered.len()
+                    );
 
                     for s in &filtered {
                         // Show section header based on level
@@ -2533,7 +4279,8 @@ fn main() {
                             } else {
                                 "○".dimmed()
                             };
-                            println!("    {} {}", check, item.text);
+                            let indent = "  ".repeat(item.depth);
+                            println!("    {}{} {}", indent, check, item.text);
                         }
                     }
                 }
@@ -2650,7 +4397,7 @@ fn main() {
                     }
                 }
 
-                RoadmapAction::Conflicts { resolve } => {
+                RoadmapAction::Conflicts { resolve, path } => {
                     let conflicts = match db.get_unresolved_conflicts() {
                         Ok(c) => c,
                         Err(e) => {
@@ -2671,10 +4418,13 @@ fn main() {
                     );
 
                     for conflict in &conflicts {
-                        println!(
-                            "  Item: {} ({})",
-                            conflict.item_change_id, conflict.conflict_type
-                        );
+                        let title = db
+                            .get_roadmap_item_by_change_id(&conflict.item_change_id)
+                            .ok()
+                            .flatten()
+                            .map(|i| i.title)
+                            .unwrap_or_else(|| conflict.item_change_id.clone());
+                        println!("  Item: {} ({})", title, conflict.conflict_type);
                         println!(
                             "    Local:  {}",
                             conflict.local_value.as_deref().unwrap_or("(none)")
@@ -2690,12 +4440,96 @@ fn main() {
                     }
 
                     if resolve {
+                        let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
+                        let parsed = parse_roadmap(&roadmap_path).ok();
+
+                        let entries: Vec<ConflictEntry> = conflicts
+                            .iter()
+                            .map(|conflict| {
+                                let located = parsed.as_ref().and_then(|p| {
+                                    p.sections.iter().find_map(|s| {
+                                        s.items
+                                            .iter()
+                                            .find(|i| i.change_id == conflict.item_change_id)
+                                            .map(|i| (s, i))
+                                    })
+                                });
+                                let item = db
+                                    .get_roadmap_item_by_change_id(&conflict.item_change_id)
+                                    .ok()
+                                    .flatten();
+                                ConflictEntry {
+                                    conflict: conflict.clone(),
+                                    title: located
+                                        .map(|(_, i)| i.text.clone())
+                                        .or_else(|| item.as_ref().map(|i| i.title.clone()))
+                                        .unwrap_or_else(|| conflict.item_change_id.clone()),
+                                    section: located
+                                        .map(|(s, _)| s.title.clone())
+                                        .or_else(|| item.and_then(|i| i.section)),
+                                    issue_number: located.and_then(|(s, _)| s.github_issue_number),
+                                }
+                            })
+                            .collect();
+
+                        let decisions = match deciduous::tui::resolve_conflicts(entries) {
+                            Ok(d) => d,
+                            Err(e) => {
+                                eprintln!("{} Running resolver: {}", "Error:".red(), e);
+                                std::process::exit(1);
+                            }
+                        };
+                        if decisions.is_empty() {
+                            println!("{} No conflicts resolved", "Info:".cyan());
+                            return;
+                        }
+
+                        let tracker = open_tracker(&Config::load().tracker, None).ok();
+                        let actor = deciduous::get_current_actor();
+                        let mut resolved = 0;
+                        for (conflict, choice) in &decisions {
+                            match apply_conflict_choice(
+                                &db,
+                                tracker.as_deref(),
+                                &roadmap_path,
+                                conflict,
+                                choice,
+                                &actor,
+                            ) {
+                                Ok(title) => {
+                                    println!(
+                                        "  {} {}: {} ({})",
+                                        "✓".green(),
+                                        title,
+                                        choice.value(conflict),
+                                        choice.resolution()
+                                    );
+                                    resolved += 1;
+                                }
+                                Err(e) => {
+                                    eprintln!("  {} {}: {}", "✗".red(), conflict.item_change_id, e)
+                                }
+                            }
+                        }
+
+                        let remaining = db.get_unresolved_conflicts().map_or(0, |c| c.len());
+                        if let Ok(Some(state)) =
+                            db.get_roadmap_sync_state(&roadmap_key(&roadmap_path))
+                        {
+                            let _ = db.update_sync_state(
+                                state.id,
+                                state.roadmap_content_hash.as_deref(),
+                                state.github_repo.as_deref(),
+                                state.last_github_sync.is_some(),
+                                state.last_markdown_parse.is_some(),
+                                remaining as i32,
+                            );
+                        }
                         println!(
-                            "{} Interactive conflict resolution not yet implemented",
-                            "TODO:".yellow()
-                        );
-                        println!(
-                            "For now, manually edit ROADMAP.md and run 'deciduous roadmap sync'"
+                            "\n{} {} resolved, {} remaining",
+                            "Summary:".green(),
+                            resolved,
+                            remaining
                         );
                     }
                 }
@@ -2704,7 +4538,7 @@ fn main() {
                     let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
 
                     // Get sync state from database
-                    match db.get_roadmap_sync_state(&roadmap_path.to_string_lossy()) {
+                    match db.get_roadmap_sync_state(&roadmap_key(&roadmap_path)) {
                         Ok(Some(state)) => {
                             println!("{}", "Roadmap Sync Status".cyan());
                             println!("  Path: {}", roadmap_path.display());
@@ -2733,9 +4567,14 @@ fn main() {
                         }
                     }
 
-                    // Show item counts from database
+                    // Show item counts for this file from database
+                    let file = roadmap_key(&roadmap_path);
                     match db.get_all_roadmap_items() {
                         Ok(items) => {
+                            let items: Vec<_> = items
+                                .into_iter()
+                                .filter(|i| i.roadmap_path.as_deref().unwrap_or(&file) == file)
+                                .collect();
                             let with_issues = items
                                 .iter()
                                 .filter(|i| i.github_issue_number.is_some())
@@ -2757,14 +4596,344 @@ fn main() {
                             println!("\n{} No items in database yet", "Items:".dimmed());
                         }
                     }
-                }
 
-                RoadmapAction::Check {
-                    path: _,
-                    incomplete,
-                    complete,
-                } => {
-                    // Get all roadmap items from database
+                    // Progress per milestone, straight from ROADMAP.md
+                    let milestones = parse_roadmap(&roadmap_path)
+                        .map(|parsed| milestone_progress(&parsed.sections))
+                        .unwrap_or_default();
+                    if !milestones.is_empty() {
+                        let today = Local::now().date_naive();
+                        println!("\n{}", "Milestones:".cyan());
+                        for milestone in &milestones {
+                            let due = match milestone.due {
+                                Some(due) if milestone.is_overdue(today) => {
+                                    format!(" (due {}, overdue)", due).red().to_string()
+                                }
+                                Some(due) => format!(" (due {})", due),
+                                None => String::new(),
+                            };
+                            let marker = if milestone.is_complete() {
+                                "✓".green()
+                            } else {
+                                "○".normal()
+                            };
+                            println!(
+                                "  {} {}{}: {}/{} done ({}%)",
+                                marker,
+                                milestone.name,
+                                due,
+                                milestone.checked,
+                                milestone.total,
+                                milestone.percent()
+                            );
+                        }
+                    }
+                }
+
+                RoadmapAction::Promote {
+                    goal_id,
+                    section,
+                    path,
+                } => {
+                    let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
+                    if !roadmap_path.exists() {
+                        eprintln!(
+                            "{} File not found: {}",
+                            "Error:".red(),
+                            roadmap_path.display()
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let goal = match db.get_all_nodes() {
+                        Ok(nodes) => nodes.into_iter().find(|n| n.id == goal_id),
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    let goal = match goal {
+                        Some(n) if n.node_type == "goal" => n,
+                        Some(n) => {
+                            eprintln!(
+                                "{} Node #{} is a {}, not a goal",
+                                "Error:".red(),
+                                goal_id,
+                                n.node_type
+                            );
+                            std::process::exit(1);
+                        }
+                        None => {
+                            eprintln!("{} Node #{} not found", "Error:".red(), goal_id);
+                            std::process::exit(1);
+                        }
+                    };
+                    if !matches!(goal.status.as_str(), "pending" | "active") {
+                        eprintln!(
+                            "{} Goal #{} is {}; only goals in progress can be promoted",
+                            "Error:".red(),
+                            goal_id,
+                            goal.status
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let parsed = match parse_roadmap(&roadmap_path) {
+                        Ok(p) => p,
+                        Err(e) => {
+                            eprintln!("{} Parsing roadmap: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    if let Some(existing) = parsed
+                        .sections
+                        .iter()
+                        .flat_map(|s| &s.items)
+                        .find(|i| i.goal_change_id.as_deref() == Some(goal.change_id.as_str()))
+                    {
+                        eprintln!(
+                            "{} Goal #{} is already on the roadmap as '{}'",
+                            "Error:".red(),
+                            goal_id,
+                            existing.text
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let content = match std::fs::read_to_string(&roadmap_path) {
+                        Ok(c) => c,
+                        Err(e) => {
+                            eprintln!("{} Reading file: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    let item_change_id = uuid::Uuid::new_v4().to_string();
+                    let updated = append_item(
+                        &content,
+                        &section,
+                        &goal.title,
+                        &item_change_id,
+                        Some(&goal.change_id),
+                    );
+                    if let Err(e) = std::fs::write(&roadmap_path, &updated) {
+                        eprintln!("{} Writing file: {}", "Error:".red(), e);
+                        std::process::exit(1);
+                    }
+
+                    // Track the item and link both ways
+                    let linked = db
+                        .create_roadmap_item_full(
+                            &item_change_id,
+                            &goal.title,
+                            None,
+                            Some(&section),
+                            None,
+                            CheckboxState::Unchecked.as_str(),
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                        )
+                        .and_then(|_| {
+                            db.set_roadmap_item_path(&item_change_id, &roadmap_key(&roadmap_path))
+                        })
+                        .and_then(|_| {
+                            db.set_roadmap_item_goal(&item_change_id, Some(&goal.change_id))
+                        })
+                        .and_then(|_| {
+                            db.set_node_roadmap_item(&goal.change_id, Some(&item_change_id))
+                        });
+                    if let Err(e) = linked {
+                        eprintln!("{} Linking goal: {}", "Warning:".yellow(), e);
+                    }
+
+                    println!(
+                        "{} Added goal #{} to '{}' in {}",
+                        "Success:".green(),
+                        goal_id,
+                        section,
+                        roadmap_path.display()
+                    );
+                    println!("  - [ ] {}", goal.title);
+                }
+
+                RoadmapAction::Start { item, path } => {
+                    let roadmap_path = path.unwrap_or_else(|| PathBuf::from("ROADMAP.md"));
+                    let items = match db.get_all_roadmap_items() {
+                        Ok(i) => i,
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+
+                    // Tasks only; section headers aren't work items
+                    let Some(target) =
+                        items
+                            .iter()
+                            .filter(|i| i.checkbox_state != "none")
+                            .find(|i| {
+                                i.change_id == item
+                                    || i.title.to_lowercase().contains(&item.to_lowercase())
+                            })
+                    else {
+                        eprintln!("{} Roadmap item '{}' not found", "Error:".red(), item);
+                        eprintln!("Run 'deciduous roadmap list' to see available items");
+                        std::process::exit(1);
+                    };
+                    if let Some(ref goal) = target.goal_change_id {
+                        eprintln!(
+                            "{} '{}' is already tracked by goal {}",
+                            "Error:".red(),
+                            target.title,
+                            goal
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let goal_id = match db.add_node(
+                        "goal",
+                        &target.title,
+                        target.description.as_deref(),
+                        None,
+                        None,
+                    ) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            eprintln!("{} Creating goal: {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    let status = if target.is_checked() {
+                        "completed"
+                    } else {
+                        "active"
+                    };
+                    let goal_change_id = db
+                        .get_all_nodes()
+                        .ok()
+                        .and_then(|nodes| nodes.into_iter().find(|n| n.id == goal_id))
+                        .map(|n| n.change_id)
+                        .unwrap_or_default();
+                    let linked = db
+                        .update_node_status(goal_id, status)
+                        .and_then(|_| {
+                            db.set_node_roadmap_item(&goal_change_id, Some(&target.change_id))
+                        })
+                        .and_then(|_| {
+                            db.set_roadmap_item_goal(&target.change_id, Some(&goal_change_id))
+                        });
+                    if let Err(e) = linked {
+                        eprintln!("{} Linking goal: {}", "Warning:".yellow(), e);
+                    }
+
+                    // Record the link in ROADMAP.md so it survives a refresh
+                    let mut recorded = false;
+                    if let Ok(mut parsed) = parse_roadmap(&roadmap_path) {
+                        if let Some(entry) = parsed
+                            .sections
+                            .iter_mut()
+                            .flat_map(|s| s.items.iter_mut())
+                            .find(|i| i.change_id == target.change_id)
+                        {
+                            entry.goal_change_id = Some(goal_change_id.clone());
+                            let content =
+                                std::fs::read_to_string(&roadmap_path).unwrap_or_default();
+                            recorded = write_roadmap_with_metadata(
+                                &roadmap_path,
+                                &parsed.sections,
+                                &content,
+                            )
+                            .is_ok();
+                        }
+                    }
+                    if !recorded {
+                        eprintln!(
+                            "{} '{}' isn't in {}; the link is only in the database",
+                            "Warning:".yellow(),
+                            target.title,
+                            roadmap_path.display()
+                        );
+                    }
+
+                    println!(
+                        "{} Created goal #{}: {}",
+                        "Success:".green(),
+                        goal_id,
+                        target.title
+                    );
+                    println!("  Tracking roadmap item {}", target.change_id);
+                }
+
+                RoadmapAction::Report {
+                    weeks,
+                    format,
+                    output,
+                } => {
+                    let Some(format) = ReportFormat::parse(&format) else {
+                        eprintln!(
+                            "{} Unknown format '{}' (use text, csv or html)",
+                            "Error:".red(),
+                            format
+                        );
+                        std::process::exit(1);
+                    };
+
+                    let items = match db.get_all_roadmap_items() {
+                        Ok(items) => items,
+                        Err(e) => {
+                            eprintln!("{} {}", "Error:".red(), e);
+                            std::process::exit(1);
+                        }
+                    };
+                    if items.is_empty() {
+                        eprintln!("{} No roadmap items in database", "Error:".red());
+                        eprintln!("Run 'deciduous roadmap init' first");
+                        std::process::exit(1);
+                    }
+                    let history = db.get_roadmap_item_history().unwrap_or_default();
+                    let outcome_created: std::collections::HashMap<String, String> = db
+                        .get_all_nodes()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|n| n.node_type == "outcome")
+                        .map(|n| (n.change_id, n.created_at))
+                        .collect();
+
+                    let report = RoadmapReport::build(
+                        &items,
+                        &history,
+                        &outcome_created,
+                        Local::now().date_naive(),
+                        weeks,
+                    );
+                    let rendered = match format {
+                        ReportFormat::Text => report.to_text(),
+                        ReportFormat::Csv => report.to_csv(),
+                        ReportFormat::Html => report.to_html("Roadmap burndown"),
+                    };
+
+                    match output {
+                        Some(path) => match std::fs::write(&path, &rendered) {
+                            Ok(()) => println!("{} {}", "Wrote".green(), path.display()),
+                            Err(e) => {
+                                eprintln!("{} Writing {}: {}", "Error:".red(), path.display(), e);
+                                std::process::exit(1);
+                            }
+                        },
+                        None => print!("{}", rendered),
+                    }
+                }
+
+                RoadmapAction::Check {
+                    path: _,
+                    incomplete,
+                    complete,
+                } => {
+                    // Get all roadmap items from database
                     let items = match db.get_all_roadmap_items() {
                         Ok(i) => i,
                         Err(e) => {
@@ -2884,93 +5053,1198 @@ fn main() {
             }
         }
     }
-}
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let char_len = max_len.saturating_sub(3);
+        let truncated: String = s.chars().take(char_len).collect();
+        format!("{}...", truncated)
+    }
+}
+
+// =============================================================================
+// Audit command helpers
+// =============================================================================
+
+/// Commit info for audit matching
+struct AuditCommit {
+    hash: String,
+    message: String,
+}
+
+/// A potential node-to-commit match
+struct CommitMatch {
+    node_id: i32,
+    node_title: String,
+    commit_hash: String,
+    commit_message: String,
+    score: f64,
+}
+
+/// Get git commits for audit (since Nov 2024)
+fn get_git_commits_for_audit() -> Vec<AuditCommit> {
+    let output = ProcessCommand::new("git")
+        .args(["log", "--format=%H|%s", "--since=2024-11-01"])
+        .output()
+        .ok();
+
+    match output {
+        Some(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.splitn(2, '|').collect();
+                if parts.len() == 2 {
+                    Some(AuditCommit {
+                        hash: parts[0].to_string(),
+                        message: parts[1].to_string(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// How far either side of a node's creation time to look for related commits
+const ASSOCIATE_FILES_WINDOW_HOURS: i64 = 24;
+
+/// Files proposed for a node by `audit --associate-files`
+struct FileMatch {
+    node_id: i32,
+    node_title: String,
+    files: Vec<String>,
+    commit_hash: String,
+    /// Commit message when the commit was found by title matching
+    commit_message: Option<String>,
+    score: f64,
+}
+
+/// Get git commits made within a time range (RFC 3339 bounds)
+fn get_git_commits_between(since: &str, until: &str) -> Vec<AuditCommit> {
+    let output = ProcessCommand::new("git")
+        .args([
+            "log",
+            "--format=%H|%s",
+            &format!("--since={}", since),
+            &format!("--until={}", until),
+        ])
+        .output()
+        .ok();
+
+    match output {
+        Some(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (hash, message) = line.split_once('|')?;
+                Some(AuditCommit {
+                    hash: hash.to_string(),
+                    message: message.to_string(),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Get the paths changed by a commit
+fn get_commit_changed_files(hash: &str) -> Vec<String> {
+    let output = ProcessCommand::new("git")
+        .args(["show", "--name-only", "--format=", hash])
+        .output()
+        .ok();
+
+    match output {
+        Some(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Find files for nodes that don't have any
+///
+/// Nodes with a commit get that commit's changed paths. Nodes without one are
+/// matched by title against commits within `ASSOCIATE_FILES_WINDOW_HOURS` of
+/// their creation, keeping the best match at or above `threshold`.
+fn find_file_matches(nodes: &[DecisionNode], threshold: f64) -> Vec<FileMatch> {
+    let mut matches = Vec::new();
+
+    for node in nodes {
+        let meta: serde_json::Value = node
+            .metadata_json
+            .as_deref()
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+
+        let has_files = meta
+            .get("files")
+            .and_then(|f| f.as_array())
+            .map(|f| !f.is_empty())
+            .unwrap_or(false);
+        if has_files {
+            continue;
+        }
+
+        let commit = meta
+            .get("commit")
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty());
+
+        if let Some(hash) = commit {
+            let files = get_commit_changed_files(hash);
+            if !files.is_empty() {
+                matches.push(FileMatch {
+                    node_id: node.id,
+                    node_title: node.title.clone(),
+                    files,
+                    commit_hash: hash.to_string(),
+                    commit_message: None,
+                    score: 1.0,
+                });
+            }
+            continue;
+        }
+
+        let Ok(created) = chrono::DateTime::parse_from_rfc3339(&node.created_at) else {
+            continue;
+        };
+        let window = chrono::Duration::hours(ASSOCIATE_FILES_WINDOW_HOURS);
+        let commits = get_git_commits_between(
+            &(created - window).to_rfc3339(),
+            &(created + window).to_rfc3339(),
+        );
+
+        let best = commits
+            .iter()
+            .map(|c| (c, keyword_match_score(&node.title, &c.message)))
+            .filter(|(_, score)| *score >= threshold)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((commit, score)) = best {
+            let files = get_commit_changed_files(&commit.hash);
+            if !files.is_empty() {
+                matches.push(FileMatch {
+                    node_id: node.id,
+                    node_title: node.title.clone(),
+                    files,
+                    commit_hash: commit.hash.clone(),
+                    commit_message: Some(commit.message.clone()),
+                    score,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+/// `audit --associate-files`: fill `metadata_json.files` from git history
+fn audit_associate_files(db: &Database, min_score: u8, dry_run: bool, yes: bool) {
+    let nodes = match db.get_all_nodes() {
+        Ok(n) => n,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let matches = find_file_matches(&nodes, min_score as f64 / 100.0);
+    if matches.is_empty() {
+        println!(
+            "{} No file associations found (>= {}%)",
+            "Result:".cyan(),
+            min_score
+        );
+        return;
+    }
+
+    println!(
+        "{} Found files for {} node(s):",
+        "Files:".green(),
+        matches.len()
+    );
+    println!("{}", "=".repeat(80));
+
+    for m in &matches {
+        match &m.commit_message {
+            None => println!(
+                "\nNode #{} (linked commit {}): {}",
+                m.node_id,
+                &m.commit_hash[..7.min(m.commit_hash.len())],
+                truncate(&m.node_title, 50)
+            ),
+            Some(message) => {
+                println!(
+                    "\nNode #{} ({}%): {}",
+                    m.node_id,
+                    (m.score * 100.0) as u8,
+                    truncate(&m.node_title, 55)
+                );
+                println!(
+                    "  ~ {}: {}",
+                    &m.commit_hash[..7.min(m.commit_hash.len())],
+                    truncate(message, 55)
+                );
+            }
+        }
+        for f in m.files.iter().take(10) {
+            println!("    {}", f);
+        }
+        if m.files.len() > 10 {
+            println!("    ... and {} more", m.files.len() - 10);
+        }
+    }
+
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "Info:".cyan());
+        return;
+    }
+
+    // Confirm unless --yes
+    if !yes {
+        println!("\n{}", "=".repeat(80));
+        print!("Apply files to {} node(s)? [y/N]: ", matches.len());
+        use std::io::Write;
+        std::io::stdout().flush().ok();
+
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input).is_err() || input.trim().to_lowercase() != "y" {
+            println!("{}", "Aborted".yellow());
+            return;
+        }
+    }
+
+    let mut applied = 0;
+    let mut failed = 0;
+
+    for m in &matches {
+        match db.update_node_files(m.node_id, &m.files) {
+            Ok(()) => {
+                applied += 1;
+                println!(
+                    "{} Node #{} <- {} file(s)",
+                    "Linked:".green(),
+                    m.node_id,
+                    m.files.len()
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("{} Node #{}: {}", "Failed:".red(), m.node_id, e);
+            }
+        }
+    }
+
+    println!(
+        "\n{} {} updated, {} failed",
+        "Done:".green(),
+        applied,
+        failed
+    );
+}
+
+/// Normalize text into a set of lowercase keywords, dropping punctuation and stopwords
+fn keyword_tokens(s: &str) -> std::collections::HashSet<String> {
+    const STOPWORDS: &[&str] = &[
+        "the", "a", "an", "and", "or", "to", "for", "in", "on", "with", "is", "was", "be", "as",
+        "of", "it", "that", "this", "from", "by",
+    ];
+
+    s.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .filter(|w| !STOPWORDS.contains(w))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Calculate keyword match score between node title and commit message
+fn keyword_match_score(node_title: &str, commit_message: &str) -> f64 {
+    let node_words = keyword_tokens(node_title);
+    let commit_words = keyword_tokens(commit_message);
+
+    if node_words.is_empty() {
+        return 0.0;
+    }
+
+    let common: std::collections::HashSet<_> = node_words.intersection(&commit_words).collect();
+    common.len() as f64 / node_words.len() as f64
+}
+
+/// Token Jaccard similarity between two strings (0.0-1.0)
+///
+/// Keywords count as the same when one is a prefix of the other and the
+/// shorter is at least 4 characters, so "auth" matches "authentication".
+fn keyword_similarity(a: &str, b: &str) -> f64 {
+    let a_words = keyword_tokens(a);
+    let b_words = keyword_tokens(b);
+
+    if a_words.is_empty() || b_words.is_empty() {
+        return 0.0;
+    }
+
+    let same = |x: &String, y: &String| {
+        x == y
+            || (x.len().min(y.len()) >= 4
+                && (x.starts_with(y.as_str()) || y.starts_with(x.as_str())))
+    };
+    let matched = a_words
+        .iter()
+        .filter(|x| b_words.iter().any(|y| same(x, y)))
+        .count()
+        .min(b_words.len());
+
+    matched as f64 / (a_words.len() + b_words.len() - matched) as f64
+}
+
+/// Similarity between two nodes based on title and, when both have one, description
+fn node_similarity(a: &DecisionNode, b: &DecisionNode) -> f64 {
+    let title = keyword_similarity(&a.title, &b.title);
+    match (a.description.as_deref(), b.description.as_deref()) {
+        (Some(desc_a), Some(desc_b)) if !desc_a.trim().is_empty() && !desc_b.trim().is_empty() => {
+            0.7 * title + 0.3 * keyword_similarity(desc_a, desc_b)
+        }
+        _ => title,
+    }
+}
+
+/// A cluster of nodes that look like duplicates of each other
+struct DuplicateGroup {
+    /// Oldest node in the group, suggested as the one to keep
+    keep: DecisionNode,
+    /// Remaining members with their similarity to `keep`
+    others: Vec<(DecisionNode, f64)>,
+}
+
+/// Group nodes of the same type whose similarity to the group's oldest node
+/// is at least `threshold`
+///
+/// Every member is compared against `keep` itself, so a node that only
+/// resembles another member (A~B, B~C but not A~C) is not pulled in.
+/// Expects `nodes` in creation order (as returned by `get_all_nodes`).
+fn find_duplicate_groups(nodes: &[DecisionNode], threshold: f64) -> Vec<DuplicateGroup> {
+    let mut grouped = vec![false; nodes.len()];
+    let mut groups = Vec::new();
+
+    for i in 0..nodes.len() {
+        if grouped[i] {
+            continue;
+        }
+        let keep = &nodes[i];
+        let mut others: Vec<(DecisionNode, f64)> = Vec::new();
+        for j in (i + 1)..nodes.len() {
+            if grouped[j] || nodes[j].node_type != keep.node_type {
+                continue;
+            }
+            let score = node_similarity(keep, &nodes[j]);
+            if score >= threshold {
+                grouped[j] = true;
+                others.push((nodes[j].clone(), score));
+            }
+        }
+        if others.is_empty() {
+            continue;
+        }
+        grouped[i] = true;
+        others.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        groups.push(DuplicateGroup {
+            keep: keep.clone(),
+            others,
+        });
+    }
+
+    let best = |g: &DuplicateGroup| g.others.first().map(|o| o.1).unwrap_or(0.0);
+    groups.sort_by(|a, b| {
+        best(b)
+            .partial_cmp(&best(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    groups
+}
+
+/// `audit --find-duplicates`: report near-duplicate groups and merge or link them
+///
+/// `--yes` links each group; deleting nodes without a prompt takes `--merge` too.
+fn audit_find_duplicates(db: &Database, min_score: u8, dry_run: bool, yes: bool, merge: bool) {
+    let nodes = match db.get_all_nodes() {
+        Ok(n) => n,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let groups = find_duplicate_groups(&nodes, min_score as f64 / 100.0);
+    if groups.is_empty() {
+        println!(
+            "{} No duplicate candidates found above {}% similarity",
+            "Result:".cyan(),
+            min_score
+        );
+        return;
+    }
+
+    println!(
+        "{} Found {} group(s) of similar nodes (>= {}%):",
+        "Duplicates:".green(),
+        groups.len(),
+        min_score
+    );
+
+    let mut merged = 0;
+    let mut linked = 0;
+
+    for group in &groups {
+        println!("{}", "=".repeat(80));
+        println!(
+            "Keep #{} [{}]: {}",
+            group.keep.id,
+            group.keep.node_type,
+            truncate(&group.keep.title, 55)
+        );
+        for (node, score) in &group.others {
+            println!(
+                "  #{} ({}%): {}",
+                node.id,
+                (score * 100.0) as u8,
+                truncate(&node.title, 55)
+            );
+        }
+
+        if dry_run {
+            continue;
+        }
+
+        let choice = if yes {
+            if merge { "m" } else { "l" }.to_string()
+        } else {
+            print!(
+                "[m]erge into #{}, [l]ink as superseded by it, [s]kip? [s]: ",
+                group.keep.id
+            );
+            use std::io::Write;
+            std::io::stdout().flush().ok();
+
+            let mut input = String::new();
+            if std::io::stdin().read_line(&mut input).is_err() {
+                break;
+            }
+            input.trim().to_lowercase()
+        };
+
+        let other_ids: Vec<i32> = group.others.iter().map(|(n, _)| n.id).collect();
+        match choice.as_str() {
+            "m" | "merge" => match db.merge_nodes(group.keep.id, &other_ids) {
+                Ok(result) => {
+                    merged += result.nodes_merged;
+                    println!(
+                        "{} {} node(s) into #{}",
+                        "Merged:".green(),
+                        result.nodes_merged,
+                        group.keep.id
+                    );
+                }
+                Err(e) => eprintln!("{} {}", "Failed:".red(), e),
+            },
+            "l" | "link" => {
+                for id in &other_ids {
+                    match db.create_edge(
+                        group.keep.id,
+                        *id,
+                        "supersedes",
+                        Some("Flagged as duplicate by audit"),
+                    ) {
+                        Ok(_) => {
+                            linked += 1;
+                            println!(
+                                "{} #{} supersedes #{}",
+                                "Linked:".green(),
+                                group.keep.id,
+                                id
+                            );
+                        }
+                        Err(e) => eprintln!("{} #{}: {}", "Failed:".red(), id, e),
+                    }
+                }
+            }
+            _ => println!("{}", "Skipped".yellow()),
+        }
+    }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "Info:".cyan());
     } else {
-        let char_len = max_len.saturating_sub(3);
-        let truncated: String = s.chars().take(char_len).collect();
-        format!("{}...", truncated)
+        println!("\n{} {} merged, {} linked", "Done:".green(), merged, linked);
     }
 }
 
 // =============================================================================
-// Audit command helpers
+// File coverage helpers
 // =============================================================================
 
-/// Commit info for audit matching
-struct AuditCommit {
-    hash: String,
-    message: String,
-}
-
-/// A potential node-to-commit match
-struct CommitMatch {
-    node_id: i32,
-    node_title: String,
-    commit_hash: String,
-    commit_message: String,
-    score: f64,
+/// Extensions counted as source code by `deciduous coverage`
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "mjs", "py", "go", "java", "kt", "rb", "ex", "exs", "c", "h",
+    "cc", "cpp", "hpp", "cs", "swift", "scala", "php", "sh", "sql", "vue", "svelte",
+];
+
+/// Whether a path looks like a source file
+fn is_source_file(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e))
 }
 
-/// Get git commits for audit (since Nov 2024)
-fn get_git_commits_for_audit() -> Vec<AuditCommit> {
-    let output = ProcessCommand::new("git")
-        .args(["log", "--format=%H|%s", "--since=2024-11-01"])
-        .output()
-        .ok();
+/// Get all files tracked by git in the current repository
+fn get_git_tracked_files() -> Vec<String> {
+    let output = ProcessCommand::new("git").args(["ls-files"]).output().ok();
 
     match output {
         Some(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
             .lines()
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.splitn(2, '|').collect();
-                if parts.len() == 2 {
-                    Some(AuditCommit {
-                        hash: parts[0].to_string(),
-                        message: parts[1].to_string(),
-                    })
-                } else {
-                    None
-                }
-            })
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
             .collect(),
         _ => Vec::new(),
     }
 }
 
-/// Calculate keyword match score between node title and commit message
-fn keyword_match_score(node_title: &str, commit_message: &str) -> f64 {
-    let stopwords: std::collections::HashSet<&str> = [
-        "the", "a", "an", "and", "or", "to", "for", "in", "on", "with", "is", "was", "be", "as",
-        "of", "it", "that", "this", "from", "by",
-    ]
-    .iter()
-    .cloned()
-    .collect();
-
-    let normalize = |s: &str| -> std::collections::HashSet<String> {
-        s.to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-            .collect::<String>()
-            .split_whitespace()
-            .filter(|w| !stopwords.contains(w))
-            .map(|s| s.to_string())
-            .collect()
+/// Coverage for one directory (matches `deciduous coverage --json`)
+#[derive(serde::Serialize)]
+struct DirectoryCoverage {
+    directory: String,
+    total: usize,
+    covered: usize,
+    /// Files in this directory with no linked decisions
+    uncovered: Vec<String>,
+}
+
+/// Output of `deciduous coverage`
+#[derive(serde::Serialize)]
+struct CoverageReport {
+    total_files: usize,
+    covered_files: usize,
+    coverage_percent: f64,
+    directories: Vec<DirectoryCoverage>,
+}
+
+/// Group files by directory and count which ones appear in `linked`
+fn file_coverage(files: &[String], linked: &std::collections::HashSet<String>) -> CoverageReport {
+    let mut by_dir: std::collections::BTreeMap<String, DirectoryCoverage> = Default::default();
+
+    for file in files {
+        let directory = match file.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
+        };
+        let entry = by_dir
+            .entry(directory.clone())
+            .or_insert_with(|| DirectoryCoverage {
+                directory,
+                total: 0,
+                covered: 0,
+                uncovered: Vec::new(),
+            });
+        entry.total += 1;
+        if linked.contains(file) {
+            entry.covered += 1;
+        } else {
+            entry.uncovered.push(file.clone());
+        }
+    }
+
+    let total_files = files.len();
+    let covered_files = by_dir.values().map(|d| d.covered).sum();
+    CoverageReport {
+        total_files,
+        covered_files,
+        coverage_percent: if total_files == 0 {
+            0.0
+        } else {
+            covered_files as f64 * 100.0 / total_files as f64
+        },
+        directories: by_dir.into_values().collect(),
+    }
+}
+
+// =============================================================================
+// Commit log helpers
+// =============================================================================
+
+/// A commit as listed by `deciduous log`
+#[derive(Debug, Clone, PartialEq)]
+struct LogCommit {
+    hash: String,
+    short_hash: String,
+    date: String,
+    subject: String,
+}
+
+/// One line of `git log` output; in graph mode, lines without a commit only
+/// carry graph edges
+#[derive(Debug, Clone, PartialEq)]
+struct LogLine {
+    graph: String,
+    commit: Option<LogCommit>,
+}
+
+impl LogLine {
+    /// Graph prefix, short hash and subject
+    fn left_column(&self) -> String {
+        match &self.commit {
+            Some(c) => format!("{}{} {}", self.graph, c.short_hash, c.subject),
+            None => self.graph.trim_end().to_string(),
+        }
+    }
+
+    /// Graph prefix for extra lines under this commit
+    fn continuation(&self) -> String {
+        self.graph.replace('*', "|").trim_end().to_string()
+    }
+}
+
+/// Parse a line of `git log --format=%x00%H%x00%h%x00%as%x00%s`, optionally
+/// prefixed by `--graph` drawing
+fn parse_log_line(line: &str) -> LogLine {
+    let mut parts = line.split('\x00');
+    let graph = parts.next().unwrap_or_default().to_string();
+    let fields: Vec<&str> = parts.collect();
+    let commit = (fields.len() >= 4).then(|| LogCommit {
+        hash: fields[0].to_string(),
+        short_hash: fields[1].to_string(),
+        date: fields[2].to_string(),
+        subject: fields[3..].join(" "),
+    });
+    LogLine { graph, commit }
+}
+
+/// Run `git log` over a revision range (HEAD by default)
+fn get_git_log(range: Option<&str>, limit: usize, graph: bool) -> Result<Vec<LogLine>, String> {
+    let mut cmd = ProcessCommand::new("git");
+    cmd.arg("log")
+        .arg(format!("--max-count={}", limit))
+        .arg("--format=%x00%H%x00%h%x00%as%x00%s");
+    if graph {
+        cmd.arg("--graph");
+    }
+    if let Some(range) = range {
+        cmd.arg(range);
+    }
+    let output = cmd
+        .arg("--")
+        .output()
+        .map_err(|e| format!("Failed to run git log: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(parse_log_line)
+        .collect())
+}
+
+/// True if a node's metadata records the given branch
+fn node_on_branch(node: &deciduous::DecisionNode, branch: &str) -> bool {
+    node.metadata_json
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| {
+            v.get("branch")
+                .and_then(|b| b.as_str())
+                .map(|b| b == branch)
+        })
+        .unwrap_or(false)
+}
+
+/// "goal #1: X › decision #2: Y › action #4: Z" for a node
+///
+/// The goal is the node's root goal and the decision is the nearest one above
+/// it; levels the node itself fills are not repeated.
+fn decision_chain(
+    node: &deciduous::DecisionNode,
+    nodes: &[deciduous::DecisionNode],
+    edges: &[deciduous::DecisionEdge],
+) -> String {
+    let goal = deciduous::tui::state::find_root_goal(node.id, nodes, edges)
+        .and_then(|id| nodes.iter().find(|n| n.id == id));
+    let decision = deciduous::blame::nearest_ancestor(node.id, "decision", nodes, edges);
+
+    let mut chain: Vec<&deciduous::DecisionNode> = Vec::new();
+    for n in [goal, decision, Some(node)].into_iter().flatten() {
+        if !chain.iter().any(|c| c.id == n.id) {
+            chain.push(n);
+        }
+    }
+    chain
+        .iter()
+        .map(|n| format!("{} #{}: {}", n.node_type, n.id, n.title))
+        .collect::<Vec<_>>()
+        .join(" › ")
+}
+
+// =============================================================================
+// Patch helpers
+// =============================================================================
+
+/// Sign a patch if asked to or if [patches] signing_key is configured
+fn sign_patch(
+    patch: &mut deciduous::GraphPatch,
+    sign: bool,
+    key: Option<PathBuf>,
+) -> Result<(), String> {
+    let patches_config = Config::load().patches;
+    let Some(key) = key.or(patches_config.signing_key) else {
+        return if sign {
+            Err(
+                "No signing key. Pass --key or set [patches] signing_key in .deciduous/config.toml"
+                    .to_string(),
+            )
+        } else {
+            Ok(())
+        };
     };
+    let signer = patches_config
+        .signer
+        .or_else(deciduous::signing::default_signer)
+        .ok_or("No signer identity. Set [patches] signer or git user.email")?;
+    patch
+        .sign(&key, &signer)
+        .map_err(|e| format!("Signing patch: {}", e))
+}
+
+/// Contents of `path` at a git revision
+fn git_show_file(rev: &str, path: &std::path::Path) -> Result<String, String> {
+    // "./" makes the path relative to the working directory, like other arguments
+    let spec = format!("{}:./{}", rev, path.display());
+    let output = ProcessCommand::new("git")
+        .args(["show", &spec])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
 
-    let node_words = normalize(node_title);
-    let commit_words = normalize(commit_message);
+// =============================================================================
+// Roadmap sync helpers
+// =============================================================================
 
-    if node_words.is_empty() {
-        return 0.0;
+/// Current state of an issue
+///
+/// Refreshes the issue cache on the way; with a cached ETag an unchanged
+/// issue costs a 304 and is answered from the cache.
+fn fetch_issue(
+    tracker: &dyn IssueTracker,
+    db: &Database,
+    number: i32,
+) -> Result<Issue, TrackerError> {
+    let Some(repo) = tracker.repo_name() else {
+        return tracker.get_issue(number);
+    };
+    let cached = db.get_cached_issue(repo, number).ok().flatten();
+    let etag = cached
+        .as_ref()
+        .filter(|c| c.body.is_some())
+        .and_then(|c| c.etag.clone());
+
+    match tracker.get_issue_if_changed(number, etag.as_deref())? {
+        IssueFetch::NotModified => match cached {
+            Some(cached) => Ok(Issue {
+                number,
+                title: cached.title,
+                body: cached.body.unwrap_or_default(),
+                state: cached.state,
+                html_url: cached.html_url,
+                created_at: cached.created_at,
+                updated_at: cached.updated_at,
+                milestone: cached.milestone,
+            }),
+            // The ETag only comes from the cache, but don't trust a stray 304
+            None => tracker.get_issue(number),
+        },
+        IssueFetch::Modified { issue, etag } => {
+            if let Err(e) = db.cache_github_issue(
+                issue.number,
+                repo,
+                &issue.title,
+                Some(&issue.body),
+                &issue.state,
+                &issue.html_url,
+                &issue.created_at,
+                &issue.updated_at,
+                etag.as_deref(),
+                issue.milestone.as_deref(),
+            ) {
+                eprintln!("    {} Caching issue: {}", "Warning:".yellow(), e);
+            }
+            Ok(issue)
+        }
     }
+}
 
-    let common: std::collections::HashSet<_> = node_words.intersection(&commit_words).collect();
-    common.len() as f64 / node_words.len() as f64
+/// Put an issue in its section's milestone, creating the milestone or
+/// moving its due date as needed
+///
+/// `milestones` caches what the tracker returned, so each milestone is
+/// looked up once per sync.
+fn assign_milestone(
+    tracker: &dyn IssueTracker,
+    milestones: &mut std::collections::HashMap<String, Milestone>,
+    due_dates: &std::collections::HashMap<String, Option<String>>,
+    section: &RoadmapSection,
+    number: i32,
+    current: Option<&str>,
+    dry_run: bool,
+) {
+    let Some(name) = section.milestone.as_deref() else {
+        return;
+    };
+    if dry_run {
+        if current != Some(name) {
+            println!(
+                "  {} Would set milestone '{}' on issue #{}",
+                "[DRY]".yellow(),
+                name,
+                number
+            );
+        }
+        return;
+    }
+
+    if !milestones.contains_key(name) {
+        let due = due_dates.get(name).cloned().flatten();
+        match tracker.ensure_milestone(name, due.as_deref()) {
+            Ok(milestone) => {
+                milestones.insert(name.to_string(), milestone);
+            }
+            Err(e) => {
+                eprintln!("    {} Milestone '{}': {}", "Warning:".yellow(), name, e);
+                return;
+            }
+        }
+    }
+    if current == Some(name) {
+        return;
+    }
+    match tracker.set_issue_milestone(number, &milestones[name]) {
+        Ok(()) => println!(
+            "  {} Milestone '{}' on issue #{}",
+            "✓".green(),
+            name,
+            number
+        ),
+        Err(e) => eprintln!(
+            "    {} Setting milestone on issue #{}: {}",
+            "Warning:".yellow(),
+            number,
+            e
+        ),
+    }
+}
+
+/// Record the checkbox state both sides agreed on, the base for the next sync
+fn record_sync_base(db: &Database, section: &str, change_id: &str, text: &str, checked: bool) {
+    let state = CheckboxState::from_bool(checked);
+    if let Err(e) = db.record_roadmap_item_synced(change_id, text, Some(section), state.as_str()) {
+        eprintln!("    {} Recording sync state: {}", "Warning:".yellow(), e);
+    }
+}
+
+/// Apply a conflict choice to the issue and ROADMAP.md, then record it
+///
+/// The issue is updated first so a failure leaves both sides as they were.
+/// Returns the item's text.
+fn apply_conflict_choice(
+    db: &Database,
+    tracker: Option<&dyn IssueTracker>,
+    roadmap_path: &std::path::Path,
+    conflict: &RoadmapConflict,
+    choice: &ConflictChoice,
+    actor: &str,
+) -> Result<String, String> {
+    if conflict.conflict_type != "checkbox" {
+        return Err(format!(
+            "Don't know how to apply '{}' conflicts",
+            conflict.conflict_type
+        ));
+    }
+    let value = choice.value(conflict);
+    let checked = match CheckboxState::parse(value) {
+        CheckboxState::None => return Err(format!("Invalid checkbox value '{}'", value)),
+        state => state.is_checked(),
+    };
+
+    let parsed = parse_roadmap(roadmap_path).map_err(|e| format!("Parsing roadmap: {}", e))?;
+    let (section, index) = parsed
+        .sections
+        .iter()
+        .find_map(|s| {
+            s.items
+                .iter()
+                .position(|i| i.change_id == conflict.item_change_id)
+                .map(|index| (s, index))
+        })
+        .ok_or_else(|| "Item is no longer in ROADMAP.md".to_string())?;
+    let item = &section.items[index];
+
+    if let Some(number) = section.github_issue_number {
+        let tracker = tracker.ok_or_else(|| "No issue tracker available".to_string())?;
+        let body = fetch_issue(tracker, db, number)
+            .map_err(|e| format!("Fetching issue #{}: {}", number, e))?
+            .body;
+        // An issue that no longer lists the task gets it back on the next sync
+        let key = &section_task_keys(section)[index];
+        if let Some(updated) = set_issue_body_checkbox(&body, key, checked) {
+            if updated != body {
+                tracker
+                    .update_issue_body(number, &updated)
+                    .map_err(|e| format!("Updating issue #{}: {}", number, e))?;
+            }
+        }
+    }
+
+    if item.checked != checked {
+        let mut sections = parsed.sections.clone();
+        for s in &mut sections {
+            for i in &mut s.items {
+                if i.change_id == item.change_id {
+                    i.checked = checked;
+                }
+            }
+        }
+        let content =
+            std::fs::read_to_string(roadmap_path).map_err(|e| format!("Reading roadmap: {}", e))?;
+        write_roadmap_with_metadata(roadmap_path, &sections, &content)
+            .map_err(|e| format!("Writing roadmap: {}", e))?;
+    }
+
+    record_sync_base(db, &section.title, &item.change_id, &item.text, checked);
+    db.resolve_roadmap_conflict(conflict.id, choice.resolution(), value, actor)
+        .map_err(|e| e.to_string())?;
+
+    Ok(item.text.clone())
+}
+
+/// Store parsed sections and their checkbox items as roadmap items
+///
+/// Items keep their ROADMAP.md change_ids so sync can match them later;
+/// items already in the database are left alone. Sub-items point at their
+/// parent item through `parent_id`.
+fn store_roadmap_sections(
+    db: &Database,
+    roadmap_path: &std::path::Path,
+    sections: &[RoadmapSection],
+) {
+    let file = roadmap_key(roadmap_path);
+    // Track current level-2 parent section for grouping
+    let mut current_l2_parent: Option<String> = None;
+
+    for section in sections {
+        // Level 2 headers (## Section) are top-level groupings
+        // Level 3 headers (### Subsection) contain the actual tasks
+        let (section_parent, items_section) = if section.level == 2 {
+            current_l2_parent = Some(section.title.clone());
+            (None, Some(section.title.as_str()))
+        } else {
+            (current_l2_parent.as_deref(), Some(section.title.as_str()))
+        };
+
+        // Create the section header entry (checkbox_state = "none")
+        if !matches!(
+            db.get_roadmap_item_by_change_id(&section.change_id),
+            Ok(Some(_))
+        ) {
+            if let Err(e) = db.create_roadmap_item_full(
+                &section.change_id,
+                &section.title,
+                section.description.as_deref(),
+                section_parent,
+                None, // parent_id - we don't track hierarchy by ID yet
+                "none",
+                section.github_issue_number,
+                section.github_issue_state.as_deref(),
+                None,
+                None,
+                Some(section.line_start as i32),
+                Some(section.line_end as i32),
+                Some(&section.content_hash),
+            ) {
+                eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
+            }
+        }
+
+        // Create items for checkboxes - they belong to THIS section
+        for item in &section.items {
+            if matches!(
+                db.get_roadmap_item_by_change_id(&item.change_id),
+                Ok(Some(_))
+            ) {
+                continue;
+            }
+            // Parents come first in the file, so they are already stored
+            let parent_id = item
+                .parent_change_id
+                .as_deref()
+                .and_then(|p| db.get_roadmap_item_by_change_id(p).ok().flatten())
+                .map(|p| p.id);
+            if let Err(e) = db.create_roadmap_item_full(
+                &item.change_id,
+                &item.text,
+                None,
+                items_section,
+                parent_id,
+                CheckboxState::from_bool(item.checked).as_str(),
+                None,
+                None,
+                None,
+                None,
+                Some(item.line_number as i32),
+                None,
+                None,
+            ) {
+                eprintln!("{} Creating roadmap item: {}", "Warning:".yellow(), e);
+            }
+        }
+
+        for item in section.items.iter().filter(|i| i.goal_change_id.is_some()) {
+            if let Err(e) =
+                db.set_roadmap_item_goal(&item.change_id, item.goal_change_id.as_deref())
+            {
+                eprintln!("{} Linking goal: {}", "Warning:".yellow(), e);
+            }
+        }
+
+        // Milestones and source files follow ROADMAP.md even for rows kept
+        // from earlier imports
+        let ids =
+            std::iter::once(&section.change_id).chain(section.items.iter().map(|i| &i.change_id));
+        for change_id in ids {
+            if let Err(e) = db
+                .set_roadmap_item_milestone(
+                    change_id,
+                    section.milestone.as_deref(),
+                    section.due.as_deref(),
+                )
+                .and_then(|_| db.set_roadmap_item_path(change_id, &file))
+            {
+                eprintln!("{} Updating roadmap item: {}", "Warning:".yellow(), e);
+            }
+        }
+    }
+}
+
+/// Carry completion between roadmap items and the goals tracking them
+///
+/// A ticked item completes its goal; a completed goal ticks its item in
+/// ROADMAP.md. Returns how many items and goals changed.
+fn sync_goal_completion(db: &Database, roadmap_path: &std::path::Path) -> (usize, usize) {
+    let Ok(parsed) = parse_roadmap(roadmap_path) else {
+        return (0, 0);
+    };
+    let goals: std::collections::HashMap<String, DecisionNode> = db
+        .get_all_nodes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|n| n.node_type == "goal")
+        .map(|n| (n.change_id.clone(), n))
+        .collect();
+
+    let mut sections = parsed.sections.clone();
+    let mut items_checked = 0;
+    let mut goals_completed = 0;
+    for item in sections.iter_mut().flat_map(|s| s.items.iter_mut()) {
+        let Some(goal) = item.goal_change_id.as_ref().and_then(|g| goals.get(g)) else {
+            continue;
+        };
+        match reconcile_goal(item.checked, &goal.status) {
+            GoalSync::InSync => {}
+            GoalSync::CompleteGoal => match db.update_node_status(goal.id, "completed") {
+                Ok(()) => {
+                    println!(
+                        "  {} Completed goal #{}: {}",
+                        "✓".green(),
+                        goal.id,
+                        goal.title
+                    );
+                    goals_completed += 1;
+                }
+                Err(e) => eprintln!(
+                    "  {} Completing goal #{}: {}",
+                    "Warning:".yellow(),
+                    goal.id,
+                    e
+                ),
+            },
+            GoalSync::CheckItem => {
+                item.checked = true;
+                if let Ok(Some(row)) = db.get_roadmap_item_by_change_id(&item.change_id) {
+                    if let Err(e) = db.update_roadmap_item_checkbox(row.id, "checked") {
+                        eprintln!("  {} Updating item: {}", "Warning:".yellow(), e);
+                    }
+                }
+                println!(
+                    "  {} Ticked '{}' (goal #{} completed)",
+                    "✓".green(),
+                    item.text,
+                    goal.id
+                );
+                items_checked += 1;
+            }
+        }
+    }
+
+    if items_checked > 0 {
+        let content = std::fs::read_to_string(roadmap_path).unwrap_or_default();
+        if let Err(e) = write_roadmap_with_metadata(roadmap_path, &sections, &content) {
+            eprintln!("{} Updating roadmap: {}", "Warning:".yellow(), e);
+        }
+    }
+    (items_checked, goals_completed)
+}
+
+// =============================================================================
+// Git notes helpers
+// =============================================================================
+
+/// Commit recorded in a node's metadata
+fn node_commit(node: &DecisionNode) -> Option<String> {
+    node.metadata_json
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| {
+            v.get("commit")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_lowercase())
+        })
+        .filter(|c| !c.is_empty())
+}
+
+/// Rewrite the deciduous note on each commit from every node linked to it
+fn mirror_commit_notes(db: &Database, commits: &[&str]) -> Result<(), String> {
+    let nodes = db.get_all_nodes().map_err(|e| e.to_string())?;
+    let index = deciduous::blame::CommitIndex::new(&nodes);
+    for commit in commits {
+        let full = deciduous::notes::resolve_commit(commit)?;
+        let linked = index.nodes_for(&full);
+        if !linked.is_empty() {
+            deciduous::notes::write_note(&full, &deciduous::notes::format_note(&linked))?;
+        }
+    }
+    Ok(())
 }
 
 // =============================================================================
@@ -3083,6 +6357,7 @@ fn export_git_history(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use deciduous::DecisionEdge;
 
     // === keyword_match_score Tests ===
 
@@ -3174,4 +6449,181 @@ mod tests {
             score
         );
     }
+
+    // === Duplicate detection Tests ===
+
+    fn dup_node(id: i32, node_type: &str, title: &str) -> DecisionNode {
+        DecisionNode {
+            id,
+            change_id: format!("cid-{}", id),
+            node_type: node_type.to_string(),
+            title: title.to_string(),
+            description: None,
+            status: "pending".to_string(),
+            created_at: format!("2024-01-01T00:00:{:02}Z", id),
+            updated_at: format!("2024-01-01T00:00:{:02}Z", id),
+            metadata_json: None,
+        }
+    }
+
+    // === Commit log Tests ===
+
+    #[test]
+    fn test_parse_log_line() {
+        let line = parse_log_line("* \x00abc1234def\x00abc1234\x002024-01-02\x00Add schema");
+        assert_eq!(line.graph, "* ");
+        let commit = line.commit.as_ref().unwrap();
+        assert_eq!(commit.hash, "abc1234def");
+        assert_eq!(commit.short_hash, "abc1234");
+        assert_eq!(commit.date, "2024-01-02");
+        assert_eq!(commit.subject, "Add schema");
+        assert_eq!(line.left_column(), "* abc1234 Add schema");
+        assert_eq!(line.continuation(), "|");
+
+        // Graph-only lines carry no commit
+        let line = parse_log_line("|\\  ");
+        assert!(line.commit.is_none());
+        assert_eq!(line.left_column(), "|\\");
+    }
+
+    #[test]
+    fn test_decision_chain() {
+        let nodes = vec![
+            dup_node(1, "goal", "Persist graph"),
+            dup_node(2, "decision", "Which ORM?"),
+            dup_node(3, "option", "Diesel"),
+            dup_node(4, "action", "Add schema"),
+        ];
+        let edges: Vec<DecisionEdge> = [(1, 2), (2, 3), (3, 4)]
+            .iter()
+            .enumerate()
+            .map(|(i, (from, to))| DecisionEdge {
+                id: i as i32 + 1,
+                from_node_id: *from,
+                to_node_id: *to,
+                from_change_id: None,
+                to_change_id: None,
+                edge_type: "leads_to".to_string(),
+                weight: Some(1.0),
+                rationale: None,
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+            })
+            .collect();
+
+        assert_eq!(
+            decision_chain(&nodes[3], &nodes, &edges),
+            "goal #1: Persist graph › decision #2: Which ORM? › action #4: Add schema"
+        );
+        // A decision doesn't repeat itself
+        assert_eq!(
+            decision_chain(&nodes[1], &nodes, &edges),
+            "goal #1: Persist graph › decision #2: Which ORM?"
+        );
+    }
+
+    #[test]
+    fn test_node_on_branch() {
+        let mut node = dup_node(1, "action", "Work");
+        assert!(!node_on_branch(&node, "main"));
+        node.metadata_json = Some(r#"{"branch": "main"}"#.to_string());
+        assert!(node_on_branch(&node, "main"));
+        assert!(!node_on_branch(&node, "feature"));
+    }
+
+    #[test]
+    fn test_keyword_similarity_prefix_tokens() {
+        // "auth" is a prefix of "authentication", so both keywords line up
+        let score = keyword_similarity("Add auth", "Add authentication");
+        assert!((score - 1.0).abs() < 0.01, "Expected ~100%, got {}", score);
+
+        // Short prefixes don't count
+        let score = keyword_similarity("Add ui", "Add uitests");
+        assert!(score < 0.6, "Expected partial match, got {}", score);
+    }
+
+    #[test]
+    fn test_keyword_similarity_is_symmetric() {
+        let a = "Implement dark mode toggle";
+        let b = "Dark mode support";
+        assert!((keyword_similarity(a, b) - keyword_similarity(b, a)).abs() < 0.001);
+        assert_eq!(keyword_similarity("", b), 0.0);
+    }
+
+    #[test]
+    fn test_find_file_matches_skips_nodes_with_files() {
+        let mut with_files = dup_node(1, "action", "Add login form");
+        with_files.metadata_json = Some(r#"{"files": ["src/login.rs"]}"#.to_string());
+        // No commit and an unparseable timestamp: nothing to search against
+        let mut no_window = dup_node(2, "action", "Add logout");
+        no_window.created_at = "not a timestamp".to_string();
+
+        let matches = find_file_matches(&[with_files, no_window], 0.5);
+        assert!(matches.is_empty());
+    }
+
+    // === File coverage Tests ===
+
+    #[test]
+    fn test_is_source_file() {
+        assert!(is_source_file("src/db.rs"));
+        assert!(is_source_file("web/src/App.tsx"));
+        assert!(!is_source_file("README.md"));
+        assert!(!is_source_file("Makefile"));
+    }
+
+    #[test]
+    fn test_file_coverage_groups_by_directory() {
+        let files: Vec<String> = ["src/db.rs", "src/main.rs", "src/tui/app.rs", "build.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let linked: std::collections::HashSet<String> =
+            ["src/db.rs".to_string()].into_iter().collect();
+
+        let report = file_coverage(&files, &linked);
+        assert_eq!(report.total_files, 4);
+        assert_eq!(report.covered_files, 1);
+        assert!((report.coverage_percent - 25.0).abs() < 0.01);
+
+        let dirs: Vec<_> = report
+            .directories
+            .iter()
+            .map(|d| (d.directory.as_str(), d.covered, d.total))
+            .collect();
+        assert_eq!(dirs, vec![(".", 0, 1), ("src", 1, 2), ("src/tui", 0, 1)]);
+        assert_eq!(report.directories[1].uncovered, vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_find_duplicate_groups() {
+        let nodes = vec![
+            dup_node(1, "goal", "Add auth"),
+            dup_node(2, "goal", "Add authentication"),
+            dup_node(3, "goal", "Improve build times"),
+            // Same title, different type: never grouped
+            dup_node(4, "action", "Add authentication"),
+        ];
+
+        let groups = find_duplicate_groups(&nodes, 0.8);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].keep.id, 1);
+        assert_eq!(groups[0].others.len(), 1);
+        assert_eq!(groups[0].others[0].0.id, 2);
+    }
+
+    #[test]
+    fn test_find_duplicate_groups_compares_against_keep() {
+        let nodes = vec![
+            dup_node(1, "decision", "Choose database engine"),
+            dup_node(2, "decision", "Choose database engine for cache"),
+            // Close to #2 but not to #1, so not grouped with #1 through #2
+            dup_node(3, "decision", "Database engine for cache layer"),
+        ];
+
+        let groups = find_duplicate_groups(&nodes, 0.5);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].keep.id, 1);
+        assert_eq!(groups[0].others.len(), 1);
+        assert_eq!(groups[0].others[0].0.id, 2);
+    }
 }
@@ -0,0 +1,420 @@
+//! Decision links stored in git itself
+//!
+//! Node–commit links can be mirrored into `refs/notes/deciduous` and into
+//! `Decision-Id: <change_id>` commit trailers, so `git log --notes=deciduous`
+//! shows the reasoning and a fresh clone can rebuild links without the
+//! database.
+
+use crate::db::DecisionNode;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Notes ref that holds decision links
+pub const NOTES_REF: &str = "refs/notes/deciduous";
+
+/// Trailer key used in notes and commit messages
+pub const DECISION_ID_TRAILER: &str = "Decision-Id";
+
+/// Trailer key for referencing nodes by local ID
+pub const REFS_NODE_TRAILER: &str = "Refs-Node";
+
+/// Marker identifying hooks written by deciduous
+const HOOK_MARKER: &str = "# Installed by deciduous";
+
+/// Render the note for a commit: one `Decision-Id` line per node
+///
+/// The change ID comes first so it can be parsed back; type and title follow
+/// for readers of `git log`.
+pub fn format_note(nodes: &[&DecisionNode]) -> String {
+    nodes
+        .iter()
+        .map(|n| {
+            format!(
+                "{}: {} {}: {}",
+                DECISION_ID_TRAILER, n.change_id, n.node_type, n.title
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Extract change IDs from `Decision-Id:` lines (notes or commit trailers)
+pub fn parse_decision_ids(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !key.trim().eq_ignore_ascii_case(DECISION_ID_TRAILER) {
+            continue;
+        }
+        if let Some(id) = value.split_whitespace().next() {
+            if !ids.iter().any(|existing| existing == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    ids
+}
+
+/// Nodes a commit message refers to
+#[derive(Debug, Default, PartialEq)]
+pub struct CommitRefs {
+    /// From `Decision-Id:` trailers
+    pub change_ids: Vec<String>,
+    /// From `Refs-Node:` trailers and `node#<id>` references
+    pub node_ids: Vec<i32>,
+}
+
+impl CommitRefs {
+    pub fn is_empty(&self) -> bool {
+        self.change_ids.is_empty() && self.node_ids.is_empty()
+    }
+}
+
+/// Find node references in a commit message
+///
+/// Recognizes `Decision-Id: <change_id>`, `Refs-Node: 4, #5` and `node#4`
+/// mentions anywhere in the message. A bare `#4` is left alone: it's usually
+/// an issue or PR number.
+pub fn parse_commit_refs(message: &str) -> CommitRefs {
+    let mut refs = CommitRefs {
+        change_ids: parse_decision_ids(message),
+        node_ids: Vec::new(),
+    };
+    // `node#` must start a word and the id must end one: skips "subnode#4"
+    // and "node#3rd"
+    let node_ref_re = Regex::new(r"(?i)(?:^|[^\w])node#(\d+)\b").unwrap();
+    let mut push = |id: i32| {
+        if !refs.node_ids.contains(&id) {
+            refs.node_ids.push(id);
+        }
+    };
+
+    for line in message.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case(REFS_NODE_TRAILER) {
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|v| v.trim_start_matches('#').parse().ok())
+                    .for_each(&mut push);
+                continue;
+            }
+        }
+
+        for caps in node_ref_re.captures_iter(line) {
+            if let Ok(id) = caps[1].parse() {
+                push(id);
+            }
+        }
+    }
+
+    refs
+}
+
+/// Append `Decision-Id` trailers to a commit message
+///
+/// IDs already present are skipped. Git's `#` comment lines stay at the end
+/// so the trailers land in the message itself.
+pub fn add_trailers(message: &str, change_ids: &[String]) -> String {
+    let existing = parse_decision_ids(message);
+    let missing: Vec<&String> = change_ids
+        .iter()
+        .filter(|id| !existing.contains(id))
+        .collect();
+    if missing.is_empty() {
+        return message.to_string();
+    }
+
+    let lines: Vec<&str> = message.lines().collect();
+    let split = lines
+        .iter()
+        .position(|l| l.starts_with('#'))
+        .unwrap_or(lines.len());
+    let mut body: Vec<&str> = lines[..split].to_vec();
+    while body.last().is_some_and(|l| l.trim().is_empty()) {
+        body.pop();
+    }
+
+    // Trailers must be separated from the message unless they extend an
+    // existing trailer block
+    let last_paragraph = body
+        .iter()
+        .rposition(|l| l.trim().is_empty())
+        .map(|i| &body[i + 1..]);
+    let ends_with_trailer = last_paragraph.is_some_and(|p| {
+        !p.is_empty()
+            && p.iter().all(|l| {
+                l.split_once(": ").is_some_and(|(key, _)| {
+                    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+            })
+    });
+    let mut out = body.join("\n");
+    if !body.is_empty() {
+        out.push_str(if ends_with_trailer { "\n" } else { "\n\n" });
+    }
+    for id in missing {
+        out.push_str(&format!("{}: {}\n", DECISION_ID_TRAILER, id));
+    }
+    if split < lines.len() {
+        out.push('\n');
+        out.push_str(&lines[split..].join("\n"));
+        out.push('\n');
+    }
+    out
+}
+
+/// Nodes that a commit being written now should carry trailers for
+///
+/// Actions without a commit, created after `since` (the previous commit's
+/// time, RFC 3339) and, when given, on `branch`.
+pub fn pending_trailer_nodes<'a>(
+    nodes: &'a [DecisionNode],
+    branch: Option<&str>,
+    since: Option<&str>,
+) -> Vec<&'a DecisionNode> {
+    let since = since.and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+    nodes
+        .iter()
+        .filter(|n| n.node_type == "action")
+        .filter(|n| {
+            let meta = n
+                .metadata_json
+                .as_deref()
+                .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok());
+            let has_commit = meta
+                .as_ref()
+                .and_then(|m| m.get("commit"))
+                .and_then(|c| c.as_str())
+                .is_some_and(|c| !c.is_empty());
+            let on_branch = branch.map_or(true, |b| {
+                meta.as_ref()
+                    .and_then(|m| m.get("branch"))
+                    .and_then(|v| v.as_str())
+                    == Some(b)
+            });
+            !has_commit && on_branch
+        })
+        .filter(
+            |n| match (since, chrono::DateTime::parse_from_rfc3339(&n.created_at)) {
+                (Some(since), Ok(created)) => created > since,
+                _ => true,
+            },
+        )
+        .collect()
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Resolve a (possibly abbreviated) commit to its full hash
+pub fn resolve_commit(commit: &str) -> Result<String, String> {
+    let spec = format!("{}^{{commit}}", commit);
+    git(&["rev-parse", "--verify", "--quiet", &spec])
+        .map(|s| s.trim().to_string())
+        .map_err(|_| format!("Unknown commit: {}", commit))
+}
+
+/// Write (or replace) the deciduous note on a commit
+pub fn write_note(commit: &str, note: &str) -> Result<(), String> {
+    let notes_ref = format!("--ref={}", NOTES_REF);
+    git(&["notes", &notes_ref, "add", "-f", "-m", note, commit]).map(|_| ())
+}
+
+/// Read every deciduous note as (full commit hash, note text)
+pub fn read_notes() -> Result<Vec<(String, String)>, String> {
+    let notes_ref = format!("--ref={}", NOTES_REF);
+    // An absent notes ref just means there is nothing to import
+    let Ok(list) = git(&["notes", &notes_ref, "list"]) else {
+        return Ok(Vec::new());
+    };
+
+    let mut notes = Vec::new();
+    for line in list.lines() {
+        let Some((_, commit)) = line.split_once(' ') else {
+            continue;
+        };
+        let text = git(&["notes", &notes_ref, "show", commit])?;
+        notes.push((commit.to_string(), text));
+    }
+    Ok(notes)
+}
+
+/// Commit time of HEAD (RFC 3339), if there is a HEAD
+pub fn head_commit_time() -> Option<String> {
+    git(&["log", "-1", "--format=%cI", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Directory git runs hooks from
+fn hooks_dir() -> Result<PathBuf, String> {
+    git(&["rev-parse", "--git-path", "hooks"]).map(|s| PathBuf::from(s.trim()))
+}
+
+/// Install a git hook that calls `deciduous hooks <name>`
+///
+/// Refuses to overwrite a hook that deciduous didn't write.
+pub fn install_hook(name: &str) -> Result<PathBuf, String> {
+    let dir = hooks_dir()?;
+    install_hook_in(&dir, name)
+}
+
+fn install_hook_in(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let path = dir.join(name);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !existing.contains(HOOK_MARKER) {
+            return Err(format!(
+                "{} already exists and was not installed by deciduous",
+                path.display()
+            ));
+        }
+    }
+
+    let script = format!(
+        "#!/bin/sh\n{}; remove this file to disable\ndeciduous hooks {} \"$@\" || true\n",
+        HOOK_MARKER, name
+    );
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    std::fs::write(&path, script).map_err(|e| e.to_string())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32, node_type: &str, meta: Option<&str>, created_at: &str) -> DecisionNode {
+        DecisionNode {
+            id,
+            change_id: format!("cid-{}", id),
+            node_type: node_type.to_string(),
+            title: format!("Node {}", id),
+            description: None,
+            status: "pending".to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            metadata_json: meta.map(|m| m.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_note_round_trip() {
+        let a = node(1, "action", None, "2024-01-01T00:00:00Z");
+        let b = node(2, "outcome", None, "2024-01-01T00:00:00Z");
+        let note = format_note(&[&a, &b]);
+        assert_eq!(
+            note,
+            "Decision-Id: cid-1 action: Node 1\nDecision-Id: cid-2 outcome: Node 2"
+        );
+        assert_eq!(parse_decision_ids(&note), vec!["cid-1", "cid-2"]);
+    }
+
+    #[test]
+    fn test_parse_commit_refs() {
+        let message = "Fix pool exhaustion (node#12)\n\n\
+                       Follows up on Node#7 but not subnode#4 or node#3rd.\n\n\
+                       Decision-Id: cid-1\n\
+                       Refs-Node: 4, #5\n";
+        let refs = parse_commit_refs(message);
+        assert_eq!(refs.change_ids, vec!["cid-1"]);
+        assert_eq!(refs.node_ids, vec![12, 7, 4, 5]);
+
+        assert!(parse_commit_refs("Plain message").is_empty());
+        // Issue and PR references aren't nodes
+        assert!(parse_commit_refs("Fix login (#12), closes #7").is_empty());
+    }
+
+    #[test]
+    fn test_add_trailers() {
+        let message = "Add schema\n\nLonger body.\n\n# Please enter the commit message\n";
+        let out = add_trailers(message, &["cid-1".to_string()]);
+        assert_eq!(
+            out,
+            "Add schema\n\nLonger body.\n\nDecision-Id: cid-1\n\n# Please enter the commit message\n"
+        );
+
+        // Existing trailers are extended, and IDs aren't repeated
+        let out = add_trailers(&out, &["cid-1".to_string(), "cid-2".to_string()]);
+        assert!(out
+            .starts_with("Add schema\n\nLonger body.\n\nDecision-Id: cid-1\nDecision-Id: cid-2\n"));
+        assert_eq!(add_trailers(&out, &["cid-2".to_string()]), out);
+    }
+
+    #[test]
+    fn test_pending_trailer_nodes() {
+        let nodes = vec![
+            node(
+                1,
+                "action",
+                Some(r#"{"branch": "main"}"#),
+                "2024-01-02T00:00:00Z",
+            ),
+            node(
+                2,
+                "action",
+                Some(r#"{"commit": "abc1234"}"#),
+                "2024-01-02T00:00:00Z",
+            ),
+            node(
+                3,
+                "action",
+                Some(r#"{"branch": "main"}"#),
+                "2023-12-31T00:00:00Z",
+            ),
+            node(4, "goal", None, "2024-01-02T00:00:00Z"),
+            node(
+                5,
+                "action",
+                Some(r#"{"branch": "other"}"#),
+                "2024-01-02T00:00:00Z",
+            ),
+        ];
+
+        let ids: Vec<i32> =
+            pending_trailer_nodes(&nodes, Some("main"), Some("2024-01-01T00:00:00Z"))
+                .iter()
+                .map(|n| n.id)
+                .collect();
+        assert_eq!(ids, vec![1]);
+
+        let ids: Vec<i32> = pending_trailer_nodes(&nodes, None, None)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(ids, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_install_hook_refuses_foreign_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = install_hook_in(dir.path(), "prepare-commit-msg").unwrap();
+        let script = std::fs::read_to_string(&path).unwrap();
+        assert!(script.contains("deciduous hooks prepare-commit-msg"));
+
+        // Reinstalling over our own hook is fine
+        install_hook_in(dir.path(), "prepare-commit-msg").unwrap();
+
+        std::fs::write(dir.path().join("post-commit"), "#!/bin/sh\necho hi\n").unwrap();
+        assert!(install_hook_in(dir.path(), "post-commit").is_err());
+    }
+}
@@ -0,0 +1,525 @@
+//! Roadmap progress reporting
+//!
+//! Buckets roadmap progress into weeks: tasks checked off, tasks linked to
+//! outcome nodes and issues closed, plus the tasks still open at the end of
+//! each week (the burndown). Each event is dated from `roadmap_item_history`
+//! when it was recorded there, falling back to the outcome node's creation
+//! time for links and to the item's `updated_at` otherwise.
+
+use crate::db::{RoadmapItem, RoadmapItemHistory};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// Writing to String never fails, but write! returns Result
+macro_rules! w {
+    ($dst:expr, $($arg:tt)*) => {
+        let _ = write!($dst, $($arg)*);
+    };
+}
+
+macro_rules! wln {
+    ($dst:expr) => {
+        let _ = writeln!($dst);
+    };
+    ($dst:expr, $($arg:tt)*) => {
+        let _ = writeln!($dst, $($arg)*);
+    };
+}
+
+/// Output format of `roadmap report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            "html" | "svg" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Progress in one week (Monday to Sunday)
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WeekStats {
+    pub week_start: NaiveDate,
+    /// Tasks checked off during the week
+    pub checked: usize,
+    /// Items linked to an outcome during the week
+    pub linked: usize,
+    /// Issues closed during the week
+    pub closed: usize,
+    /// Tasks still unchecked at the end of the week
+    pub remaining: usize,
+}
+
+/// Weekly roadmap progress
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RoadmapReport {
+    /// Tasks on the roadmap (section headers excluded)
+    pub total: usize,
+    /// Oldest week first, ending with the current week
+    pub weeks: Vec<WeekStats>,
+}
+
+/// Monday of the week containing `date`
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Date of an RFC 3339 timestamp, or of a plain YYYY-MM-DD prefix
+fn date_of(timestamp: &str) -> Option<NaiveDate> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.date_naive())
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(timestamp.get(..10)?, "%Y-%m-%d").ok())
+}
+
+/// When `field` of an item last changed to `value`, per the history
+fn last_change<'a>(
+    history: &'a [RoadmapItemHistory],
+    change_id: &str,
+    field: &str,
+    value: &str,
+) -> Option<&'a str> {
+    history
+        .iter()
+        .rev()
+        .find(|h| {
+            h.item_change_id == change_id
+                && h.field == field
+                && h.new_value.as_deref() == Some(value)
+        })
+        .map(|h| h.changed_at.as_str())
+}
+
+impl RoadmapReport {
+    /// Build a report covering the `weeks` weeks up to and including
+    /// `today`'s
+    ///
+    /// `outcome_created` maps outcome change_ids to their creation time.
+    pub fn build(
+        items: &[RoadmapItem],
+        history: &[RoadmapItemHistory],
+        outcome_created: &HashMap<String, String>,
+        today: NaiveDate,
+        weeks: usize,
+    ) -> Self {
+        let tasks: Vec<&RoadmapItem> = items
+            .iter()
+            .filter(|i| i.checkbox_state != "none")
+            .collect();
+
+        let mut checked_dates = Vec::new();
+        let mut linked_dates = Vec::new();
+        let mut closed_dates = Vec::new();
+        for item in items {
+            if item.checkbox_state == "checked" {
+                let at = last_change(history, &item.change_id, "checkbox", "checked")
+                    .unwrap_or(&item.updated_at);
+                checked_dates.extend(date_of(at));
+            }
+            if let Some(ref outcome) = item.outcome_change_id {
+                let at = last_change(history, &item.change_id, "outcome", outcome)
+                    .or_else(|| outcome_created.get(outcome).map(String::as_str))
+                    .unwrap_or(&item.updated_at);
+                linked_dates.extend(date_of(at));
+            }
+            if item.github_issue_state.as_deref() == Some("closed") {
+                let at = last_change(history, &item.change_id, "issue_state", "closed")
+                    .unwrap_or(&item.updated_at);
+                closed_dates.extend(date_of(at));
+            }
+        }
+
+        let current = week_start(today);
+        let weeks = (0..weeks.max(1) as i64)
+            .rev()
+            .map(|back| {
+                let start = current - Duration::weeks(back);
+                let end = start + Duration::days(6);
+                let in_week = |dates: &[NaiveDate]| {
+                    dates.iter().filter(|d| **d >= start && **d <= end).count()
+                };
+                let done_by_end = checked_dates.iter().filter(|d| **d <= end).count();
+                WeekStats {
+                    week_start: start,
+                    checked: in_week(&checked_dates),
+                    linked: in_week(&linked_dates),
+                    closed: in_week(&closed_dates),
+                    remaining: tasks.len().saturating_sub(done_by_end),
+                }
+            })
+            .collect();
+
+        RoadmapReport {
+            total: tasks.len(),
+            weeks,
+        }
+    }
+
+    /// Tasks still open now
+    pub fn remaining(&self) -> usize {
+        self.weeks.last().map_or(self.total, |w| w.remaining)
+    }
+
+    /// Average tasks checked per week over the report
+    pub fn velocity(&self) -> f64 {
+        if self.weeks.is_empty() {
+            return 0.0;
+        }
+        let checked: usize = self.weeks.iter().map(|w| w.checked).sum();
+        checked as f64 / self.weeks.len() as f64
+    }
+
+    /// Weeks left at the current velocity (None when nothing is moving)
+    pub fn weeks_to_done(&self) -> Option<f64> {
+        let velocity = self.velocity();
+        (velocity > 0.0).then(|| self.remaining() as f64 / velocity)
+    }
+
+    /// Table with a bar chart of remaining tasks
+    pub fn to_text(&self) -> String {
+        const BAR_WIDTH: usize = 30;
+        let mut out = String::new();
+
+        wln!(
+            out,
+            "Roadmap progress ({} weeks, {} tasks)\n",
+            self.weeks.len(),
+            self.total
+        );
+        wln!(
+            out,
+            "{:<10}  {:>7}  {:>6}  {:>6}  {:>9}",
+            "Week of",
+            "Checked",
+            "Linked",
+            "Closed",
+            "Remaining"
+        );
+        for week in &self.weeks {
+            let bar = (week.remaining * BAR_WIDTH + self.total.saturating_sub(1))
+                .checked_div(self.total)
+                .unwrap_or(0);
+            wln!(
+                out,
+                "{:<10}  {:>7}  {:>6}  {:>6}  {:>9}  {}",
+                week.week_start,
+                week.checked,
+                week.linked,
+                week.closed,
+                week.remaining,
+                "█".repeat(bar)
+            );
+        }
+
+        w!(
+            out,
+            "\nVelocity: {:.1} tasks/week, {} remaining",
+            self.velocity(),
+            self.remaining()
+        );
+        match self.weeks_to_done() {
+            Some(weeks) if self.remaining() > 0 => {
+                wln!(out, " (~{:.0} weeks at this pace)", weeks.ceil());
+            }
+            _ => {
+                wln!(out);
+            }
+        }
+        out
+    }
+
+    /// One row per week
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("week_start,checked,linked,closed,remaining\n");
+        for week in &self.weeks {
+            wln!(
+                out,
+                "{},{},{},{},{}",
+                week.week_start,
+                week.checked,
+                week.linked,
+                week.closed,
+                week.remaining
+            );
+        }
+        out
+    }
+
+    /// Standalone HTML page with an SVG burndown and velocity bars
+    pub fn to_html(&self, title: &str) -> String {
+        const WIDTH: f64 = 720.0;
+        const HEIGHT: f64 = 280.0;
+        const PAD: f64 = 40.0;
+
+        let plot_w = WIDTH - 2.0 * PAD;
+        let plot_h = HEIGHT - 2.0 * PAD;
+        let slot = plot_w / self.weeks.len().max(1) as f64;
+        let max_checked = self.weeks.iter().map(|w| w.checked).max().unwrap_or(0);
+        let y_max = self.total.max(max_checked).max(1) as f64;
+        let y = |value: usize| PAD + plot_h - value as f64 / y_max * plot_h;
+
+        let mut svg = String::new();
+        wln!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="11">"##,
+            w = WIDTH,
+            h = HEIGHT
+        );
+        wln!(
+            svg,
+            r##"  <line x1="{p}" y1="{b}" x2="{r}" y2="{b}" stroke="#999"/>"##,
+            p = PAD,
+            b = PAD + plot_h,
+            r = PAD + plot_w
+        );
+        wln!(
+            svg,
+            r##"  <line x1="{p}" y1="{p}" x2="{p}" y2="{b}" stroke="#999"/>"##,
+            p = PAD,
+            b = PAD + plot_h
+        );
+        wln!(
+            svg,
+            r##"  <text x="{x}" y="{y}" text-anchor="end">{total}</text>"##,
+            x = PAD - 4.0,
+            y = y(self.total) + 4.0,
+            total = self.total
+        );
+
+        // Velocity bars
+        for (i, week) in self.weeks.iter().enumerate() {
+            let x = PAD + i as f64 * slot + slot * 0.25;
+            wln!(
+                svg,
+                r##"  <rect x="{x:.1}" y="{y:.1}" width="{w:.1}" height="{h:.1}" fill="#9ecae1"><title>{week}: {n} checked</title></rect>"##,
+                x = x,
+                y = y(week.checked),
+                w = slot * 0.5,
+                h = PAD + plot_h - y(week.checked),
+                week = week.week_start,
+                n = week.checked
+            );
+            wln!(
+                svg,
+                r##"  <text x="{x:.1}" y="{y}" text-anchor="middle">{label}</text>"##,
+                x = PAD + i as f64 * slot + slot / 2.0,
+                y = PAD + plot_h + 14.0,
+                label = week.week_start.format("%m-%d")
+            );
+        }
+
+        // Burndown line
+        let points: Vec<String> = self
+            .weeks
+            .iter()
+            .enumerate()
+            .map(|(i, week)| {
+                format!(
+                    "{:.1},{:.1}",
+                    PAD + i as f64 * slot + slot / 2.0,
+                    y(week.remaining)
+                )
+            })
+            .collect();
+        wln!(
+            svg,
+            r##"  <polyline points="{}" fill="none" stroke="#d62728" stroke-width="2"/>"##,
+            points.join(" ")
+        );
+        wln!(
+            svg,
+            r##"  <text x="{x}" y="16" fill="#d62728">remaining</text><text x="{x2}" y="16" fill="#3182bd">checked per week</text>"##,
+            x = PAD,
+            x2 = PAD + 80.0
+        );
+        wln!(svg, "</svg>");
+
+        let mut rows = String::new();
+        for week in &self.weeks {
+            wln!(
+                rows,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                week.week_start,
+                week.checked,
+                week.linked,
+                week.closed,
+                week.remaining
+            );
+        }
+
+        let title = escape_html(title);
+        let mut out = String::new();
+        wln!(out, "<!DOCTYPE html>");
+        wln!(out, "<html>\n<head>\n<meta charset=\"utf-8\">");
+        wln!(out, "<title>{}</title>", title);
+        wln!(
+            out,
+            "<style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse}}td,th{{padding:4px 10px;text-align:right;border-bottom:1px solid #ddd}}</style>"
+        );
+        wln!(out, "</head>\n<body>");
+        wln!(out, "<h1>{}</h1>", title);
+        wln!(
+            out,
+            "<p>{} tasks, {} remaining, {:.1} checked per week</p>",
+            self.total,
+            self.remaining(),
+            self.velocity()
+        );
+        w!(out, "{}", svg);
+        wln!(
+            out,
+            "<table>\n<tr><th>Week of</th><th>Checked</th><th>Linked</th><th>Closed</th><th>Remaining</th></tr>"
+        );
+        w!(out, "{}", rows);
+        wln!(out, "</table>\n</body>\n</html>");
+        out
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(change_id: &str, checkbox: &str, updated_at: &str) -> RoadmapItem {
+        RoadmapItem {
+            id: 0,
+            change_id: change_id.to_string(),
+            title: change_id.to_string(),
+            description: None,
+            section: Some("Beta".to_string()),
+            parent_id: None,
+            checkbox_state: checkbox.to_string(),
+            github_issue_number: None,
+            github_issue_state: None,
+            outcome_node_id: None,
+            outcome_change_id: None,
+            markdown_line_start: None,
+            markdown_line_end: None,
+            content_hash: None,
+            created_at: "2024-05-01T00:00:00+00:00".to_string(),
+            updated_at: updated_at.to_string(),
+            last_synced_at: None,
+            synced_checkbox_state: None,
+            milestone: None,
+            due_date: None,
+            goal_change_id: None,
+            roadmap_path: None,
+        }
+    }
+
+    fn change(change_id: &str, field: &str, value: &str, at: &str) -> RoadmapItemHistory {
+        RoadmapItemHistory {
+            id: 0,
+            item_change_id: change_id.to_string(),
+            field: field.to_string(),
+            old_value: None,
+            new_value: Some(value.to_string()),
+            changed_at: at.to_string(),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn sample() -> RoadmapReport {
+        let mut section = item("section", "none", "2024-06-12T00:00:00+00:00");
+        section.github_issue_state = Some("closed".to_string());
+
+        let mut linked = item("b", "checked", "2024-06-12T00:00:00+00:00");
+        linked.outcome_change_id = Some("outcome-1".to_string());
+
+        let items = vec![
+            section,
+            // Checked per history in the first week, touched again later
+            item("a", "checked", "2024-06-12T00:00:00+00:00"),
+            linked,
+            item("c", "unchecked", "2024-06-12T00:00:00+00:00"),
+            item("d", "unchecked", "2024-06-12T00:00:00+00:00"),
+        ];
+        let history = vec![change(
+            "a",
+            "checkbox",
+            "checked",
+            "2024-06-04T10:00:00+00:00",
+        )];
+        let outcomes = HashMap::from([(
+            "outcome-1".to_string(),
+            "2024-06-05T09:00:00+00:00".to_string(),
+        )]);
+
+        RoadmapReport::build(&items, &history, &outcomes, date("2024-06-13"), 2)
+    }
+
+    #[test]
+    fn test_week_start() {
+        assert_eq!(week_start(date("2024-06-13")), date("2024-06-10"));
+        assert_eq!(week_start(date("2024-06-10")), date("2024-06-10"));
+        assert_eq!(week_start(date("2024-06-09")), date("2024-06-03"));
+    }
+
+    #[test]
+    fn test_build_buckets_events_by_week() {
+        let report = sample();
+        assert_eq!(report.total, 4);
+        assert_eq!(
+            report.weeks,
+            vec![
+                WeekStats {
+                    week_start: date("2024-06-03"),
+                    checked: 1,
+                    linked: 1,
+                    closed: 0,
+                    remaining: 3,
+                },
+                WeekStats {
+                    week_start: date("2024-06-10"),
+                    checked: 1,
+                    linked: 0,
+                    closed: 1,
+                    remaining: 2,
+                },
+            ]
+        );
+        assert_eq!(report.remaining(), 2);
+        assert_eq!(report.velocity(), 1.0);
+        assert_eq!(report.weeks_to_done(), Some(2.0));
+    }
+
+    #[test]
+    fn test_outputs() {
+        let report = sample();
+
+        let text = report.to_text();
+        assert!(text.contains("Roadmap progress (2 weeks, 4 tasks)"));
+        assert!(text.contains("2024-06-10"));
+        assert!(text.contains("Velocity: 1.0 tasks/week, 2 remaining (~2 weeks"));
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "week_start,checked,linked,closed,remaining");
+        assert_eq!(lines[2], "2024-06-10,1,0,1,2");
+
+        let html = report.to_html("Q3 <roadmap>");
+        assert!(html.contains("<title>Q3 &lt;roadmap&gt;</title>"));
+        assert!(html.contains("<polyline"));
+        assert_eq!(html.matches("<rect").count(), 2);
+    }
+}
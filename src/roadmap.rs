@@ -3,7 +3,12 @@
 //! Parses ROADMAP.md format into structured roadmap items,
 //! handles metadata comments for sync, and provides utilities
 //! for bidirectional synchronization with GitHub Issues.
+//!
+//! Checkbox sync is a three-way merge: each item's state at the last sync
+//! (stored in the database) is the base, so a box ticked on either side is
+//! carried to the other.
 
+use chrono::NaiveDate;
 use regex::Regex;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -21,11 +26,27 @@ pub struct RoadmapSection {
     pub items: Vec<RoadmapCheckItem>,
     pub github_issue_number: Option<i32>,
     pub github_issue_state: Option<String>,
+    /// Milestone name (`milestone="..."` in the section metadata)
+    pub milestone: Option<String>,
+    /// Target date as written (`due="YYYY-MM-DD"`); see [`RoadmapSection::due_date`]
+    pub due: Option<String>,
     pub line_start: usize,
     pub line_end: usize,
     pub content_hash: String,
 }
 
+impl RoadmapSection {
+    /// Target date, if set and valid
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        self.due.as_deref().and_then(parse_due_date)
+    }
+
+    /// Number of checked items
+    pub fn checked_count(&self) -> usize {
+        self.items.iter().filter(|i| i.checked).count()
+    }
+}
+
 /// Represents a checkbox item (- [ ] or - [x])
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RoadmapCheckItem {
@@ -33,6 +54,15 @@ pub struct RoadmapCheckItem {
     pub text: String,
     pub checked: bool,
     pub outcome_change_id: Option<String>,
+    /// Goal node tracking this item (`roadmap start` / `roadmap promote`)
+    #[serde(default)]
+    pub goal_change_id: Option<String>,
+    /// Nesting level: 0 for top-level items, 1 for their sub-items, and so on
+    #[serde(default)]
+    pub depth: usize,
+    /// Item this one is nested under
+    #[serde(default)]
+    pub parent_change_id: Option<String>,
     pub line_number: usize,
 }
 
@@ -43,6 +73,8 @@ pub struct SectionMetadata {
     pub issue: Option<i32>,
     pub status: Option<String>,
     pub last_sync: Option<String>,
+    pub milestone: Option<String>,
+    pub due: Option<String>,
 }
 
 /// Metadata for checkbox items
@@ -50,6 +82,7 @@ pub struct SectionMetadata {
 pub struct ItemMetadata {
     pub id: Option<String>,
     pub outcome_change_id: Option<String>,
+    pub goal_change_id: Option<String>,
 }
 
 /// Result of parsing ROADMAP.md
@@ -138,11 +171,27 @@ pub fn parse_section_metadata(comment: &str) -> SectionMetadata {
         meta.last_sync = caps.get(1).map(|m| m.as_str().to_string());
     }
 
+    // Extract milestone
+    if let Some(caps) = Regex::new(r#"milestone="([^"]+)""#)
+        .ok()
+        .and_then(|re| re.captures(comment))
+    {
+        meta.milestone = caps.get(1).map(|m| m.as_str().to_string());
+    }
+
+    // Extract due date
+    if let Some(caps) = Regex::new(r#"due="([^"]+)""#)
+        .ok()
+        .and_then(|re| re.captures(comment))
+    {
+        meta.due = caps.get(1).map(|m| m.as_str().to_string());
+    }
+
     meta
 }
 
 /// Parse item metadata from HTML comment
-/// Format: <!-- roadmap:item id="UUID" outcome_change_id="UUID" -->
+/// Format: <!-- roadmap:item id="UUID" outcome_change_id="UUID" goal_change_id="UUID" -->
 pub fn parse_item_metadata(comment: &str) -> ItemMetadata {
     let mut meta = ItemMetadata::default();
 
@@ -166,6 +215,14 @@ pub fn parse_item_metadata(comment: &str) -> ItemMetadata {
         }
     }
 
+    // Extract goal_change_id
+    if let Some(caps) = Regex::new(r#"goal_change_id="([^"]+)""#)
+        .ok()
+        .and_then(|re| re.captures(comment))
+    {
+        meta.goal_change_id = caps.get(1).map(|m| m.as_str().to_string());
+    }
+
     meta
 }
 
@@ -175,6 +232,8 @@ pub fn generate_section_metadata(
     issue_number: Option<i32>,
     issue_state: Option<&str>,
     last_sync: Option<&str>,
+    milestone: Option<&str>,
+    due: Option<&str>,
 ) -> String {
     let mut parts = vec![format!(r#"id="{}""#, change_id)];
 
@@ -190,19 +249,54 @@ pub fn generate_section_metadata(
         parts.push(format!(r#"last_sync="{}""#, sync));
     }
 
+    if let Some(milestone) = milestone {
+        parts.push(format!(r#"milestone="{}""#, milestone.replace('"', "'")));
+    }
+
+    if let Some(due) = due {
+        parts.push(format!(r#"due="{}""#, due));
+    }
+
     format!("<!-- roadmap:section {} -->", parts.join(" "))
 }
 
 /// Generate item metadata comment
-pub fn generate_item_metadata(change_id: &str, outcome_change_id: Option<&str>) -> String {
+pub fn generate_item_metadata(
+    change_id: &str,
+    outcome_change_id: Option<&str>,
+    goal_change_id: Option<&str>,
+) -> String {
     let outcome = outcome_change_id.unwrap_or("");
+    let goal = goal_change_id
+        .map(|g| format!(r#" goal_change_id="{}""#, g))
+        .unwrap_or_default();
     format!(
-        r#"<!-- roadmap:item id="{}" outcome_change_id="{}" -->"#,
-        change_id, outcome
+        r#"<!-- roadmap:item id="{}" outcome_change_id="{}"{} -->"#,
+        change_id, outcome, goal
     )
 }
 
+/// Key a roadmap file is tracked under (sync state, imported items)
+///
+/// `./ROADMAP.md` and `ROADMAP.md` are the same file.
+pub fn roadmap_key<P: AsRef<Path>>(path: P) -> String {
+    let key = path.as_ref().to_string_lossy().replace('\\', "/");
+    let mut key = key.as_str();
+    while let Some(rest) = key.strip_prefix("./") {
+        key = rest;
+    }
+    key.to_string()
+}
+
+/// Width of a line's leading whitespace, counting tabs as four columns
+fn indent_width(leading: &str) -> usize {
+    leading.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum()
+}
+
 /// Parse ROADMAP.md file into structured sections
+///
+/// Indented checkboxes become sub-items of the nearest less-indented item
+/// above them, in the same section.
 pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
     let content = fs::read_to_string(path.as_ref())?;
     let path_str = path.as_ref().to_string_lossy().to_string();
@@ -213,7 +307,7 @@ pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
 
     // Regex patterns
     let header_re = Regex::new(r"^(#{2,3})\s+(.+)$")?;
-    let checkbox_re = Regex::new(r"^-\s+\[([ xX])\]\s+(.+)$")?;
+    let checkbox_re = Regex::new(r"^(\s*)-\s+\[([ xX])\]\s+(.+)$")?;
     let section_meta_re = Regex::new(r"<!--\s*roadmap:section\s+(.+?)\s*-->")?;
     let item_meta_re = Regex::new(r"<!--\s*roadmap:item\s+(.+?)\s*-->")?;
 
@@ -248,6 +342,8 @@ pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
             // Collect description lines until next header or checkbox
             let mut description_lines: Vec<&str> = Vec::new();
             let mut items: Vec<RoadmapCheckItem> = Vec::new();
+            // Open parents as (indent width, change_id), outermost first
+            let mut parents: Vec<(usize, String)> = Vec::new();
             let mut j = description_start;
 
             while j < lines.len() {
@@ -260,8 +356,9 @@ pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
 
                 // Check for checkbox item
                 if let Some(check_caps) = checkbox_re.captures(next_line) {
-                    let checked = check_caps.get(1).unwrap().as_str().to_lowercase() == "x";
-                    let text = check_caps.get(2).unwrap().as_str().trim().to_string();
+                    let indent = indent_width(check_caps.get(1).unwrap().as_str());
+                    let checked = check_caps.get(2).unwrap().as_str().to_lowercase() == "x";
+                    let text = check_caps.get(3).unwrap().as_str().trim().to_string();
 
                     // Look for item metadata on next line
                     let mut item_meta = ItemMetadata::default();
@@ -275,11 +372,21 @@ pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
 
                     let item_change_id = item_meta.id.unwrap_or_else(|| Uuid::new_v4().to_string());
 
+                    while parents.last().is_some_and(|(w, _)| *w >= indent) {
+                        parents.pop();
+                    }
+                    let depth = parents.len();
+                    let parent_change_id = parents.last().map(|(_, id)| id.clone());
+                    parents.push((indent, item_change_id.clone()));
+
                     items.push(RoadmapCheckItem {
                         change_id: item_change_id,
                         text,
                         checked,
                         outcome_change_id: item_meta.outcome_change_id,
+                        goal_change_id: item_meta.goal_change_id,
+                        depth,
+                        parent_change_id,
                         line_number: j + 1, // 1-indexed
                     });
                 } else if !next_line.trim().is_empty()
@@ -314,6 +421,8 @@ pub fn parse_roadmap<P: AsRef<Path>>(path: P) -> Result<ParsedRoadmap> {
                 items,
                 github_issue_number: section_meta.issue,
                 github_issue_state: section_meta.status,
+                milestone: section_meta.milestone,
+                due: section_meta.due,
                 line_start,
                 line_end,
                 content_hash: section_hash,
@@ -342,13 +451,16 @@ pub fn write_roadmap_with_metadata<P: AsRef<Path>>(
     let mut output_lines: Vec<String> = Vec::new();
 
     let header_re = Regex::new(r"^(#{2,3})\s+(.+)$")?;
-    let checkbox_re = Regex::new(r"^-\s+\[([ xX])\]\s+(.+)$")?;
+    let checkbox_re = Regex::new(r"^(\s*)-\s+\[([ xX])\]\s+(.+)$")?;
     let section_meta_re = Regex::new(r"<!--\s*roadmap:section\s+(.+?)\s*-->")?;
     let item_meta_re = Regex::new(r"<!--\s*roadmap:item\s+(.+?)\s*-->")?;
 
     // Build lookup maps
     let section_map: HashMap<String, &RoadmapSection> =
         sections.iter().map(|s| (s.title.clone(), s)).collect();
+    let all_items: Vec<&RoadmapCheckItem> = sections.iter().flat_map(|s| &s.items).collect();
+    // Each item claims one line, so repeated sub-items ("Tests") stay apart
+    let mut claimed: Vec<bool> = vec![false; all_items.len()];
 
     let mut i = 0;
     while i < lines.len() {
@@ -373,6 +485,8 @@ pub fn write_roadmap_with_metadata<P: AsRef<Path>>(
                     section.github_issue_number,
                     section.github_issue_state.as_deref(),
                     None, // last_sync will be set by sync operation
+                    section.milestone.as_deref(),
+                    section.due.as_deref(),
                 );
                 output_lines.push(meta_comment);
             }
@@ -383,19 +497,46 @@ pub fn write_roadmap_with_metadata<P: AsRef<Path>>(
 
         // Check for checkbox item
         if let Some(check_caps) = checkbox_re.captures(line) {
-            let text = check_caps.get(2).unwrap().as_str().trim();
-
-            output_lines.push(line.to_string());
-
-            // Find matching item in sections
-            let mut found_item: Option<&RoadmapCheckItem> = None;
-            for section in sections {
-                for item in &section.items {
-                    if item.text.contains(text) || text.contains(&item.text) {
-                        found_item = Some(item);
-                        break;
-                    }
+            let leading = check_caps.get(1).unwrap().as_str();
+            let text = check_caps.get(3).unwrap().as_str().trim();
+            let checked = check_caps.get(2).unwrap().as_str() != " ";
+
+            // Find matching item: by the id already on the line, then by text
+            let existing_id = lines
+                .get(i + 1)
+                .and_then(|next| item_meta_re.captures(next))
+                .and_then(|c| parse_item_metadata(c.get(1).unwrap().as_str()).id);
+            let found = existing_id
+                .and_then(|id| {
+                    (0..all_items.len()).find(|&k| !claimed[k] && all_items[k].change_id == id)
+                })
+                .or_else(|| {
+                    (0..all_items.len()).find(|&k| !claimed[k] && all_items[k].text == text)
+                })
+                .or_else(|| {
+                    (0..all_items.len()).find(|&k| {
+                        !claimed[k]
+                            && (all_items[k].text.contains(text)
+                                || text.contains(&all_items[k].text))
+                    })
+                });
+            if let Some(k) = found {
+                claimed[k] = true;
+            }
+            let found_item = found.map(|k| all_items[k]);
+
+            // Write the item's checkbox state (it may have been ticked remotely)
+            match found_item {
+                Some(item) if item.checked != checked => {
+                    let mark = check_caps.get(2).unwrap();
+                    output_lines.push(format!(
+                        "{}{}{}",
+                        &line[..mark.start()],
+                        if item.checked { "x" } else { " " },
+                        &line[mark.end()..]
+                    ));
                 }
+                _ => output_lines.push(line.to_string()),
             }
 
             // Skip existing item metadata if present
@@ -405,9 +546,12 @@ pub fn write_roadmap_with_metadata<P: AsRef<Path>>(
 
             // Add updated metadata
             if let Some(item) = found_item {
-                let meta_comment =
-                    generate_item_metadata(&item.change_id, item.outcome_change_id.as_deref());
-                output_lines.push(format!("  {}", meta_comment));
+                let meta_comment = generate_item_metadata(
+                    &item.change_id,
+                    item.outcome_change_id.as_deref(),
+                    item.goal_change_id.as_deref(),
+                );
+                output_lines.push(format!("{}  {}", leading, meta_comment));
             }
 
             i += 1;
@@ -447,7 +591,12 @@ pub fn generate_issue_body(section: &RoadmapSection) -> String {
         body.push_str("## Tasks\n\n");
         for item in &section.items {
             let checkbox = if item.checked { "[x]" } else { "[ ]" };
-            body.push_str(&format!("- {} {}\n", checkbox, item.text));
+            body.push_str(&format!(
+                "{}- {} {}\n",
+                "  ".repeat(item.depth),
+                checkbox,
+                item.text
+            ));
         }
     }
 
@@ -463,18 +612,399 @@ pub fn generate_issue_body(section: &RoadmapSection) -> String {
 
 /// Parse checkbox state from issue body
 pub fn parse_issue_body_checkboxes(body: &str) -> Vec<(String, bool)> {
+    issue_body_tasks(body)
+        .into_iter()
+        .map(|task| (task.key.text().to_string(), task.checked))
+        .collect()
+}
+
+/// Identifies a checklist item by the texts of the items it's nested under,
+/// its own text, and how many earlier items share that path
+///
+/// Sub-items repeated under different parents ("Tests" under both "Parser"
+/// and "Writer") get different keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskKey {
+    path: Vec<String>,
+    occurrence: usize,
+}
+
+impl TaskKey {
+    fn text(&self) -> &str {
+        self.path.last().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// Key each item of a checklist given as (indent, text) pairs, in order
+fn task_keys<'a>(items: impl IntoIterator<Item = (usize, &'a str)>) -> Vec<TaskKey> {
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    let mut seen: HashMap<Vec<String>, usize> = HashMap::new();
+
+    items
+        .into_iter()
+        .map(|(indent, text)| {
+            while parents.last().is_some_and(|(w, _)| *w >= indent) {
+                parents.pop();
+            }
+            let path: Vec<String> = parents
+                .iter()
+                .map(|(_, t)| t.to_string())
+                .chain(std::iter::once(text.to_string()))
+                .collect();
+            parents.push((indent, text));
+
+            let count = seen.entry(path.clone()).or_insert(0);
+            let occurrence = *count;
+            *count += 1;
+            TaskKey { path, occurrence }
+        })
+        .collect()
+}
+
+/// Keys of a section's items, in order
+pub fn section_task_keys(section: &RoadmapSection) -> Vec<TaskKey> {
+    task_keys(section.items.iter().map(|i| (i.depth, i.text.as_str())))
+}
+
+/// A checkbox line in an issue body
+struct IssueTask {
+    /// Index of the line in `body.split('\n')`
+    line: usize,
+    key: TaskKey,
+    checked: bool,
+}
+
+fn issue_body_tasks(body: &str) -> Vec<IssueTask> {
     let checkbox_re = Regex::new(r"-\s+\[([ xX])\]\s+(.+)").unwrap();
-    let mut items = Vec::new();
+    let mut found = Vec::new();
 
-    for line in body.lines() {
+    for (line_index, line) in body.split('\n').enumerate() {
         if let Some(caps) = checkbox_re.captures(line) {
             let checked = caps.get(1).unwrap().as_str().to_lowercase() == "x";
-            let text = caps.get(2).unwrap().as_str().trim().to_string();
-            items.push((text, checked));
+            let text = caps.get(2).unwrap().as_str().trim();
+            let leading = &line[..line.len() - line.trim_start().len()];
+            found.push((line_index, indent_width(leading), text, checked));
         }
     }
 
-    items
+    let keys = task_keys(found.iter().map(|(_, indent, text, _)| (*indent, *text)));
+    found
+        .into_iter()
+        .zip(keys)
+        .map(|((line, _, _, checked), key)| IssueTask { line, key, checked })
+        .collect()
+}
+
+/// Tick or untick the task `key` in an issue body, leaving everything else
+/// as it is
+///
+/// Returns `None` if the body doesn't list the task.
+pub fn set_issue_body_checkbox(body: &str, key: &TaskKey, checked: bool) -> Option<String> {
+    let checkbox_re = Regex::new(r"-\s+\[([ xX])\]").unwrap();
+    let target = issue_body_tasks(body)
+        .into_iter()
+        .find(|task| &task.key == key)?
+        .line;
+
+    let lines: Vec<String> = body
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| match checkbox_re.captures(line) {
+            Some(caps) if i == target => {
+                let mark = caps.get(1).unwrap();
+                format!(
+                    "{}{}{}",
+                    &line[..mark.start()],
+                    if checked { "x" } else { " " },
+                    &line[mark.end()..]
+                )
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+/// Add an unchecked item to the end of a section, creating the section
+/// (as `###`) at the end of the document if there is none by that title
+pub fn append_item(
+    content: &str,
+    section_title: &str,
+    text: &str,
+    change_id: &str,
+    goal_change_id: Option<&str>,
+) -> String {
+    let header_re = Regex::new(r"^(#{2,3})\s+(.+)$").unwrap();
+    let item_lines = [
+        format!("- [ ] {}", text),
+        format!(
+            "  {}",
+            generate_item_metadata(change_id, None, goal_change_id)
+        ),
+    ];
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let header = lines.iter().position(|l| {
+        header_re
+            .captures(l)
+            .is_some_and(|c| c[2].trim().eq_ignore_ascii_case(section_title.trim()))
+    });
+
+    match header {
+        Some(header) => {
+            let next_header = lines[header + 1..]
+                .iter()
+                .position(|l| header_re.is_match(l))
+                .map_or(lines.len(), |p| header + 1 + p);
+            // After the section's last non-blank line
+            let last = (header..next_header)
+                .rev()
+                .find(|&i| !lines[i].trim().is_empty())
+                .unwrap_or(header);
+            let at = last + 1;
+            // Keep items apart from a description paragraph
+            let after_text = last > header
+                && !lines[last].trim_start().starts_with("- [")
+                && !lines[last].trim_start().starts_with("<!--");
+            let mut insert: Vec<String> = Vec::new();
+            if after_text || last == header {
+                insert.push(String::new());
+            }
+            insert.extend(item_lines);
+            lines.splice(at..at, insert);
+        }
+        None => {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("### {}", section_title));
+            lines.push(String::new());
+            lines.extend(item_lines);
+        }
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// How to reconcile an item with the goal node tracking it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalSync {
+    /// Both sides agree
+    InSync,
+    /// The item was ticked: complete the goal
+    CompleteGoal,
+    /// The goal was completed: tick the item
+    CheckItem,
+}
+
+/// Completion only moves forward: whichever side finished first wins
+pub fn reconcile_goal(item_checked: bool, goal_status: &str) -> GoalSync {
+    match (item_checked, goal_status == "completed") {
+        (true, false) => GoalSync::CompleteGoal,
+        (false, true) => GoalSync::CheckItem,
+        _ => GoalSync::InSync,
+    }
+}
+
+/// Parse a `due="..."` value (YYYY-MM-DD)
+pub fn parse_due_date(due: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(due.trim(), "%Y-%m-%d").ok()
+}
+
+/// Whether a target date has passed with work still open
+pub fn is_overdue(due: NaiveDate, complete: bool, today: NaiveDate) -> bool {
+    !complete && due < today
+}
+
+/// Checkbox progress of all sections in one milestone
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MilestoneProgress {
+    pub name: String,
+    /// Earliest target date among the milestone's sections
+    pub due: Option<NaiveDate>,
+    pub sections: usize,
+    pub checked: usize,
+    pub total: usize,
+}
+
+impl MilestoneProgress {
+    pub fn is_complete(&self) -> bool {
+        self.checked == self.total
+    }
+
+    /// Completion in percent (100 for a milestone without items)
+    pub fn percent(&self) -> usize {
+        (self.checked * 100).checked_div(self.total).unwrap_or(100)
+    }
+
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due
+            .is_some_and(|due| is_overdue(due, self.is_complete(), today))
+    }
+}
+
+/// Progress per milestone, in order of first appearance
+pub fn milestone_progress(sections: &[RoadmapSection]) -> Vec<MilestoneProgress> {
+    let mut progress: Vec<MilestoneProgress> = Vec::new();
+
+    for section in sections {
+        let Some(name) = section.milestone.as_deref() else {
+            continue;
+        };
+        let index = match progress.iter().position(|p| p.name == name) {
+            Some(index) => index,
+            None => {
+                progress.push(MilestoneProgress {
+                    name: name.to_string(),
+                    due: None,
+                    sections: 0,
+                    checked: 0,
+                    total: 0,
+                });
+                progress.len() - 1
+            }
+        };
+
+        let entry = &mut progress[index];
+        entry.sections += 1;
+        entry.checked += section.checked_count();
+        entry.total += section.items.len();
+        entry.due = match (entry.due, section.due_date()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    progress
+}
+
+/// How one checkbox is reconciled between ROADMAP.md and its issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckboxSync {
+    /// Both sides already agree
+    InSync,
+    /// ROADMAP.md changed since the last sync, or the issue lacks the item
+    PushLocal,
+    /// The issue changed since the last sync
+    PullRemote,
+    /// The sides disagree and there is no sync base to tell which one changed
+    Conflict,
+}
+
+/// Three-way merge of a checkbox
+///
+/// `base` is the state both sides agreed on at the last sync. With a base, a
+/// disagreement is always attributable to one side: checkboxes only have two
+/// states, so if both sides changed they changed to the same value.
+pub fn reconcile_checkbox(local: bool, remote: Option<bool>, base: Option<bool>) -> CheckboxSync {
+    match (remote, base) {
+        (None, _) => CheckboxSync::PushLocal,
+        (Some(remote), _) if remote == local => CheckboxSync::InSync,
+        (Some(_), Some(base)) if local == base => CheckboxSync::PullRemote,
+        (Some(_), Some(_)) => CheckboxSync::PushLocal,
+        (Some(_), None) => CheckboxSync::Conflict,
+    }
+}
+
+/// Reconciliation decision for one checkbox item
+#[derive(Debug, Clone)]
+pub struct CheckboxReconciliation {
+    pub change_id: String,
+    pub text: String,
+    pub local: bool,
+    /// State in the issue body (None if the issue doesn't list the item)
+    pub remote: Option<bool>,
+    pub action: CheckboxSync,
+}
+
+impl CheckboxReconciliation {
+    /// State both sides hold once the decision is applied (None for conflicts)
+    pub fn resolved(&self) -> Option<bool> {
+        match self.action {
+            CheckboxSync::InSync | CheckboxSync::PushLocal => Some(self.local),
+            CheckboxSync::PullRemote => self.remote,
+            CheckboxSync::Conflict => None,
+        }
+    }
+}
+
+/// Result of reconciling a section with its issue body
+#[derive(Debug, Clone)]
+pub struct SectionSync {
+    /// Section as it should be written to ROADMAP.md
+    pub local: RoadmapSection,
+    /// Section as it should be published in the issue body (conflicted
+    /// items keep the issue's state so neither side is overwritten)
+    pub remote: RoadmapSection,
+    pub items: Vec<CheckboxReconciliation>,
+}
+
+impl SectionSync {
+    /// Items ticked or unticked in ROADMAP.md because of the issue
+    pub fn pulled(&self) -> impl Iterator<Item = &CheckboxReconciliation> {
+        self.items
+            .iter()
+            .filter(|i| i.action == CheckboxSync::PullRemote)
+    }
+
+    /// Items that need a human decision
+    pub fn conflicts(&self) -> impl Iterator<Item = &CheckboxReconciliation> {
+        self.items
+            .iter()
+            .filter(|i| i.action == CheckboxSync::Conflict)
+    }
+}
+
+/// Reconcile a section's checkboxes with the checkboxes in its issue body
+///
+/// `base` returns the state an item (by change_id) had at its last sync.
+pub fn reconcile_section(
+    section: &RoadmapSection,
+    issue_body: &str,
+    base: impl Fn(&str) -> Option<bool>,
+) -> SectionSync {
+    let remote_items: HashMap<TaskKey, bool> = issue_body_tasks(issue_body)
+        .into_iter()
+        .map(|task| (task.key, task.checked))
+        .collect();
+    let keys = section_task_keys(section);
+
+    let mut local = section.clone();
+    let mut remote = section.clone();
+    let mut items = Vec::new();
+
+    for (i, item) in section.items.iter().enumerate() {
+        let remote_checked = remote_items.get(&keys[i]).copied();
+        let action = reconcile_checkbox(item.checked, remote_checked, base(&item.change_id));
+        let decision = CheckboxReconciliation {
+            change_id: item.change_id.clone(),
+            text: item.text.clone(),
+            local: item.checked,
+            remote: remote_checked,
+            action,
+        };
+
+        match decision.resolved() {
+            Some(state) => {
+                local.items[i].checked = state;
+                remote.items[i].checked = state;
+            }
+            None => remote.items[i].checked = remote_checked.unwrap_or(item.checked),
+        }
+        items.push(decision);
+    }
+
+    SectionSync {
+        local,
+        remote,
+        items,
+    }
 }
 
 #[cfg(test)]
@@ -542,21 +1072,137 @@ mod tests {
             Some(42),
             Some("open"),
             Some("2025-01-15T10:30:00Z"),
+            None,
+            None,
         );
 
         assert!(comment.contains("roadmap:section"));
         assert!(comment.contains(r#"id="abc-123""#));
         assert!(comment.contains(r#"issue="42""#));
         assert!(comment.contains(r#"status="open""#));
+        assert!(!comment.contains("milestone"));
+    }
+
+    #[test]
+    fn test_section_metadata_milestone_round_trip() {
+        let comment = generate_section_metadata(
+            "abc-123",
+            None,
+            None,
+            None,
+            Some("v1.0 \"Oak\""),
+            Some("2025-03-31"),
+        );
+        let meta = parse_section_metadata(&comment);
+
+        assert_eq!(meta.id, Some("abc-123".to_string()));
+        assert_eq!(meta.milestone, Some("v1.0 'Oak'".to_string()));
+        assert_eq!(meta.due, Some("2025-03-31".to_string()));
+    }
+
+    #[test]
+    fn test_parse_roadmap_keeps_milestones() {
+        let content = r#"## Q1
+
+### Sync
+<!-- roadmap:section id="s1" milestone="v1.0" due="2025-03-31" -->
+- [x] Checkboxes
+- [ ] Milestones
+
+### Search
+<!-- roadmap:section id="s2" milestone="v1.0" due="not a date" -->
+- [ ] Index
+"#;
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+
+        let parsed = parse_roadmap(file.path()).unwrap();
+        let sync = &parsed.sections[1];
+        assert_eq!(sync.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(sync.due_date(), NaiveDate::from_ymd_opt(2025, 3, 31));
+        assert_eq!(parsed.sections[2].due_date(), None);
+
+        // Rewriting keeps the milestone attributes
+        let written = write_roadmap_with_metadata(file.path(), &parsed.sections, content).unwrap();
+        assert!(written.contains(r#"milestone="v1.0" due="2025-03-31""#));
+    }
+
+    #[test]
+    fn test_milestone_progress() {
+        let mut first = section_with_items(&[("a", "A", true), ("b", "B", false)]);
+        first.milestone = Some("v1.0".to_string());
+        first.due = Some("2025-03-31".to_string());
+        let mut second = section_with_items(&[("c", "C", true)]);
+        second.milestone = Some("v1.0".to_string());
+        second.due = Some("2025-02-28".to_string());
+        let mut third = section_with_items(&[("d", "D", true)]);
+        third.milestone = Some("v2.0".to_string());
+        let unplanned = section_with_items(&[("e", "E", false)]);
+
+        let progress = milestone_progress(&[first, second, unplanned, third]);
+        assert_eq!(progress.len(), 2);
+
+        let v1 = &progress[0];
+        assert_eq!(v1.name, "v1.0");
+        assert_eq!((v1.sections, v1.checked, v1.total), (2, 2, 3));
+        assert_eq!(v1.percent(), 66);
+        assert_eq!(v1.due, NaiveDate::from_ymd_opt(2025, 2, 28));
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert!(v1.is_overdue(today));
+        // Done or undated milestones are never overdue
+        assert!(!progress[1].is_overdue(today));
+        assert!(progress[1].is_complete());
     }
 
     #[test]
     fn test_generate_item_metadata() {
-        let comment = generate_item_metadata("item-123", Some("outcome-456"));
+        let comment = generate_item_metadata("item-123", Some("outcome-456"), None);
 
         assert!(comment.contains("roadmap:item"));
         assert!(comment.contains(r#"id="item-123""#));
         assert!(comment.contains(r#"outcome_change_id="outcome-456""#));
+        assert!(!comment.contains("goal_change_id"));
+    }
+
+    #[test]
+    fn test_append_item() {
+        let content = "# Roadmap\n\n### Beta\nShip the beta.\n\n- [x] Docs\n  <!-- roadmap:item id=\"a\" outcome_change_id=\"\" -->\n\n### Later\n- [ ] Plugins\n";
+
+        let updated = append_item(content, "beta", "Offline mode", "b", Some("goal-1"));
+        assert_eq!(
+            updated,
+            "# Roadmap\n\n### Beta\nShip the beta.\n\n- [x] Docs\n  <!-- roadmap:item id=\"a\" outcome_change_id=\"\" -->\n- [ ] Offline mode\n  <!-- roadmap:item id=\"b\" outcome_change_id=\"\" goal_change_id=\"goal-1\" -->\n\n### Later\n- [ ] Plugins\n"
+        );
+
+        // Description only: items start after a blank line
+        let updated = append_item("### Beta\nShip it.\n", "Beta", "Offline mode", "b", None);
+        assert!(updated.starts_with("### Beta\nShip it.\n\n- [ ] Offline mode\n"));
+
+        // Missing sections are added at the end
+        let updated = append_item("# Roadmap\n\n", "In Progress", "Offline mode", "b", None);
+        assert!(updated.starts_with("# Roadmap\n\n### In Progress\n\n- [ ] Offline mode\n"));
+    }
+
+    #[test]
+    fn test_reconcile_goal() {
+        assert_eq!(reconcile_goal(true, "pending"), GoalSync::CompleteGoal);
+        assert_eq!(reconcile_goal(false, "completed"), GoalSync::CheckItem);
+        assert_eq!(reconcile_goal(true, "completed"), GoalSync::InSync);
+        assert_eq!(reconcile_goal(false, "active"), GoalSync::InSync);
+    }
+
+    #[test]
+    fn test_item_metadata_goal_round_trip() {
+        let comment = generate_item_metadata("item-1", None, Some("goal-9"));
+        let inner = comment
+            .trim_start_matches("<!-- roadmap:item ")
+            .trim_end_matches(" -->");
+        let meta = parse_item_metadata(inner);
+
+        assert_eq!(meta.id.as_deref(), Some("item-1"));
+        assert_eq!(meta.outcome_change_id, None);
+        assert_eq!(meta.goal_change_id.as_deref(), Some("goal-9"));
     }
 
     #[test]
@@ -644,6 +1290,9 @@ Description here.
                     text: "First task".to_string(),
                     checked: false,
                     outcome_change_id: None,
+                    goal_change_id: None,
+                    depth: 0,
+                    parent_change_id: None,
                     line_number: 1,
                 },
                 RoadmapCheckItem {
@@ -651,11 +1300,16 @@ Description here.
                     text: "Second task".to_string(),
                     checked: true,
                     outcome_change_id: Some("outcome-123".to_string()),
+                    goal_change_id: None,
+                    depth: 0,
+                    parent_change_id: None,
                     line_number: 2,
                 },
             ],
             github_issue_number: None,
             github_issue_state: None,
+            milestone: None,
+            due: None,
             line_start: 1,
             line_end: 5,
             content_hash: "hash".to_string(),
@@ -689,4 +1343,253 @@ More text.
         assert_eq!(items[1], ("Checked task".to_string(), true));
         assert_eq!(items[2], ("Also checked (uppercase)".to_string(), true));
     }
+
+    #[test]
+    fn test_set_issue_body_checkbox() {
+        let body = "Intro\r\n\n## Tasks\n\n- [ ] First\n- [x] Second\n";
+        let section = section_with_items(&[("a", "First", false), ("b", "Second", true)]);
+        let keys = section_task_keys(&section);
+
+        let ticked = set_issue_body_checkbox(body, &keys[0], true).unwrap();
+        assert_eq!(ticked, "Intro\r\n\n## Tasks\n\n- [x] First\n- [x] Second\n");
+
+        let unticked = set_issue_body_checkbox(body, &keys[1], false).unwrap();
+        assert!(unticked.contains("- [ ] Second"));
+
+        let missing = section_with_items(&[("c", "Missing", false)]);
+        let missing_key = &section_task_keys(&missing)[0];
+        assert_eq!(set_issue_body_checkbox(body, missing_key, true), None);
+    }
+
+    #[test]
+    fn test_set_issue_body_checkbox_nested_duplicates() {
+        let body = "- [ ] Parser\n  - [ ] Tests\n- [ ] Writer\n  - [ ] Tests\n";
+        let section = nested_duplicates_section();
+        let keys = section_task_keys(&section);
+
+        let ticked = set_issue_body_checkbox(body, &keys[3], true).unwrap();
+        assert_eq!(
+            ticked,
+            "- [ ] Parser\n  - [ ] Tests\n- [ ] Writer\n  - [x] Tests\n"
+        );
+    }
+
+    fn section_with_items(items: &[(&str, &str, bool)]) -> RoadmapSection {
+        RoadmapSection {
+            change_id: "section".to_string(),
+            title: "Feature".to_string(),
+            level: 3,
+            description: None,
+            items: items
+                .iter()
+                .enumerate()
+                .map(|(i, (id, text, checked))| RoadmapCheckItem {
+                    change_id: id.to_string(),
+                    text: text.to_string(),
+                    checked: *checked,
+                    outcome_change_id: None,
+                    goal_change_id: None,
+                    depth: 0,
+                    parent_change_id: None,
+                    line_number: i + 1,
+                })
+                .collect(),
+            github_issue_number: Some(1),
+            github_issue_state: None,
+            milestone: None,
+            due: None,
+            line_start: 1,
+            line_end: 5,
+            content_hash: "hash".to_string(),
+        }
+    }
+
+    /// "Parser" and "Writer", each with a "Tests" sub-item
+    fn nested_duplicates_section() -> RoadmapSection {
+        let mut section = section_with_items(&[
+            ("parser", "Parser", false),
+            ("parser-tests", "Tests", false),
+            ("writer", "Writer", false),
+            ("writer-tests", "Tests", false),
+        ]);
+        for (child, parent) in [(1, "parser"), (3, "writer")] {
+            section.items[child].depth = 1;
+            section.items[child].parent_change_id = Some(parent.to_string());
+        }
+        section
+    }
+
+    #[test]
+    fn test_reconcile_checkbox() {
+        use CheckboxSync::*;
+        // Agreement needs nothing
+        assert_eq!(reconcile_checkbox(true, Some(true), None), InSync);
+        assert_eq!(reconcile_checkbox(false, Some(false), Some(true)), InSync);
+        // Only the issue changed since the base
+        assert_eq!(
+            reconcile_checkbox(false, Some(true), Some(false)),
+            PullRemote
+        );
+        assert_eq!(
+            reconcile_checkbox(true, Some(false), Some(true)),
+            PullRemote
+        );
+        // Only ROADMAP.md changed since the base
+        assert_eq!(
+            reconcile_checkbox(true, Some(false), Some(false)),
+            PushLocal
+        );
+        // Item missing from the issue
+        assert_eq!(reconcile_checkbox(true, None, None), PushLocal);
+        // Never synced and they disagree
+        assert_eq!(reconcile_checkbox(true, Some(false), None), Conflict);
+    }
+
+    #[test]
+    fn test_reconcile_section() {
+        let section = section_with_items(&[
+            ("a", "Ticked remotely", false),
+            ("b", "Ticked locally", true),
+            ("c", "Never synced", true),
+            ("d", "Not in issue", false),
+        ]);
+        let body = "## Tasks\n\n- [x] Ticked remotely\n- [ ] Ticked locally\n- [ ] Never synced\n";
+        let sync = reconcile_section(&section, body, |id| match id {
+            "a" | "b" => Some(false),
+            _ => None,
+        });
+
+        let actions: Vec<CheckboxSync> = sync.items.iter().map(|i| i.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                CheckboxSync::PullRemote,
+                CheckboxSync::PushLocal,
+                CheckboxSync::Conflict,
+                CheckboxSync::PushLocal
+            ]
+        );
+
+        let local: Vec<bool> = sync.local.items.iter().map(|i| i.checked).collect();
+        assert_eq!(local, vec![true, true, true, false]);
+        // The conflicted item keeps the issue's state in the published body
+        let remote: Vec<bool> = sync.remote.items.iter().map(|i| i.checked).collect();
+        assert_eq!(remote, vec![true, true, false, false]);
+
+        assert_eq!(sync.pulled().count(), 1);
+        assert_eq!(sync.conflicts().next().unwrap().change_id, "c");
+    }
+
+    #[test]
+    fn test_reconcile_section_nested_duplicates() {
+        let section = nested_duplicates_section();
+        // Only Writer's "Tests" was ticked on the issue
+        let body = "## Tasks\n\n- [ ] Parser\n  - [ ] Tests\n- [ ] Writer\n  - [x] Tests\n";
+        let sync = reconcile_section(&section, body, |_| Some(false));
+
+        let actions: Vec<CheckboxSync> = sync.items.iter().map(|i| i.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                CheckboxSync::InSync,
+                CheckboxSync::InSync,
+                CheckboxSync::InSync,
+                CheckboxSync::PullRemote
+            ]
+        );
+        let pulled: Vec<&str> = sync.pulled().map(|i| i.change_id.as_str()).collect();
+        assert_eq!(pulled, vec!["writer-tests"]);
+    }
+
+    #[test]
+    fn test_write_roadmap_updates_checkboxes() {
+        let content = "### Feature\n- [ ] First\n- [X] Second\n";
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+
+        let mut parsed = parse_roadmap(file.path()).unwrap();
+        parsed.sections[0].items[0].checked = true;
+        parsed.sections[0].items[1].checked = false;
+
+        let written = write_roadmap_with_metadata(file.path(), &parsed.sections, content).unwrap();
+        assert!(written.contains("- [x] First"));
+        assert!(written.contains("- [ ] Second"));
+
+        let reparsed = parse_roadmap(file.path()).unwrap();
+        assert!(reparsed.sections[0].items[0].checked);
+        assert!(!reparsed.sections[0].items[1].checked);
+        assert_eq!(
+            reparsed.sections[0].items[0].change_id,
+            parsed.sections[0].items[0].change_id
+        );
+    }
+
+    #[test]
+    fn test_roadmap_key() {
+        assert_eq!(roadmap_key("ROADMAP.md"), "ROADMAP.md");
+        assert_eq!(roadmap_key("./ROADMAP.md"), "ROADMAP.md");
+        assert_eq!(
+            roadmap_key(Path::new("packages/api/ROADMAP.md")),
+            "packages/api/ROADMAP.md"
+        );
+    }
+
+    #[test]
+    fn test_parse_roadmap_nested_items() {
+        let content = "### Feature\n- [ ] Parser\n  - [x] Tests\n    - [ ] Fixtures\n  - [ ] Docs\n- [ ] Writer\n  - [ ] Tests\n";
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+
+        let parsed = parse_roadmap(file.path()).unwrap();
+        let items = &parsed.sections[0].items;
+        let depths: Vec<usize> = items.iter().map(|i| i.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 1, 0, 1]);
+        assert_eq!(items[0].parent_change_id, None);
+        assert_eq!(
+            items[1].parent_change_id.as_ref(),
+            Some(&items[0].change_id)
+        );
+        assert_eq!(
+            items[2].parent_change_id.as_ref(),
+            Some(&items[1].change_id)
+        );
+        assert_eq!(
+            items[3].parent_change_id.as_ref(),
+            Some(&items[0].change_id)
+        );
+        assert_eq!(
+            items[5].parent_change_id.as_ref(),
+            Some(&items[4].change_id)
+        );
+
+        let body = generate_issue_body(&parsed.sections[0]);
+        assert!(body.contains("- [ ] Parser\n  - [x] Tests\n    - [ ] Fixtures\n"));
+    }
+
+    #[test]
+    fn test_write_roadmap_nested_items() {
+        let content = "### Feature\n- [ ] Parser\n  - [ ] Tests\n- [ ] Writer\n  - [ ] Tests\n";
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+
+        let mut parsed = parse_roadmap(file.path()).unwrap();
+        parsed.sections[0].items[3].checked = true;
+        let written = write_roadmap_with_metadata(file.path(), &parsed.sections, content).unwrap();
+        assert!(written.contains("  - [ ] Tests\n    <!-- roadmap:item"));
+        assert!(written.contains("  - [x] Tests\n    <!-- roadmap:item"));
+
+        // Same text under two parents keeps its own id through a rewrite
+        let reparsed = parse_roadmap(file.path()).unwrap();
+        let ids = |p: &ParsedRoadmap| -> Vec<String> {
+            p.sections[0]
+                .items
+                .iter()
+                .map(|i| i.change_id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&reparsed), ids(&parsed));
+        assert!(!reparsed.sections[0].items[1].checked);
+        assert!(reparsed.sections[0].items[3].checked);
+        assert_eq!(reparsed.sections[0].items[3].depth, 1);
+    }
 }
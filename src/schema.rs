@@ -106,6 +106,11 @@ diesel::table! {
         created_at -> Text,
         updated_at -> Text,
         last_synced_at -> Nullable<Text>,
+        synced_checkbox_state -> Nullable<Text>,
+        milestone -> Nullable<Text>,
+        due_date -> Nullable<Text>,
+        goal_change_id -> Nullable<Text>,
+        roadmap_path -> Nullable<Text>,
     }
 }
 
@@ -131,6 +136,19 @@ diesel::table! {
         resolution -> Nullable<Text>,
         detected_at -> Text,
         resolved_at -> Nullable<Text>,
+        resolved_value -> Nullable<Text>,
+        resolved_by -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    roadmap_item_history (id) {
+        id -> Integer,
+        item_change_id -> Text,
+        field -> Text,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+        changed_at -> Text,
     }
 }
 
@@ -150,5 +168,61 @@ diesel::table! {
         created_at -> Text,
         updated_at -> Text,
         cached_at -> Text,
+        etag -> Nullable<Text>,
+        milestone -> Nullable<Text>,
+    }
+}
+
+// ============================================================================
+// Operation Log - Per-mutation history for undo/redo
+// ============================================================================
+
+diesel::table! {
+    operation_log (id) {
+        id -> Integer,
+        operation -> Text,
+        target_change_id -> Nullable<Text>,
+        before_json -> Nullable<Text>,
+        after_json -> Nullable<Text>,
+        actor -> Text,
+        session -> Nullable<Text>,
+        state -> Text,
+        created_at -> Text,
+        undone_at -> Nullable<Text>,
+    }
+}
+
+// ============================================================================
+// Node Aliases - change_ids of nodes folded in by `deciduous merge`
+// ============================================================================
+
+diesel::table! {
+    node_aliases (alias_change_id) {
+        alias_change_id -> Text,
+        change_id -> Text,
+        created_at -> Text,
+    }
+}
+
+// ============================================================================
+// Node Files - Index of metadata_json.files for reverse lookup
+// ============================================================================
+
+diesel::table! {
+    node_files (node_id, path) {
+        node_id -> Integer,
+        path -> Text,
+    }
+}
+
+// ============================================================================
+// Node Tags - Free-form labels on nodes (`deciduous tag`)
+// ============================================================================
+
+diesel::table! {
+    node_tags (node_id, tag) {
+        node_id -> Integer,
+        tag -> Text,
+        created_at -> Text,
     }
 }
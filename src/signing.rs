@@ -0,0 +1,204 @@
+//! SSH signatures for graph patches
+//!
+//! Signing and verification shell out to `ssh-keygen -Y`, the mechanism git
+//! uses for SSH commit signing. Any SSH key works (ed25519 included), and
+//! trusted signers are listed in an OpenSSH allowed_signers file:
+//!
+//! ```text
+//! alice@example.com ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA...
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Namespace passed to `ssh-keygen -Y` so patch signatures can't be replayed
+/// as signatures for anything else (commits, files, ...)
+pub const SIGNATURE_NAMESPACE: &str = "deciduous-patch";
+
+/// Run ssh-keygen with `input` on stdin, returning stdout
+fn ssh_keygen(args: &[&str], input: &[u8]) -> Result<String, String> {
+    let mut child = Command::new("ssh-keygen")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run ssh-keygen: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .map_err(|e| format!("Failed to write to ssh-keygen: {}", e))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run ssh-keygen: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Sign `payload` with an SSH private key, returning the armored signature
+pub fn sign(payload: &[u8], key: &Path) -> Result<String, String> {
+    let key = expand_home(key);
+    let key = key.to_string_lossy();
+    ssh_keygen(
+        &["-Y", "sign", "-q", "-f", &key, "-n", SIGNATURE_NAMESPACE],
+        payload,
+    )
+    .map(|s| s.trim().to_string())
+}
+
+/// Check that `signature` over `payload` was made by `signer` according to
+/// an allowed_signers file
+pub fn verify(
+    payload: &[u8],
+    signature: &str,
+    signer: &str,
+    allowed_signers: &Path,
+) -> Result<(), String> {
+    let allowed_signers = expand_home(allowed_signers);
+    if !allowed_signers.exists() {
+        return Err(format!(
+            "No allowed signers file at {}",
+            allowed_signers.display()
+        ));
+    }
+
+    let allowed = allowed_signers.to_string_lossy();
+    with_signature_file(signature, |sig| {
+        ssh_keygen(
+            &[
+                "-Y",
+                "verify",
+                "-f",
+                &allowed,
+                "-I",
+                signer,
+                "-n",
+                SIGNATURE_NAMESPACE,
+                "-s",
+                sig,
+            ],
+            payload,
+        )
+    })
+    .map_err(|e| {
+        if e.is_empty() {
+            format!("Signature by {} could not be verified", signer)
+        } else {
+            e
+        }
+    })
+}
+
+/// Check that `signature` matches `payload`, without checking who made it
+pub fn check_signature(payload: &[u8], signature: &str) -> Result<(), String> {
+    with_signature_file(signature, |sig| {
+        ssh_keygen(
+            &[
+                "-Y",
+                "check-novalidate",
+                "-n",
+                SIGNATURE_NAMESPACE,
+                "-s",
+                sig,
+            ],
+            payload,
+        )
+    })
+}
+
+/// Run `f` with the path of a temporary file holding `signature`
+///
+/// ssh-keygen only reads signatures from a file.
+fn with_signature_file(
+    signature: &str,
+    f: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<(), String> {
+    let sig_path = std::env::temp_dir().join(format!("deciduous-{}.sig", uuid::Uuid::new_v4()));
+    std::fs::write(&sig_path, format!("{}\n", signature))
+        .map_err(|e| format!("Failed to write signature: {}", e))?;
+    let result = f(&sig_path.to_string_lossy());
+    let _ = std::fs::remove_file(&sig_path);
+    result.map(|_| ())
+}
+
+/// Signer identity to use when none is configured: git's user.email
+pub fn default_signer() -> Option<String> {
+    Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generate an ed25519 key in `dir`, or None when ssh-keygen is unavailable
+    fn keypair(dir: &Path) -> Option<(PathBuf, String)> {
+        let key = dir.join("id_ed25519");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+            .arg(&key)
+            .status()
+            .ok()?;
+        if !status.success() {
+            return None;
+        }
+        let public = std::fs::read_to_string(key.with_extension("pub")).ok()?;
+        Some((key, public.trim().to_string()))
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let Some((key, public)) = keypair(dir.path()) else {
+            return;
+        };
+        let allowed = dir.path().join("allowed_signers");
+        std::fs::write(&allowed, format!("alice@example.com {}\n", public)).unwrap();
+
+        let signature = sign(b"patch body", &key).unwrap();
+        assert!(signature.starts_with("-----BEGIN SSH SIGNATURE-----"));
+
+        assert!(verify(b"patch body", &signature, "alice@example.com", &allowed).is_ok());
+        // Tampered payload
+        assert!(verify(b"patch body!", &signature, "alice@example.com", &allowed).is_err());
+        // Signer not in allowed_signers
+        assert!(verify(b"patch body", &signature, "mallory@example.com", &allowed).is_err());
+    }
+
+    #[test]
+    fn test_check_signature_ignores_signer() {
+        let dir = tempfile::tempdir().unwrap();
+        let Some((key, _)) = keypair(dir.path()) else {
+            return;
+        };
+        let signature = sign(b"patch body", &key).unwrap();
+
+        assert!(check_signature(b"patch body", &signature).is_ok());
+        assert!(check_signature(b"patch body!", &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_without_allowed_signers() {
+        let err = verify(b"x", "sig", "alice", Path::new("/nonexistent/allowed")).unwrap_err();
+        assert!(err.contains("No allowed signers file"));
+    }
+}
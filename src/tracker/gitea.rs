@@ -0,0 +1,414 @@
+//! Gitea (and Forgejo) issues over the REST API (v1)
+
+use super::http::{query, RestClient};
+use super::{Issue, IssueComment, IssueTracker, Milestone};
+use crate::github::{date_part, CommentAuthor, GitHubError, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Environment variable read for the API token by default
+pub const TOKEN_ENV: &str = "GITEA_TOKEN";
+
+/// Gitea client for one repository
+pub struct GiteaClient {
+    http: RestClient,
+    repo: String,
+    token_env: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaIssue {
+    number: i32,
+    title: String,
+    body: Option<String>,
+    state: String,
+    html_url: String,
+    created_at: String,
+    updated_at: String,
+    #[serde(default)]
+    milestone: Option<GiteaMilestone>,
+}
+
+impl From<GiteaIssue> for Issue {
+    fn from(issue: GiteaIssue) -> Self {
+        Issue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            state: issue.state,
+            html_url: issue.html_url,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            milestone: issue.milestone.map(|m| m.title),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GiteaMilestone {
+    id: i64,
+    title: String,
+    due_on: Option<String>,
+}
+
+impl From<GiteaMilestone> for Milestone {
+    fn from(milestone: GiteaMilestone) -> Self {
+        Milestone {
+            id: milestone.id,
+            title: milestone.title,
+            due_on: milestone.due_on.map(|d| date_part(&d)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GiteaComment {
+    id: i64,
+    body: String,
+    user: GiteaUser,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaLabel {
+    id: i64,
+    name: String,
+}
+
+impl GiteaClient {
+    /// `url` is the instance root (e.g. https://gitea.example.com), `repo`
+    /// is "owner/name"
+    pub fn new(url: &str, repo: &str, token: Option<String>, token_env: &str) -> Self {
+        Self {
+            http: RestClient::new(
+                &format!("{}/api/v1", url.trim_end_matches('/')),
+                token.map(|t| ("Authorization", format!("token {}", t))),
+            ),
+            repo: repo.to_string(),
+            token_env: token_env.to_string(),
+        }
+    }
+
+    /// Path under the repository's API root
+    fn path(&self, rest: &str) -> String {
+        format!("/repos/{}{}", self.repo, rest)
+    }
+
+    fn edit(&self, number: i32, changes: serde_json::Value) -> Result<()> {
+        self.http.send(
+            "PATCH",
+            &self.path(&format!("/issues/{}", number)),
+            &changes,
+        )
+    }
+
+    fn labels(&self) -> Result<Vec<GiteaLabel>> {
+        self.http.get_all(&self.path("/labels"), "limit")
+    }
+}
+
+impl IssueTracker for GiteaClient {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    fn repo_name(&self) -> Option<&str> {
+        Some(&self.repo)
+    }
+
+    fn check_auth(&self) -> Result<bool> {
+        if !self.http.has_auth() {
+            return Ok(false);
+        }
+        match self.http.request("GET", "/user", None) {
+            Ok(_) => Ok(true),
+            Err(GitHubError::RequestFailed { status: 401, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn auth_hint(&self) -> String {
+        format!(
+            "Set {} to a Gitea access token with issue read/write scope",
+            self.token_env
+        )
+    }
+
+    fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<Issue> {
+        // Gitea takes label IDs, not names
+        let label_ids: Vec<i64> = if labels.is_empty() {
+            Vec::new()
+        } else {
+            self.labels()?
+                .into_iter()
+                .filter(|l| labels.contains(&l.name.as_str()))
+                .map(|l| l.id)
+                .collect()
+        };
+        let issue: GiteaIssue = self.http.json(
+            "POST",
+            &self.path("/issues"),
+            Some(&json!({ "title": title, "body": body, "labels": label_ids })),
+        )?;
+        Ok(issue.into())
+    }
+
+    fn get_issue(&self, number: i32) -> Result<Issue> {
+        match self
+            .http
+            .json::<GiteaIssue>("GET", &self.path(&format!("/issues/{}", number)), None)
+        {
+            Ok(issue) => Ok(issue.into()),
+            Err(GitHubError::RequestFailed { status: 404, .. }) => {
+                Err(GitHubError::IssueNotFound { number })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn update_issue_body(&self, number: i32, body: &str) -> Result<()> {
+        self.edit(number, json!({ "body": body }))
+    }
+
+    fn update_issue_title(&self, number: i32, title: &str) -> Result<()> {
+        self.edit(number, json!({ "title": title }))
+    }
+
+    fn close_issue(&self, number: i32) -> Result<()> {
+        self.edit(number, json!({ "state": "closed" }))
+    }
+
+    fn reopen_issue(&self, number: i32) -> Result<()> {
+        self.edit(number, json!({ "state": "open" }))
+    }
+
+    fn get_issue_comments(&self, number: i32) -> Result<Vec<IssueComment>> {
+        let comments: Vec<GiteaComment> = self.http.json(
+            "GET",
+            &self.path(&format!("/issues/{}/comments", number)),
+            None,
+        )?;
+        Ok(comments
+            .into_iter()
+            .map(|c| IssueComment {
+                id: c.id,
+                body: c.body,
+                author: CommentAuthor {
+                    login: c.user.login,
+                },
+                created_at: c.created_at,
+                created_at_alt: None,
+            })
+            .collect())
+    }
+
+    fn add_comment(&self, number: i32, body: &str) -> Result<()> {
+        self.http.send(
+            "POST",
+            &self.path(&format!("/issues/{}/comments", number)),
+            &json!({ "body": body }),
+        )
+    }
+
+    fn list_issues_with_label(&self, label: &str) -> Result<Vec<Issue>> {
+        let issues: Vec<GiteaIssue> = self.http.get_all(
+            &self.path(&format!(
+                "/issues?{}",
+                query(&[("labels", label), ("state", "all"), ("type", "issues")])
+            )),
+            "limit",
+        )?;
+        Ok(issues.into_iter().map(Issue::from).collect())
+    }
+
+    fn find_issue_by_title(&self, title: &str) -> Result<Option<Issue>> {
+        let issues: Vec<GiteaIssue> = self.http.json(
+            "GET",
+            &self.path(&format!(
+                "/issues?{}",
+                query(&[("q", title), ("state", "all"), ("type", "issues")])
+            )),
+            None,
+        )?;
+        // Search is fuzzy; only an exact title counts
+        Ok(issues
+            .into_iter()
+            .find(|i| i.title.to_lowercase() == title.to_lowercase())
+            .map(Issue::from))
+    }
+
+    fn label_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.labels()?.iter().any(|l| l.name == name))
+    }
+
+    fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()> {
+        self.http.send(
+            "POST",
+            &self.path("/labels"),
+            &json!({ "name": name, "description": description, "color": format!("#{}", color) }),
+        )
+    }
+
+    fn ensure_milestone(&self, title: &str, due_on: Option<&str>) -> Result<Milestone> {
+        let existing: Vec<GiteaMilestone> = self.http.get_all(
+            &self.path(&format!(
+                "/milestones?{}",
+                query(&[("state", "all"), ("name", title)])
+            )),
+            "limit",
+        )?;
+        let due = due_on.map(|d| format!("{}T00:00:00Z", d));
+
+        match existing.into_iter().find(|m| m.title == title) {
+            Some(milestone) => {
+                let milestone = Milestone::from(milestone);
+                if due_on.is_none() || milestone.due_on.as_deref() == due_on {
+                    return Ok(milestone);
+                }
+                let updated: GiteaMilestone = self.http.json(
+                    "PATCH",
+                    &self.path(&format!("/milestones/{}", milestone.id)),
+                    Some(&json!({ "due_on": due })),
+                )?;
+                Ok(updated.into())
+            }
+            None => {
+                let created: GiteaMilestone = self.http.json(
+                    "POST",
+                    &self.path("/milestones"),
+                    Some(&json!({ "title": title, "due_on": due })),
+                )?;
+                Ok(created.into())
+            }
+        }
+    }
+
+    fn set_issue_milestone(&self, number: i32, milestone: &Milestone) -> Result<()> {
+        self.edit(number, json!({ "milestone": milestone.id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracker::mock::{MockResponse, MockServer};
+
+    fn issue_json(number: i32, state: &str) -> serde_json::Value {
+        json!({
+            "number": number,
+            "title": "Add sync",
+            "body": "Body",
+            "state": state,
+            "html_url": format!("https://gitea.example.com/owner/app/issues/{}", number),
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z"
+        })
+    }
+
+    fn client(server: &MockServer) -> GiteaClient {
+        GiteaClient::new(
+            &server.url,
+            "owner/app",
+            Some("secret".to_string()),
+            TOKEN_ENV,
+        )
+    }
+
+    #[test]
+    fn test_create_issue_resolves_label_ids() {
+        let server = MockServer::start(|req| match (req.method.as_str(), req.url.as_str()) {
+            ("GET", url) if url.starts_with("/api/v1/repos/owner/app/labels") => {
+                MockResponse::json(
+                    200,
+                    json!([{"id": 4, "name": "roadmap"}, {"id": 5, "name": "bug"}]),
+                )
+            }
+            _ => MockResponse::json(201, issue_json(12, "open")),
+        });
+
+        let issue = client(&server)
+            .create_issue("Add sync", "Body", &["roadmap"])
+            .unwrap();
+        assert_eq!(issue.number, 12);
+
+        let requests = server.requests();
+        let create = requests.last().unwrap();
+        assert_eq!(create.line(), "POST /api/v1/repos/owner/app/issues");
+        assert_eq!(create.header("Authorization"), Some("token secret"));
+        assert_eq!(create.json()["labels"], json!([4]));
+    }
+
+    #[test]
+    fn test_close_and_reopen() {
+        let server = MockServer::start(|_| MockResponse::json(201, issue_json(3, "closed")));
+        let gitea = client(&server);
+        gitea.close_issue(3).unwrap();
+        gitea.reopen_issue(3).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line(), "PATCH /api/v1/repos/owner/app/issues/3");
+        assert_eq!(requests[0].json()["state"], "closed");
+        assert_eq!(requests[1].json()["state"], "open");
+    }
+
+    #[test]
+    fn test_get_issue_not_found() {
+        let server =
+            MockServer::start(|_| MockResponse::json(404, json!({"message": "not found"})));
+        assert!(matches!(
+            client(&server).get_issue(42),
+            Err(GitHubError::IssueNotFound { number: 42 })
+        ));
+    }
+
+    #[test]
+    fn test_comments_and_labels() {
+        let server = MockServer::start(|req| {
+            if req.url.contains("/comments") {
+                MockResponse::json(
+                    200,
+                    json!([{"id": 9, "body": "Done", "user": {"login": "bob"},
+                            "created_at": "2024-01-03T00:00:00Z"}]),
+                )
+            } else {
+                MockResponse::json(200, json!([{"id": 4, "name": "roadmap"}]))
+            }
+        });
+        let gitea = client(&server);
+
+        let comments = gitea.get_issue_comments(3).unwrap();
+        assert_eq!(comments[0].author.login, "bob");
+        assert!(gitea.label_exists("roadmap").unwrap());
+        assert!(!gitea.label_exists("bug").unwrap());
+    }
+
+    #[test]
+    fn test_ensure_milestone_creates_missing() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => MockResponse::json(200, json!([])),
+            _ => MockResponse::json(
+                201,
+                json!({"id": 3, "title": "Beta", "due_on": "2024-06-30T00:00:00Z"}),
+            ),
+        });
+
+        let milestone = client(&server)
+            .ensure_milestone("Beta", Some("2024-06-30"))
+            .unwrap();
+        assert_eq!(milestone.id, 3);
+        assert_eq!(milestone.due_on.as_deref(), Some("2024-06-30"));
+
+        let requests = server.requests();
+        assert!(requests[0].url.contains("state=all&name=Beta"));
+        assert_eq!(
+            requests[1].line(),
+            "POST /api/v1/repos/owner/app/milestones"
+        );
+        assert_eq!(requests[1].json()["due_on"], "2024-06-30T00:00:00Z");
+    }
+}
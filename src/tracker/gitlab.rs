@@ -0,0 +1,447 @@
+//! GitLab issues over the REST API (v4)
+
+use super::http::{query, RestClient};
+use super::{Issue, IssueComment, IssueTracker, Milestone};
+use crate::github::{CommentAuthor, GitHubError, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Environment variable read for the API token by default
+pub const TOKEN_ENV: &str = "GITLAB_TOKEN";
+
+/// GitLab client for one project
+pub struct GitLabClient {
+    http: RestClient,
+    project: String,
+    token_env: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabIssue {
+    iid: i32,
+    title: String,
+    description: Option<String>,
+    state: String, // "opened" or "closed"
+    web_url: String,
+    created_at: String,
+    updated_at: String,
+    #[serde(default)]
+    milestone: Option<GitLabMilestone>,
+}
+
+impl From<GitLabIssue> for Issue {
+    fn from(issue: GitLabIssue) -> Self {
+        Issue {
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            state: if issue.state == "opened" {
+                "open".to_string()
+            } else {
+                issue.state
+            },
+            html_url: issue.web_url,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            milestone: issue.milestone.map(|m| m.title),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabMilestone {
+    id: i64,
+    title: String,
+    due_date: Option<String>,
+}
+
+impl From<GitLabMilestone> for Milestone {
+    fn from(milestone: GitLabMilestone) -> Self {
+        Milestone {
+            id: milestone.id,
+            title: milestone.title,
+            due_on: milestone.due_date,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabNote {
+    id: i64,
+    body: String,
+    author: GitLabUser,
+    created_at: String,
+    /// System notes record events ("closed", "added label"), not comments
+    #[serde(default)]
+    system: bool,
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabLabel {
+    name: String,
+}
+
+impl GitLabClient {
+    /// `url` is the instance root (e.g. https://gitlab.com), `project` its
+    /// full path ("group/subgroup/project")
+    pub fn new(url: &str, project: &str, token: Option<String>, token_env: &str) -> Self {
+        Self {
+            http: RestClient::new(
+                &format!("{}/api/v4", url.trim_end_matches('/')),
+                token.map(|t| ("PRIVATE-TOKEN", t)),
+            ),
+            project: project.to_string(),
+            token_env: token_env.to_string(),
+        }
+    }
+
+    /// Path under the project's API root
+    fn path(&self, rest: &str) -> String {
+        format!("/projects/{}{}", self.project.replace('/', "%2F"), rest)
+    }
+
+    fn edit(&self, number: i32, changes: serde_json::Value) -> Result<()> {
+        self.http
+            .send("PUT", &self.path(&format!("/issues/{}", number)), &changes)
+    }
+}
+
+impl IssueTracker for GitLabClient {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn repo_name(&self) -> Option<&str> {
+        Some(&self.project)
+    }
+
+    fn check_auth(&self) -> Result<bool> {
+        if !self.http.has_auth() {
+            return Ok(false);
+        }
+        match self.http.request("GET", "/user", None) {
+            Ok(_) => Ok(true),
+            Err(GitHubError::RequestFailed { status: 401, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn auth_hint(&self) -> String {
+        format!(
+            "Set {} to a GitLab access token with the api scope",
+            self.token_env
+        )
+    }
+
+    fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<Issue> {
+        let issue: GitLabIssue = self.http.json(
+            "POST",
+            &self.path("/issues"),
+            Some(&json!({ "title": title, "description": body, "labels": labels.join(",") })),
+        )?;
+        Ok(issue.into())
+    }
+
+    fn get_issue(&self, number: i32) -> Result<Issue> {
+        match self
+            .http
+            .json::<GitLabIssue>("GET", &self.path(&format!("/issues/{}", number)), None)
+        {
+            Ok(issue) => Ok(issue.into()),
+            Err(GitHubError::RequestFailed { status: 404, .. }) => {
+                Err(GitHubError::IssueNotFound { number })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn update_issue_body(&self, number: i32, body: &str) -> Result<()> {
+        self.edit(number, json!({ "description": body }))
+    }
+
+    fn update_issue_title(&self, number: i32, title: &str) -> Result<()> {
+        self.edit(number, json!({ "title": title }))
+    }
+
+    fn close_issue(&self, number: i32) -> Result<()> {
+        self.edit(number, json!({ "state_event": "close" }))
+    }
+
+    fn reopen_issue(&self, number: i32) -> Result<()> {
+        self.edit(number, json!({ "state_event": "reopen" }))
+    }
+
+    fn get_issue_comments(&self, number: i32) -> Result<Vec<IssueComment>> {
+        let notes: Vec<GitLabNote> = self.http.get_all(
+            &self.path(&format!("/issues/{}/notes?sort=asc", number)),
+            "per_page",
+        )?;
+        Ok(notes
+            .into_iter()
+            .filter(|n| !n.system)
+            .map(|n| IssueComment {
+                id: n.id,
+                body: n.body,
+                author: CommentAuthor {
+                    login: n.author.username,
+                },
+                created_at: n.created_at,
+                created_at_alt: None,
+            })
+            .collect())
+    }
+
+    fn add_comment(&self, number: i32, body: &str) -> Result<()> {
+        self.http.send(
+            "POST",
+            &self.path(&format!("/issues/{}/notes", number)),
+            &json!({ "body": body }),
+        )
+    }
+
+    fn list_issues_with_label(&self, label: &str) -> Result<Vec<Issue>> {
+        let issues: Vec<GitLabIssue> = self.http.get_all(
+            &self.path(&format!(
+                "/issues?{}",
+                query(&[("labels", label), ("state", "all")])
+            )),
+            "per_page",
+        )?;
+        Ok(issues.into_iter().map(Issue::from).collect())
+    }
+
+    fn find_issue_by_title(&self, title: &str) -> Result<Option<Issue>> {
+        let issues: Vec<GitLabIssue> = self.http.json(
+            "GET",
+            &self.path(&format!(
+                "/issues?{}",
+                query(&[("search", title), ("in", "title"), ("state", "all")])
+            )),
+            None,
+        )?;
+        // Search is fuzzy; only an exact title counts
+        Ok(issues
+            .into_iter()
+            .find(|i| i.title.to_lowercase() == title.to_lowercase())
+            .map(Issue::from))
+    }
+
+    fn label_exists(&self, name: &str) -> Result<bool> {
+        let labels: Vec<GitLabLabel> = self.http.json(
+            "GET",
+            &self.path(&format!("/labels?{}", query(&[("search", name)]))),
+            None,
+        )?;
+        Ok(labels.iter().any(|l| l.name == name))
+    }
+
+    fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()> {
+        self.http.send(
+            "POST",
+            &self.path("/labels"),
+            &json!({ "name": name, "description": description, "color": format!("#{}", color) }),
+        )
+    }
+
+    fn ensure_milestone(&self, title: &str, due_on: Option<&str>) -> Result<Milestone> {
+        let existing: Vec<GitLabMilestone> = self.http.json(
+            "GET",
+            &self.path(&format!("/milestones?{}", query(&[("title", title)]))),
+            None,
+        )?;
+
+        match existing.into_iter().find(|m| m.title == title) {
+            Some(milestone) => {
+                if due_on.is_none() || milestone.due_date.as_deref() == due_on {
+                    return Ok(milestone.into());
+                }
+                let updated: GitLabMilestone = self.http.json(
+                    "PUT",
+                    &self.path(&format!("/milestones/{}", milestone.id)),
+                    Some(&json!({ "due_date": due_on })),
+                )?;
+                Ok(updated.into())
+            }
+            None => {
+                let created: GitLabMilestone = self.http.json(
+                    "POST",
+                    &self.path("/milestones"),
+                    Some(&json!({ "title": title, "due_date": due_on })),
+                )?;
+                Ok(created.into())
+            }
+        }
+    }
+
+    fn set_issue_milestone(&self, number: i32, milestone: &Milestone) -> Result<()> {
+        self.edit(number, json!({ "milestone_id": milestone.id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracker::mock::{MockResponse, MockServer};
+
+    fn issue_json(iid: i32, title: &str, state: &str) -> serde_json::Value {
+        json!({
+            "iid": iid,
+            "title": title,
+            "description": null,
+            "state": state,
+            "web_url": format!("https://gitlab.example.com/group/app/-/issues/{}", iid),
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z"
+        })
+    }
+
+    fn client(server: &MockServer) -> GitLabClient {
+        GitLabClient::new(
+            &server.url,
+            "group/app",
+            Some("secret".to_string()),
+            TOKEN_ENV,
+        )
+    }
+
+    #[test]
+    fn test_create_and_close_issue() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "POST" => MockResponse::json(201, issue_json(7, "Add sync", "opened")),
+            _ => MockResponse::json(200, issue_json(7, "Add sync", "closed")),
+        });
+        let gitlab = client(&server);
+
+        let issue = gitlab
+            .create_issue("Add sync", "Body", &["roadmap", "q3"])
+            .unwrap();
+        assert_eq!(issue.number, 7);
+        assert_eq!(issue.state, "open");
+        assert_eq!(issue.body, "");
+        gitlab.close_issue(7).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].line(),
+            "POST /api/v4/projects/group%2Fapp/issues"
+        );
+        assert_eq!(requests[0].header("PRIVATE-TOKEN"), Some("secret"));
+        assert_eq!(requests[0].json()["labels"], "roadmap,q3");
+        assert_eq!(
+            requests[1].line(),
+            "PUT /api/v4/projects/group%2Fapp/issues/7"
+        );
+        assert_eq!(requests[1].json()["state_event"], "close");
+    }
+
+    #[test]
+    fn test_list_issues_paginates() {
+        let server = MockServer::start(|req| {
+            let page: Vec<serde_json::Value> = if req.url.contains("page=1&") {
+                (1..=50).map(|i| issue_json(i, "Item", "opened")).collect()
+            } else {
+                vec![issue_json(51, "Last", "closed")]
+            };
+            MockResponse::json(200, json!(page))
+        });
+
+        let issues = client(&server).list_issues_with_label("roadmap").unwrap();
+        assert_eq!(issues.len(), 51);
+        assert_eq!(issues[50].state, "closed");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].url.contains("labels=roadmap&state=all"));
+        assert!(requests[1].url.contains("page=2&per_page=50"));
+    }
+
+    #[test]
+    fn test_comments_skip_system_notes() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                json!([
+                    {"id": 1, "body": "closed", "author": {"username": "bot"},
+                     "created_at": "2024-01-01T00:00:00Z", "system": true},
+                    {"id": 2, "body": "Looks good", "author": {"username": "alice"},
+                     "created_at": "2024-01-02T00:00:00Z", "system": false}
+                ]),
+            )
+        });
+
+        let comments = client(&server).get_issue_comments(3).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author.login, "alice");
+    }
+
+    #[test]
+    fn test_find_by_title_and_missing_issue() {
+        let server = MockServer::start(|req| {
+            if req.url.contains("/issues/99") {
+                MockResponse::json(404, json!({"message": "404 Not found"}))
+            } else {
+                MockResponse::json(
+                    200,
+                    json!([
+                        issue_json(1, "Add sync v2", "opened"),
+                        issue_json(2, "add SYNC", "opened")
+                    ]),
+                )
+            }
+        });
+        let gitlab = client(&server);
+
+        let found = gitlab.find_issue_by_title("Add sync").unwrap().unwrap();
+        assert_eq!(found.number, 2);
+        assert!(matches!(
+            gitlab.get_issue(99),
+            Err(GitHubError::IssueNotFound { number: 99 })
+        ));
+    }
+
+    #[test]
+    fn test_ensure_milestone_updates_due_date() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => MockResponse::json(
+                200,
+                json!([{"id": 12, "title": "v1.0", "due_date": "2024-03-01"}]),
+            ),
+            "PUT" if req.url.contains("/milestones/") => MockResponse::json(
+                200,
+                json!({"id": 12, "title": "v1.0", "due_date": "2024-04-01"}),
+            ),
+            _ => MockResponse::json(200, issue_json(5, "Item", "opened")),
+        });
+        let gitlab = client(&server);
+
+        let milestone = gitlab.ensure_milestone("v1.0", Some("2024-04-01")).unwrap();
+        assert_eq!(milestone.id, 12);
+        assert_eq!(milestone.due_on.as_deref(), Some("2024-04-01"));
+        gitlab.set_issue_milestone(5, &milestone).unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].url.contains("/milestones?title=v1.0"));
+        assert_eq!(
+            requests[1].line(),
+            "PUT /api/v4/projects/group%2Fapp/milestones/12"
+        );
+        assert_eq!(requests[1].json()["due_date"], "2024-04-01");
+        assert_eq!(requests[2].json()["milestone_id"], 12);
+    }
+
+    #[test]
+    fn test_check_auth() {
+        let server =
+            MockServer::start(|_| MockResponse::json(401, json!({"message": "401 Unauthorized"})));
+        assert!(!client(&server).check_auth().unwrap());
+
+        let anonymous = GitLabClient::new(&server.url, "group/app", None, TOKEN_ENV);
+        assert!(!anonymous.check_auth().unwrap());
+        assert!(anonymous.auth_hint().contains("GITLAB_TOKEN"));
+    }
+}
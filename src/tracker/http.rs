@@ -0,0 +1,333 @@
+//! Minimal JSON-over-HTTP client shared by the REST tracker backends
+
+use crate::github::{GitHubError, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Items requested per page from list endpoints (Gitea caps this at 50)
+pub(crate) const PER_PAGE: usize = 50;
+
+/// Attempts per request when rate limited
+const MAX_ATTEMPTS: usize = 3;
+
+/// Longest we wait for a rate limit to reset before giving up
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(90);
+
+/// Blocking JSON client for one API root
+pub(crate) struct RestClient {
+    agent: ureq::Agent,
+    base_url: String,
+    auth: Option<(&'static str, String)>,
+}
+
+impl RestClient {
+    /// `auth` is a header sent with every request, e.g. ("PRIVATE-TOKEN", token)
+    pub fn new(base_url: &str, auth: Option<(&'static str, String)>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("deciduous/", env!("CARGO_PKG_VERSION")))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth,
+        }
+    }
+
+    /// True if requests carry credentials
+    pub fn has_auth(&self) -> bool {
+        self.auth.is_some()
+    }
+
+    /// Send a request and return the raw response
+    pub fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<ureq::Response> {
+        self.request_with_headers(method, path, body, &[])
+    }
+
+    /// Send a request with extra headers, waiting out rate limits
+    ///
+    /// `path` is relative to the API root, or an absolute URL (as found in
+    /// `Link` headers).
+    pub fn request_with_headers(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+        headers: &[(&str, &str)],
+    ) -> Result<ureq::Response> {
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.base_url, path)
+        };
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request = self
+                .agent
+                .request(method, &url)
+                .set("Accept", "application/json");
+            if let Some((name, value)) = &self.auth {
+                request = request.set(name, value);
+            }
+            for (name, value) in headers {
+                request = request.set(name, value);
+            }
+
+            let result = match body {
+                Some(body) => request.send_json(body.clone()),
+                None => request.call(),
+            };
+            match result {
+                Ok(response) => return Ok(response),
+                Err(ureq::Error::Status(status, response))
+                    if is_rate_limited(status, &response) =>
+                {
+                    let wait = rate_limit_wait(&response);
+                    if attempt == MAX_ATTEMPTS || wait > MAX_RATE_LIMIT_WAIT {
+                        return Err(GitHubError::RateLimited);
+                    }
+                    std::thread::sleep(wait);
+                }
+                Err(ureq::Error::Status(status, response)) => {
+                    return Err(GitHubError::RequestFailed {
+                        url,
+                        status,
+                        message: error_message(response),
+                    })
+                }
+                Err(ureq::Error::Transport(e)) => {
+                    return Err(GitHubError::RequestFailed {
+                        url,
+                        status: 0,
+                        message: e.to_string(),
+                    })
+                }
+            }
+        }
+        Err(GitHubError::RateLimited)
+    }
+
+    /// Send a request and decode the JSON response
+    pub fn json<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<T> {
+        decode(self.request(method, path, body)?, method, path)
+    }
+
+    /// Send a request, ignoring the response body
+    pub fn send(&self, method: &str, path: &str, body: &Value) -> Result<()> {
+        self.request(method, path, Some(body)).map(|_| ())
+    }
+
+    /// Conditional GET: `None` when the server answers 304 Not Modified for
+    /// `etag`, otherwise the decoded body and its new ETag
+    pub fn get_if_changed<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<(T, Option<String>)>> {
+        let headers: Vec<(&str, &str)> = etag.map(|e| ("If-None-Match", e)).into_iter().collect();
+        let response = self.request_with_headers("GET", path, None, &headers)?;
+        if response.status() == 304 {
+            return Ok(None);
+        }
+        let etag = response.header("ETag").map(str::to_string);
+        Ok(Some((decode(response, "GET", path)?, etag)))
+    }
+
+    /// GET every page of a list endpoint
+    ///
+    /// Follows `Link: <...>; rel="next"` when the server sends it, otherwise
+    /// requests `page=N` until a short page comes back.
+    pub fn get_all<T: DeserializeOwned>(&self, path: &str, per_page_param: &str) -> Result<Vec<T>> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let page_path = |page: usize| {
+            format!(
+                "{}{}page={}&{}={}",
+                path, separator, page, per_page_param, PER_PAGE
+            )
+        };
+
+        let mut all = Vec::new();
+        let mut page = 1;
+        let mut next = Some(page_path(page));
+        while let Some(current) = next.take() {
+            let response = self.request("GET", &current, None)?;
+            let link = response.header("Link").map(str::to_string);
+            let items: Vec<T> = decode(response, "GET", &current)?;
+            let full = items.len() >= PER_PAGE;
+            all.extend(items);
+
+            next = match link {
+                Some(link) => next_link(&link),
+                None if full => {
+                    page += 1;
+                    Some(page_path(page))
+                }
+                None => None,
+            };
+        }
+        Ok(all)
+    }
+}
+
+/// Encode query parameters (`a=1&b=two+words`)
+pub(crate) fn query(params: &[(&str, &str)]) -> String {
+    serde_urlencoded::to_string(params).unwrap_or_default()
+}
+
+fn decode<T: DeserializeOwned>(response: ureq::Response, method: &str, path: &str) -> Result<T> {
+    response.into_json().map_err(|e| GitHubError::ParseError {
+        message: format!("{} {}: {}", method, path, e),
+    })
+}
+
+/// 429, or GitHub's 403 with an exhausted quota or a Retry-After
+fn is_rate_limited(status: u16, response: &ureq::Response) -> bool {
+    status == 429
+        || (status == 403
+            && (response.header("X-RateLimit-Remaining") == Some("0")
+                || response.header("Retry-After").is_some()))
+}
+
+/// How long to wait before retrying a rate-limited request
+fn rate_limit_wait(response: &ureq::Response) -> Duration {
+    if let Some(secs) = response
+        .header("Retry-After")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    {
+        return Duration::from_secs(secs);
+    }
+    if let Some(reset) = response
+        .header("X-RateLimit-Reset")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+    {
+        let remaining = reset - chrono::Utc::now().timestamp();
+        return Duration::from_secs(remaining.max(0) as u64);
+    }
+    // Secondary limits without a hint: GitHub asks for at least a minute
+    Duration::from_secs(60)
+}
+
+/// URL with `rel="next"` from a Link header
+fn next_link(header: &str) -> Option<String> {
+    header
+        .split(',')
+        .find(|part| part.contains("rel=\"next\""))
+        .and_then(|part| {
+            let start = part.find('<')? + 1;
+            let end = part.find('>')?;
+            Some(part[start..end].to_string())
+        })
+}
+
+/// Best-effort error text from an API error response
+fn error_message(response: ureq::Response) -> String {
+    let text = response.into_string().unwrap_or_default();
+    serde_json::from_str::<Value>(&text)
+        .ok()
+        .and_then(|v| {
+            v.get("message")
+                .or_else(|| v.get("error"))
+                .map(|m| match m {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+        })
+        .unwrap_or_else(|| text.trim().chars().take(200).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracker::mock::{MockResponse, MockServer};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_next_link() {
+        let header = "<https://api.example.com/items?page=3>; rel=\"next\", \
+                      <https://api.example.com/items?page=9>; rel=\"last\"";
+        assert_eq!(
+            next_link(header).as_deref(),
+            Some("https://api.example.com/items?page=3")
+        );
+        assert_eq!(next_link("<https://x/items?page=1>; rel=\"prev\""), None);
+    }
+
+    #[test]
+    fn test_retries_after_rate_limit() {
+        let calls = AtomicUsize::new(0);
+        let server = MockServer::start(move |_| {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                let reset = chrono::Utc::now().timestamp().to_string();
+                MockResponse::json(403, json!({"message": "API rate limit exceeded"}))
+                    .header("X-RateLimit-Remaining", "0")
+                    .header("X-RateLimit-Reset", &reset)
+            } else {
+                MockResponse::json(200, json!({"ok": true}))
+            }
+        });
+
+        let client = RestClient::new(&server.url, None);
+        let value: Value = client.json("GET", "/thing", None).unwrap();
+        assert_eq!(value["ok"], true);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_gives_up_on_long_rate_limit() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(429, json!({"message": "slow down"})).header("Retry-After", "3600")
+        });
+
+        let client = RestClient::new(&server.url, None);
+        assert!(matches!(
+            client.json::<Value>("GET", "/thing", None),
+            Err(GitHubError::RateLimited)
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_forbidden_is_not_rate_limit() {
+        let server = MockServer::start(|_| MockResponse::json(403, json!({"message": "nope"})));
+        let client = RestClient::new(&server.url, None);
+        assert!(matches!(
+            client.json::<Value>("GET", "/thing", None),
+            Err(GitHubError::RequestFailed { status: 403, ref message, .. }) if message == "nope"
+        ));
+    }
+
+    #[test]
+    fn test_conditional_get() {
+        let server = MockServer::start(|req| {
+            if req.header("If-None-Match") == Some("\"v1\"") {
+                MockResponse::empty(304)
+            } else {
+                MockResponse::json(200, json!({"n": 1})).header("ETag", "\"v1\"")
+            }
+        });
+        let client = RestClient::new(&server.url, None);
+
+        let (value, etag) = client
+            .get_if_changed::<Value>("/thing", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(value["n"], 1);
+        assert_eq!(etag.as_deref(), Some("\"v1\""));
+
+        assert!(client
+            .get_if_changed::<Value>("/thing", etag.as_deref())
+            .unwrap()
+            .is_none());
+    }
+}
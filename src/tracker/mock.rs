@@ -0,0 +1,142 @@
+//! Local HTTP server with scripted responses for tracker tests
+
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tiny_http::{Header, Response, Server};
+
+/// A request the mock server received
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    /// Path and query string
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Recorded {
+    /// Value of a header (case-insensitive name)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Request body parsed as JSON (Null if empty)
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// "METHOD /path?query"
+    pub fn line(&self) -> String {
+        format!("{} {}", self.method, self.url)
+    }
+}
+
+/// A scripted response
+pub struct MockResponse {
+    pub status: u16,
+    pub body: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockResponse {
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Response without a body (e.g. 304 Not Modified)
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            body: String::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+type Handler = Box<dyn Fn(&Recorded) -> MockResponse + Send + Sync>;
+
+/// Serves requests on 127.0.0.1 from a handler until dropped
+pub struct MockServer {
+    pub url: String,
+    server: Arc<Server>,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&Recorded) -> MockResponse + Send + Sync + 'static) -> Self {
+        let handler: Handler = Box::new(handler);
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("bind mock server"));
+        let port = server.server_addr().to_ip().expect("ip address").port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let thread = {
+            let server = Arc::clone(&server);
+            let requests = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    let _ = request.as_reader().read_to_string(&mut body);
+                    let recorded = Recorded {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        headers: request
+                            .headers()
+                            .iter()
+                            .map(|h| (h.field.to_string(), h.value.to_string()))
+                            .collect(),
+                        body,
+                    };
+
+                    let reply = handler(&recorded);
+                    requests.lock().unwrap().push(recorded);
+
+                    let mut response =
+                        Response::from_string(reply.body).with_status_code(reply.status);
+                    response.add_header(
+                        Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
+                    );
+                    for (name, value) in reply.headers {
+                        response.add_header(
+                            Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap(),
+                        );
+                    }
+                    let _ = request.respond(response);
+                }
+            })
+        };
+
+        Self {
+            url: format!("http://127.0.0.1:{}", port),
+            server,
+            requests,
+            thread: Some(thread),
+        }
+    }
+
+    /// Everything received so far, in order
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
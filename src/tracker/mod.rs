@@ -0,0 +1,329 @@
+//! Issue tracker backends
+//!
+//! Roadmap sync talks to an [`IssueTracker`] rather than GitHub directly.
+//! The backend is chosen in `.deciduous/config.toml`:
+//!
+//! ```toml
+//! [tracker]
+//! kind = "gitea"                     # github (default), gitlab or gitea
+//! url = "https://git.example.com"    # instance root (API root for github)
+//! repo = "team/app"                  # default: parsed from the origin remote
+//! token_env = "GITEA_TOKEN"          # default: GITHUB_TOKEN / GITLAB_TOKEN / GITEA_TOKEN
+//! ```
+//!
+//! All three backends use their REST APIs with a token read from the
+//! environment (`GITHUB_TOKEN` for [`GitHubClient`], which also accepts the
+//! `gh` CLI's login).
+
+pub mod gitea;
+pub mod gitlab;
+pub(crate) mod http;
+#[cfg(test)]
+pub(crate) mod mock;
+
+pub use crate::github::{
+    GitHubComment as IssueComment, GitHubError as TrackerError, GitHubIssue as Issue, Result,
+};
+pub use gitea::GiteaClient;
+pub use gitlab::GitLabClient;
+
+use crate::config::{TrackerConfig, TrackerKind};
+use crate::github::{self, GitHubClient};
+use std::process::Command;
+
+/// Result of a conditional issue fetch
+#[derive(Debug, Clone)]
+pub enum IssueFetch {
+    /// The copy fetched with this ETag is still current
+    NotModified,
+    /// The issue changed (or no ETag was given); `etag` validates this copy
+    Modified { issue: Issue, etag: Option<String> },
+}
+
+/// A tracker milestone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Milestone {
+    /// What the backend uses to assign issues (GitHub `number`, GitLab and
+    /// Gitea `id`)
+    pub id: i64,
+    pub title: String,
+    /// Due date as YYYY-MM-DD
+    pub due_on: Option<String>,
+}
+
+/// Operations roadmap sync needs from an issue tracker
+///
+/// Issue numbers are the per-repository numbers users see (GitHub/Gitea
+/// `number`, GitLab `iid`), and `Issue::state` is normalized to "open" or
+/// "closed".
+pub trait IssueTracker {
+    /// Display name of the backend ("GitHub", "GitLab", ...)
+    fn name(&self) -> &'static str;
+
+    /// Repository the tracker is bound to, if known
+    fn repo_name(&self) -> Option<&str>;
+
+    /// Check that credentials are present and accepted
+    fn check_auth(&self) -> Result<bool>;
+
+    /// What to tell the user when `check_auth` fails
+    fn auth_hint(&self) -> String;
+
+    fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<Issue>;
+    fn get_issue(&self, number: i32) -> Result<Issue>;
+    fn update_issue_body(&self, number: i32, body: &str) -> Result<()>;
+    fn update_issue_title(&self, number: i32, title: &str) -> Result<()>;
+    fn close_issue(&self, number: i32) -> Result<()>;
+    fn reopen_issue(&self, number: i32) -> Result<()>;
+    fn get_issue_comments(&self, number: i32) -> Result<Vec<IssueComment>>;
+    fn add_comment(&self, number: i32, body: &str) -> Result<()>;
+    fn list_issues_with_label(&self, label: &str) -> Result<Vec<Issue>>;
+
+    /// Fetch an issue unless it is unchanged since `etag` was recorded
+    ///
+    /// Backends without conditional requests always fetch.
+    fn get_issue_if_changed(&self, number: i32, etag: Option<&str>) -> Result<IssueFetch> {
+        let _ = etag;
+        let issue = self.get_issue(number)?;
+        Ok(IssueFetch::Modified { issue, etag: None })
+    }
+
+    /// Find an issue whose title matches exactly (case-insensitive)
+    fn find_issue_by_title(&self, title: &str) -> Result<Option<Issue>>;
+
+    fn label_exists(&self, name: &str) -> Result<bool>;
+
+    /// Create a label; `color` is a hex string without `#`
+    fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()>;
+
+    /// Find the milestone titled `title`, creating it if needed and setting
+    /// its due date (YYYY-MM-DD) when given and different
+    fn ensure_milestone(&self, title: &str, due_on: Option<&str>) -> Result<Milestone>;
+
+    /// Assign an issue to a milestone
+    fn set_issue_milestone(&self, number: i32, milestone: &Milestone) -> Result<()>;
+}
+
+impl IssueTracker for GitHubClient {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn repo_name(&self) -> Option<&str> {
+        GitHubClient::repo_name(self)
+    }
+
+    fn check_auth(&self) -> Result<bool> {
+        self.token_accepted()
+    }
+
+    fn auth_hint(&self) -> String {
+        "Set GITHUB_TOKEN (or GH_TOKEN), or run 'gh auth login'".to_string()
+    }
+
+    fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<Issue> {
+        GitHubClient::create_issue(self, title, body, labels)
+    }
+
+    fn get_issue(&self, number: i32) -> Result<Issue> {
+        GitHubClient::get_issue(self, number)
+    }
+
+    fn get_issue_if_changed(&self, number: i32, etag: Option<&str>) -> Result<IssueFetch> {
+        GitHubClient::get_issue_if_changed(self, number, etag)
+    }
+
+    fn update_issue_body(&self, number: i32, body: &str) -> Result<()> {
+        GitHubClient::update_issue_body(self, number, body)
+    }
+
+    fn update_issue_title(&self, number: i32, title: &str) -> Result<()> {
+        GitHubClient::update_issue_title(self, number, title)
+    }
+
+    fn close_issue(&self, number: i32) -> Result<()> {
+        GitHubClient::close_issue(self, number)
+    }
+
+    fn reopen_issue(&self, number: i32) -> Result<()> {
+        GitHubClient::reopen_issue(self, number)
+    }
+
+    fn get_issue_comments(&self, number: i32) -> Result<Vec<IssueComment>> {
+        GitHubClient::get_issue_comments(self, number)
+    }
+
+    fn add_comment(&self, number: i32, body: &str) -> Result<()> {
+        GitHubClient::add_comment(self, number, body)
+    }
+
+    fn list_issues_with_label(&self, label: &str) -> Result<Vec<Issue>> {
+        GitHubClient::list_issues_with_label(self, label)
+    }
+
+    fn find_issue_by_title(&self, title: &str) -> Result<Option<Issue>> {
+        GitHubClient::find_issue_by_title(self, title)
+    }
+
+    fn label_exists(&self, name: &str) -> Result<bool> {
+        GitHubClient::label_exists(self, name)
+    }
+
+    fn create_label(&self, name: &str, description: &str, color: &str) -> Result<()> {
+        GitHubClient::create_label(self, name, description, color)
+    }
+
+    fn ensure_milestone(&self, title: &str, due_on: Option<&str>) -> Result<Milestone> {
+        GitHubClient::ensure_milestone(self, title, due_on)
+    }
+
+    fn set_issue_milestone(&self, number: i32, milestone: &Milestone) -> Result<()> {
+        GitHubClient::set_issue_milestone(self, number, milestone)
+    }
+}
+
+/// Build the configured tracker
+///
+/// `repo` (e.g. from `--repo`) overrides `tracker.repo`; when neither is set
+/// the repository is taken from the `origin` remote.
+pub fn open_tracker(config: &TrackerConfig, repo: Option<String>) -> Result<Box<dyn IssueTracker>> {
+    let repo = repo.or_else(|| config.repo.clone());
+
+    match config.kind {
+        TrackerKind::Github => {
+            let repo = repo.or_else(|| origin_url().and_then(|u| repo_from_remote_url(&u)));
+            let url = config.url.clone().unwrap_or_else(github::default_api_url);
+            let token = match &config.token_env {
+                Some(var) => read_token(var),
+                None => github::default_token(),
+            };
+            Ok(Box::new(GitHubClient::with_api_url(repo, &url, token)))
+        }
+        TrackerKind::Gitlab => {
+            let repo = repo_or_origin(repo)?;
+            let token_env = config.token_env.as_deref().unwrap_or(gitlab::TOKEN_ENV);
+            let url = config.url.as_deref().unwrap_or("https://gitlab.com");
+            Ok(Box::new(GitLabClient::new(
+                url,
+                &repo,
+                read_token(token_env),
+                token_env,
+            )))
+        }
+        TrackerKind::Gitea => {
+            let repo = repo_or_origin(repo)?;
+            let token_env = config.token_env.as_deref().unwrap_or(gitea::TOKEN_ENV);
+            let url = config
+                .url
+                .as_deref()
+                .ok_or_else(|| TrackerError::NotConfigured {
+                    message: "tracker.url is required for Gitea".to_string(),
+                })?;
+            Ok(Box::new(GiteaClient::new(
+                url,
+                &repo,
+                read_token(token_env),
+                token_env,
+            )))
+        }
+    }
+}
+
+/// The given repository, or the one the `origin` remote points at
+fn repo_or_origin(repo: Option<String>) -> Result<String> {
+    repo.or_else(|| origin_url().and_then(|u| repo_from_remote_url(&u)))
+        .ok_or_else(|| TrackerError::NotConfigured {
+            message: "set tracker.repo in config.toml or pass --repo".to_string(),
+        })
+}
+
+/// Non-empty value of a token environment variable
+fn read_token(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|t| !t.trim().is_empty())
+}
+
+/// URL of the `origin` remote
+pub(crate) fn origin_url() -> Option<String> {
+    Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// Repository path from a git remote URL
+///
+/// Handles `https://host/group/project.git`, `ssh://git@host:22/group/project`
+/// and scp-style `git@host:group/project.git`.
+pub fn repo_from_remote_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        // Drop the host (and any user/port)
+        rest.split_once('/')?.1
+    } else {
+        // scp-style: user@host:path
+        url.split_once(':')?.1
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if path.contains('/') {
+        Some(path.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_from_remote_url() {
+        assert_eq!(
+            repo_from_remote_url("https://gitlab.com/group/sub/app.git"),
+            Some("group/sub/app".to_string())
+        );
+        assert_eq!(
+            repo_from_remote_url("git@git.example.com:team/app.git"),
+            Some("team/app".to_string())
+        );
+        assert_eq!(
+            repo_from_remote_url("ssh://git@git.example.com:2222/team/app"),
+            Some("team/app".to_string())
+        );
+        assert_eq!(repo_from_remote_url("/srv/git/app.git"), None);
+    }
+
+    #[test]
+    fn test_open_tracker_selects_backend() {
+        let config = TrackerConfig {
+            kind: TrackerKind::Gitlab,
+            url: None,
+            repo: Some("group/app".to_string()),
+            token_env: Some("DECIDUOUS_TEST_UNSET_TOKEN".to_string()),
+        };
+        let tracker = open_tracker(&config, None).unwrap();
+        assert_eq!(tracker.name(), "GitLab");
+        assert_eq!(tracker.repo_name(), Some("group/app"));
+        assert!(!tracker.check_auth().unwrap());
+        assert!(tracker.auth_hint().contains("DECIDUOUS_TEST_UNSET_TOKEN"));
+
+        let tracker = open_tracker(&TrackerConfig::default(), Some("o/r".to_string())).unwrap();
+        assert_eq!(tracker.name(), "GitHub");
+        assert_eq!(tracker.repo_name(), Some("o/r"));
+    }
+
+    #[test]
+    fn test_gitea_requires_url() {
+        let config = TrackerConfig {
+            kind: TrackerKind::Gitea,
+            repo: Some("team/app".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            open_tracker(&config, None),
+            Err(TrackerError::NotConfigured { .. })
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Application state for the TUI
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
@@ -175,6 +176,7 @@ pub struct App {
     // Graph data
     pub graph: DecisionGraph,
     pub filtered_nodes: Vec<DecisionNode>,
+    pub node_tags: HashMap<i32, Vec<String>>,
 
     // View state
     pub current_view: View,
@@ -189,6 +191,7 @@ pub struct App {
     // Filters
     pub type_filter: Option<String>,
     pub branch_filter: Option<String>,
+    pub tag_filter: Option<String>,
     pub search_query: String,
     pub branch_search_query: String,
     pub branch_search_matches: Vec<String>,
@@ -245,6 +248,7 @@ impl App {
 
         let actual_path = Database::db_path();
         let graph = db.get_graph()?;
+        let node_tags = db.get_all_node_tags()?;
         let filtered_nodes = graph.nodes.clone();
 
         // Sort by created_at descending (newest first)
@@ -256,6 +260,7 @@ impl App {
             db_path: actual_path,
             graph,
             filtered_nodes,
+            node_tags,
             current_view: View::Timeline,
             selected_index: 0,
             scroll_offset: 0,
@@ -264,6 +269,7 @@ impl App {
             detail_scroll: 0,
             type_filter: None,
             branch_filter: None,
+            tag_filter: None,
             search_query: String::new(),
             branch_search_query: String::new(),
             branch_search_matches: vec![],
@@ -297,6 +303,7 @@ impl App {
     /// Reload the graph from database
     pub fn reload_graph(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.graph = self.db.get_graph()?;
+        self.node_tags = self.db.get_all_node_tags()?;
         self.apply_filters();
         Ok(())
     }
@@ -330,6 +337,8 @@ impl App {
             &self.graph.nodes,
             self.type_filter.as_deref(),
             self.branch_filter.as_deref(),
+            self.tag_filter.as_deref(),
+            &self.node_tags,
             &self.search_query,
             self.reverse_order,
         );
@@ -389,6 +398,11 @@ impl App {
         types::get_prompt(node)
     }
 
+    /// Parse metadata and extract custom fields
+    pub fn get_fields(node: &DecisionNode) -> Vec<(String, String)> {
+        types::get_fields(node)
+    }
+
     // Navigation methods
     pub fn move_up(&mut self) {
         if self.selected_index > 0 {
@@ -479,7 +493,12 @@ impl App {
     /// Detect GitHub repo from git remote URL
     fn detect_github_repo(&self) -> Option<String> {
         // First try to get from database sync state
-        if let Ok(Some(state)) = self.db.get_roadmap_sync_state("ROADMAP.md") {
+        let file = self
+            .roadmap_state
+            .file_filter
+            .as_deref()
+            .unwrap_or("ROADMAP.md");
+        if let Ok(Some(state)) = self.db.get_roadmap_sync_state(file) {
             if state.github_repo.is_some() {
                 return state.github_repo;
             }
@@ -883,6 +902,33 @@ impl App {
         self.apply_filters();
     }
 
+    /// Cycle through tag filters
+    pub fn cycle_tag_filter(&mut self) {
+        let tags = super::state::get_unique_tags(&self.node_tags);
+        if tags.is_empty() {
+            self.set_status("No tags found".to_string());
+            return;
+        }
+
+        self.tag_filter = super::state::cycle_tag_filter(self.tag_filter.as_deref(), &tags);
+
+        if let Some(ref tag) = self.tag_filter {
+            self.set_status(format!("Filter: #{}", tag));
+        } else {
+            self.set_status("Filter: All tags".to_string());
+        }
+
+        self.apply_filters();
+    }
+
+    /// Tags on a node
+    pub fn get_tags(&self, node: &DecisionNode) -> &[String] {
+        self.node_tags
+            .get(&node.id)
+            .map(|t| t.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Enter branch search mode
     pub fn enter_branch_search(&mut self) {
         self.mode = Mode::BranchSearch;
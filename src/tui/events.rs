@@ -165,6 +165,7 @@ fn handle_timeline_keys(app: &mut App, key: KeyEvent) -> bool {
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.type_filter = None;
             app.branch_filter = None;
+            app.tag_filter = None;
             app.search_query.clear();
             app.apply_filters();
         }
@@ -198,6 +199,11 @@ fn handle_timeline_keys(app: &mut App, key: KeyEvent) -> bool {
             app.cycle_branch_filter();
         }
 
+        // Filter by tag (cycle)
+        KeyCode::Char('t') => {
+            app.cycle_tag_filter();
+        }
+
         // Branch search (fuzzy)
         KeyCode::Char('B') => {
             app.enter_branch_search();
@@ -6,6 +6,7 @@
 //! - DAG visualization with hierarchical layout
 //! - Node detail panel with code jumping
 //! - Auto-refresh on database changes
+//! - Side-by-side resolver for roadmap sync conflicts
 
 pub mod app;
 pub mod events;
@@ -23,18 +24,25 @@ use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{poll, read, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{poll, read, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::prelude::*;
 
+use crate::db::RoadmapConflict;
 use app::App;
 use events::handle_event;
+use views::conflicts::{ConflictChoice, ConflictEntry, ConflictResolverState, ResolverOutcome};
 
 /// Run the TUI application
-pub fn run(db_path: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `roadmap_file` limits the roadmap view to items from that file.
+pub fn run(
+    db_path: Option<PathBuf>,
+    roadmap_file: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -43,7 +51,7 @@ pub fn run(db_path: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app, ensuring cleanup happens even on error
-    let result = run_app_inner(&mut terminal, db_path);
+    let result = run_app_inner(&mut terminal, db_path, roadmap_file);
 
     // Restore terminal - this MUST run even if app fails
     let _ = disable_raw_mode();
@@ -57,12 +65,57 @@ pub fn run(db_path: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
     result
 }
 
+/// Run the roadmap conflict resolver
+///
+/// Returns the conflicts the user made a choice for, or nothing if they quit
+/// without applying.
+pub fn resolve_conflicts(
+    entries: Vec<ConflictEntry>,
+) -> Result<Vec<(RoadmapConflict, ConflictChoice)>, Box<dyn std::error::Error>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_resolver_inner(&mut terminal, ConflictResolverState::new(entries));
+
+    // Restore terminal - this MUST run even if the resolver fails
+    let _ = disable_raw_mode();
+    let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+    let _ = terminal.show_cursor();
+
+    result
+}
+
+fn run_resolver_inner<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut state: ConflictResolverState,
+) -> Result<Vec<(RoadmapConflict, ConflictChoice)>, Box<dyn std::error::Error>> {
+    loop {
+        terminal.draw(|f| views::conflicts::draw(f, &state, f.area()))?;
+
+        if let Event::Key(key) = read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match state.handle_key(key) {
+                ResolverOutcome::Continue => {}
+                ResolverOutcome::Apply => return Ok(state.decisions()),
+                ResolverOutcome::Cancel => return Ok(Vec::new()),
+            }
+        }
+    }
+}
+
 fn run_app_inner<B: Backend + std::io::Write>(
     terminal: &mut Terminal<B>,
     db_path: Option<PathBuf>,
+    roadmap_file: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Create app state
     let mut app = App::new(db_path)?;
+    app.roadmap_state.set_file_filter(roadmap_file);
 
     // Setup file watcher for auto-refresh
     let (tx, rx) = mpsc::channel();
@@ -10,7 +10,7 @@
 //! The "imperative shell" (app.rs, events.rs) handles I/O and calls these pure functions.
 
 use crate::{DecisionEdge, DecisionNode};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 // =============================================================================
 // Filter Functions - Pure transformations on node lists
@@ -40,6 +40,26 @@ pub fn filter_by_branch(nodes: &[DecisionNode], branch: Option<&str>) -> Vec<Dec
     }
 }
 
+/// Filter nodes by tag (looked up in `node_tags`, keyed by node ID)
+pub fn filter_by_tag(
+    nodes: &[DecisionNode],
+    tag: Option<&str>,
+    node_tags: &HashMap<i32, Vec<String>>,
+) -> Vec<DecisionNode> {
+    match tag {
+        Some(t) => nodes
+            .iter()
+            .filter(|n| {
+                node_tags
+                    .get(&n.id)
+                    .is_some_and(|tags| tags.iter().any(|nt| nt == t))
+            })
+            .cloned()
+            .collect(),
+        None => nodes.to_vec(),
+    }
+}
+
 /// Filter nodes by search query (searches title and description)
 pub fn filter_by_search(nodes: &[DecisionNode], query: &str) -> Vec<DecisionNode> {
     if query.is_empty() {
@@ -77,11 +97,14 @@ pub fn apply_all_filters(
     nodes: &[DecisionNode],
     type_filter: Option<&str>,
     branch_filter: Option<&str>,
+    tag_filter: Option<&str>,
+    node_tags: &HashMap<i32, Vec<String>>,
     search_query: &str,
     reverse_order: bool,
 ) -> Vec<DecisionNode> {
     let filtered = filter_by_type(nodes, type_filter);
     let filtered = filter_by_branch(&filtered, branch_filter);
+    let filtered = filter_by_tag(&filtered, tag_filter, node_tags);
     let filtered = filter_by_search(&filtered, search_query);
     sort_by_time(&filtered, reverse_order)
 }
@@ -339,6 +362,19 @@ pub fn cycle_type_filter(current: Option<&str>) -> Option<String> {
     }
 }
 
+/// Unique tags across all nodes, sorted
+pub fn get_unique_tags(node_tags: &HashMap<i32, Vec<String>>) -> Vec<String> {
+    let mut tags: Vec<String> = node_tags.values().flatten().cloned().collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Cycle through tag filters
+pub fn cycle_tag_filter(current: Option<&str>, tags: &[String]) -> Option<String> {
+    cycle_branch_filter(current, tags)
+}
+
 /// Cycle through branch filters
 pub fn cycle_branch_filter(current: Option<&str>, branches: &[String]) -> Option<String> {
     if branches.is_empty() {
@@ -705,6 +741,28 @@ mod tests {
         assert_eq!(cycle_branch_filter(None, &[]), None);
     }
 
+    #[test]
+    fn test_tag_filters() {
+        let nodes = vec![
+            make_node(1, "goal", "G1", None),
+            make_node(2, "action", "A1", None),
+            make_node(3, "action", "A2", None),
+        ];
+        let node_tags = HashMap::from([
+            (1, vec!["security".to_string()]),
+            (2, vec!["perf".to_string(), "security".to_string()]),
+        ]);
+
+        let tagged = filter_by_tag(&nodes, Some("security"), &node_tags);
+        assert_eq!(tagged.iter().map(|n| n.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(filter_by_tag(&nodes, None, &node_tags).len(), 3);
+
+        let tags = get_unique_tags(&node_tags);
+        assert_eq!(tags, vec!["perf", "security"]);
+        assert_eq!(cycle_tag_filter(None, &tags), Some("perf".to_string()));
+        assert_eq!(cycle_tag_filter(Some("security"), &tags), None);
+    }
+
     #[test]
     fn test_filter_branch_matches() {
         let branches = vec![
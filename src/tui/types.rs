@@ -30,7 +30,7 @@ pub const NODE_STATUSES: &[&str] = &["pending", "active", "completed", "rejected
 
 /// Valid edge types connecting nodes
 #[rustfmt::skip]
-pub const EDGE_TYPES: &[&str] = &["leads_to", "requires", "chosen", "rejected", "blocks", "enables"];
+pub const EDGE_TYPES: &[&str] = &["leads_to", "requires", "chosen", "rejected", "blocks", "enables", "supersedes"];
 
 // =============================================================================
 // Metadata - stored as JSON string in metadata_json field
@@ -121,6 +121,14 @@ pub fn get_prompt(node: &DecisionNode) -> Option<String> {
     NodeMetadata::from_option(node.metadata_json.as_ref()).prompt
 }
 
+/// Extract custom fields as (name, display value) pairs, sorted by name
+pub fn get_fields(node: &DecisionNode) -> Vec<(String, String)> {
+    crate::fields::node_fields(node.metadata_json.as_deref())
+        .iter()
+        .map(|(name, value)| (name.clone(), crate::fields::format_field_value(value)))
+        .collect()
+}
+
 /// Get short commit hash (7 chars) (mirrors shortCommit in TypeScript)
 pub fn short_commit(commit: &str) -> &str {
     &commit[..7.min(commit.len())]
@@ -240,6 +248,7 @@ mod tests {
         assert!(is_edge_type("rejected"));
         assert!(is_edge_type("blocks"));
         assert!(is_edge_type("enables"));
+        assert!(is_edge_type("supersedes"));
         assert!(!is_edge_type("invalid"));
         assert!(!is_edge_type(""));
     }
@@ -269,6 +278,23 @@ mod tests {
         assert_eq!(meta.confidence, None);
     }
 
+    #[test]
+    fn test_get_fields() {
+        let node = make_test_node(
+            1,
+            "action",
+            "Test",
+            Some(r#"{"fields": {"risk": "high", "cost_estimate": 2.5}}"#),
+        );
+        assert_eq!(
+            get_fields(&node),
+            vec![
+                ("cost_estimate".to_string(), "2.5".to_string()),
+                ("risk".to_string(), "high".to_string())
+            ]
+        );
+    }
+
     #[test]
     fn test_get_confidence() {
         let node = make_test_node(1, "goal", "Test", Some(r#"{"confidence": 90}"#));
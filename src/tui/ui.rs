@@ -182,6 +182,15 @@ fn draw_filter_bar(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
+    // Tag filter
+    if let Some(ref tag) = app.tag_filter {
+        spans.push(Span::raw(" │ Tag: "));
+        spans.push(Span::styled(
+            format!("[#{}]", tag),
+            Style::default().fg(Color::Black).bg(Color::Magenta),
+        ));
+    }
+
     // Search indicator
     if app.mode == Mode::Search || !app.search_query.is_empty() {
         spans.push(Span::raw(" │ Search: "));
@@ -208,7 +217,7 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
             if app.detail_in_files {
                 "n/N:files  p:preview  d:diff  o:open  F:exit  q:quit"
             } else {
-                "j/k:move  o:files  O:commit  s:story  p:preview  F:browse  /:search  f:type  b:branch  q:quit"
+                "j/k:move  o:files  O:commit  s:story  p:preview  F:browse  /:search  f:type  b:branch  t:tag  q:quit"
             }
         }
         View::Dag => "h/j/k/l:pan  +/-:zoom  0:reset  Tab:Timeline  ?:help  q:quit",
@@ -231,7 +240,7 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
 fn draw_help_overlay(frame: &mut Frame, area: Rect) {
     // Center the help popup
     let popup_width = 60.min(area.width.saturating_sub(4));
-    let popup_height = 32.min(area.height.saturating_sub(4));
+    let popup_height = 34.min(area.height.saturating_sub(4));
 
     let popup_area = Rect {
         x: (area.width - popup_width) / 2,
@@ -255,6 +264,8 @@ fn draw_help_overlay(frame: &mut Frame, area: Rect) {
   O            Open commit in git
   /            Search
   f            Cycle type filter
+  b            Cycle branch filter
+  t            Cycle tag filter
   Ctrl+c       Clear all filters
   Tab          Switch view
   r            Refresh
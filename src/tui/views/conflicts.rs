@@ -0,0 +1,566 @@
+//! Conflict resolver - side-by-side view of roadmap sync conflicts
+//!
+//! Follows TEA (The Elm Architecture):
+//! - Model: ConflictResolverState (data)
+//! - Update: state mutation methods and handle_key()
+//! - View: draw() function
+//!
+//! The resolver only collects choices; applying them to ROADMAP.md and the
+//! issue is up to the caller.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+use super::roadmap::{move_down, move_up, truncate_str};
+use crate::db::{CheckboxState, ConflictResolution, RoadmapConflict};
+
+// =============================================================================
+// Model - State
+// =============================================================================
+
+/// A conflict with the roadmap context needed to show it
+#[derive(Debug, Clone)]
+pub struct ConflictEntry {
+    pub conflict: RoadmapConflict,
+    /// Item text (falls back to the change_id)
+    pub title: String,
+    pub section: Option<String>,
+    pub issue_number: Option<i32>,
+}
+
+/// What the user chose for one conflict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictChoice {
+    KeepLocal,
+    KeepRemote,
+    Edited(String),
+}
+
+impl ConflictChoice {
+    /// How the choice is recorded in the database
+    pub fn resolution(&self) -> ConflictResolution {
+        match self {
+            ConflictChoice::KeepLocal => ConflictResolution::KeepLocal,
+            ConflictChoice::KeepRemote => ConflictResolution::KeepRemote,
+            ConflictChoice::Edited(_) => ConflictResolution::Edited,
+        }
+    }
+
+    /// Value to apply to both sides
+    pub fn value<'a>(&'a self, conflict: &'a RoadmapConflict) -> &'a str {
+        match self {
+            ConflictChoice::KeepLocal => conflict.local_value.as_deref().unwrap_or(""),
+            ConflictChoice::KeepRemote => conflict.remote_value.as_deref().unwrap_or(""),
+            ConflictChoice::Edited(value) => value,
+        }
+    }
+}
+
+/// What the event loop should do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolverOutcome {
+    Continue,
+    /// Apply the choices made so far
+    Apply,
+    /// Quit without applying anything
+    Cancel,
+}
+
+/// State for the conflict resolver
+#[derive(Debug, Clone, Default)]
+pub struct ConflictResolverState {
+    entries: Vec<ConflictEntry>,
+    /// Choice per entry (same order as `entries`)
+    choices: Vec<Option<ConflictChoice>>,
+    /// Selected conflict
+    pub selected_index: usize,
+    /// Edit buffer while typing a value
+    pub editing: Option<String>,
+    /// Feedback line (validation errors, hints)
+    pub status: Option<String>,
+}
+
+// =============================================================================
+// Pure Functions - Functional Core
+// =============================================================================
+
+/// Validate and normalize a hand-edited value for a conflict type
+pub fn normalize_value(conflict_type: &str, input: &str) -> Result<String, String> {
+    let input = input.trim();
+    match conflict_type {
+        "checkbox" => match CheckboxState::parse(input) {
+            CheckboxState::None => Err("Enter 'checked' or 'unchecked'".to_string()),
+            state => Ok(state.as_str().to_string()),
+        },
+        _ if input.is_empty() => Err("Value can't be empty".to_string()),
+        _ => Ok(input.to_string()),
+    }
+}
+
+/// Index of the first undecided entry after `current`, wrapping around
+pub fn next_undecided(choices: &[Option<ConflictChoice>], current: usize) -> Option<usize> {
+    (1..=choices.len())
+        .map(|offset| (current + offset) % choices.len())
+        .find(|&i| choices[i].is_none())
+}
+
+// =============================================================================
+// Update - State Mutations (Methods)
+// =============================================================================
+
+impl ConflictResolverState {
+    pub fn new(entries: Vec<ConflictEntry>) -> Self {
+        let choices = vec![None; entries.len()];
+        Self {
+            entries,
+            choices,
+            ..Default::default()
+        }
+    }
+
+    pub fn entries(&self) -> &[ConflictEntry] {
+        &self.entries
+    }
+
+    pub fn selected_entry(&self) -> Option<&ConflictEntry> {
+        self.entries.get(self.selected_index)
+    }
+
+    pub fn choice(&self, index: usize) -> Option<&ConflictChoice> {
+        self.choices.get(index).and_then(|c| c.as_ref())
+    }
+
+    /// Number of conflicts still without a choice
+    pub fn undecided_count(&self) -> usize {
+        self.choices.iter().filter(|c| c.is_none()).count()
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = move_up(self.selected_index);
+    }
+
+    pub fn move_down(&mut self) {
+        self.selected_index = move_down(self.selected_index, self.entries.len());
+    }
+
+    /// Record a choice for the selected conflict and move to the next open one
+    pub fn choose(&mut self, choice: ConflictChoice) {
+        if self.entries.is_empty() {
+            return;
+        }
+        self.choices[self.selected_index] = Some(choice);
+        self.status = None;
+        if let Some(next) = next_undecided(&self.choices, self.selected_index) {
+            self.selected_index = next;
+        }
+    }
+
+    /// Forget the choice for the selected conflict
+    pub fn clear_choice(&mut self) {
+        if let Some(choice) = self.choices.get_mut(self.selected_index) {
+            *choice = None;
+        }
+    }
+
+    /// Start typing a value, prefilled with the current choice or local value
+    pub fn start_edit(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let initial = match self.choice(self.selected_index) {
+            Some(choice) => choice.value(&entry.conflict).to_string(),
+            None => entry.conflict.local_value.clone().unwrap_or_default(),
+        };
+        self.editing = Some(initial);
+        self.status = None;
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    /// Accept the edit buffer if it's valid for the conflict type
+    pub fn confirm_edit(&mut self) {
+        let (Some(input), Some(entry)) = (self.editing.as_ref(), self.selected_entry()) else {
+            return;
+        };
+        match normalize_value(&entry.conflict.conflict_type, input) {
+            Ok(value) => {
+                self.editing = None;
+                self.choose(ConflictChoice::Edited(value));
+            }
+            Err(message) => self.status = Some(message),
+        }
+    }
+
+    /// Conflicts with a choice, in list order
+    pub fn decisions(&self) -> Vec<(RoadmapConflict, ConflictChoice)> {
+        self.entries
+            .iter()
+            .zip(&self.choices)
+            .filter_map(|(entry, choice)| {
+                choice.as_ref().map(|c| (entry.conflict.clone(), c.clone()))
+            })
+            .collect()
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: KeyEvent) -> ResolverOutcome {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return ResolverOutcome::Cancel;
+        }
+
+        if let Some(buffer) = self.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.cancel_edit(),
+                KeyCode::Enter => self.confirm_edit(),
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return ResolverOutcome::Continue;
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => self.move_up(),
+            KeyCode::Char('l') | KeyCode::Left => self.choose(ConflictChoice::KeepLocal),
+            KeyCode::Char('r') | KeyCode::Right => self.choose(ConflictChoice::KeepRemote),
+            KeyCode::Char('e') => self.start_edit(),
+            KeyCode::Char('u') => self.clear_choice(),
+            KeyCode::Enter => {
+                if self.undecided_count() == self.entries.len() {
+                    self.status = Some("Choose l, r or e for at least one conflict".to_string());
+                } else {
+                    return ResolverOutcome::Apply;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return ResolverOutcome::Cancel,
+            _ => {}
+        }
+        ResolverOutcome::Continue
+    }
+}
+
+// =============================================================================
+// View - Rendering
+// =============================================================================
+
+/// Draw the resolver
+pub fn draw(frame: &mut Frame, state: &ConflictResolverState, area: Rect) {
+    let list_height = (state.entries.len() as u16 + 2).clamp(3, 12);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(list_height),
+            Constraint::Min(5),
+            Constraint::Length(if state.editing.is_some() { 3 } else { 1 }),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    draw_list(frame, state, chunks[0]);
+    draw_sides(frame, state, chunks[1]);
+    draw_status(frame, state, chunks[2]);
+    draw_help_bar(frame, chunks[3]);
+}
+
+/// Draw the list of conflicts with their choices
+fn draw_list(frame: &mut Frame, state: &ConflictResolverState, area: Rect) {
+    let block = Block::default()
+        .title(format!(
+            " Conflicts ({} undecided of {}) ",
+            state.undecided_count(),
+            state.entries.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let max_title_len = (area.width as usize).saturating_sub(30);
+    let items: Vec<ListItem> = state
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (marker, marker_style) = match state.choice(i) {
+                None => ("  ?   ".to_string(), Style::default().fg(Color::Yellow)),
+                Some(ConflictChoice::KeepLocal) => {
+                    ("local ".to_string(), Style::default().fg(Color::Green))
+                }
+                Some(ConflictChoice::KeepRemote) => {
+                    ("remote".to_string(), Style::default().fg(Color::Green))
+                }
+                Some(ConflictChoice::Edited(_)) => {
+                    ("edited".to_string(), Style::default().fg(Color::Cyan))
+                }
+            };
+            let line = Line::from(vec![
+                Span::styled(format!(" {} ", marker), marker_style),
+                Span::styled(
+                    truncate_str(&entry.title, max_title_len),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("  ({})", entry.conflict.conflict_type),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            let style = if i == state.selected_index {
+                Style::default().bg(Color::Rgb(40, 40, 50))
+            } else {
+                Style::default()
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+/// Draw local and remote values side by side
+fn draw_sides(frame: &mut Frame, state: &ConflictResolverState, area: Rect) {
+    let Some(entry) = state.selected_entry() else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let choice = state.choice(state.selected_index);
+    let remote_title = match entry.issue_number {
+        Some(number) => format!(" Remote (issue #{}) ", number),
+        None => " Remote (issue) ".to_string(),
+    };
+    let sides = [
+        (
+            " Local (ROADMAP.md) ".to_string(),
+            entry.conflict.local_value.as_deref(),
+            choice == Some(&ConflictChoice::KeepLocal),
+        ),
+        (
+            remote_title,
+            entry.conflict.remote_value.as_deref(),
+            choice == Some(&ConflictChoice::KeepRemote),
+        ),
+    ];
+
+    for ((title, value, chosen), area) in sides.into_iter().zip(chunks.iter()) {
+        let border = if chosen { Color::Green } else { Color::Blue };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border));
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                entry.title.clone(),
+                Style::default().fg(Color::White).bold(),
+            )),
+            Line::from(Span::styled(
+                entry.section.clone().unwrap_or_default(),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+        ];
+        lines.push(match value {
+            Some(value) => Line::from(Span::styled(
+                value.to_string(),
+                Style::default().fg(Color::Cyan),
+            )),
+            None => Line::from(Span::styled("(none)", Style::default().fg(Color::DarkGray))),
+        });
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, *area);
+    }
+}
+
+/// Draw the edit box or the status line
+fn draw_status(frame: &mut Frame, state: &ConflictResolverState, area: Rect) {
+    if let Some(ref buffer) = state.editing {
+        let block = Block::default()
+            .title(" New value (Enter=accept, Esc=cancel) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let input = Paragraph::new(format!("{}_", buffer)).block(block);
+        frame.render_widget(input, area);
+        return;
+    }
+
+    if let Some(ref status) = state.status {
+        let line = Paragraph::new(status.as_str()).style(Style::default().fg(Color::Red));
+        frame.render_widget(line, area);
+    }
+}
+
+/// Draw the help bar at the bottom
+fn draw_help_bar(frame: &mut Frame, area: Rect) {
+    let help = Line::from(vec![
+        Span::styled(" j/k", Style::default().fg(Color::Cyan)),
+        Span::raw(":nav "),
+        Span::styled("l", Style::default().fg(Color::Cyan)),
+        Span::raw(":keep local "),
+        Span::styled("r", Style::default().fg(Color::Cyan)),
+        Span::raw(":keep remote "),
+        Span::styled("e", Style::default().fg(Color::Cyan)),
+        Span::raw(":edit "),
+        Span::styled("u", Style::default().fg(Color::Cyan)),
+        Span::raw(":undo "),
+        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::raw(":apply "),
+        Span::styled("q", Style::default().fg(Color::Cyan)),
+        Span::raw(":quit"),
+    ]);
+
+    let help_widget =
+        Paragraph::new(help).style(Style::default().bg(Color::DarkGray).fg(Color::White));
+    frame.render_widget(help_widget, area);
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_entry(id: i32, conflict_type: &str, local: &str, remote: &str) -> ConflictEntry {
+        ConflictEntry {
+            conflict: RoadmapConflict {
+                id,
+                item_change_id: format!("item-{}", id),
+                conflict_type: conflict_type.to_string(),
+                local_value: Some(local.to_string()),
+                remote_value: Some(remote.to_string()),
+                resolution: None,
+                detected_at: "2024-01-01T00:00:00Z".to_string(),
+                resolved_at: None,
+                resolved_value: None,
+                resolved_by: None,
+            },
+            title: format!("Item {}", id),
+            section: Some("Next".to_string()),
+            issue_number: Some(id),
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(state: &mut ConflictResolverState, text: &str) {
+        for c in text.chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_normalize_value() {
+        assert_eq!(
+            normalize_value("checkbox", " Checked "),
+            Ok("checked".to_string())
+        );
+        assert!(normalize_value("checkbox", "done").is_err());
+        assert_eq!(normalize_value("title", " New "), Ok("New".to_string()));
+        assert!(normalize_value("title", "  ").is_err());
+    }
+
+    #[test]
+    fn test_next_undecided_wraps() {
+        let choices = vec![None, Some(ConflictChoice::KeepLocal), None];
+        assert_eq!(next_undecided(&choices, 0), Some(2));
+        assert_eq!(next_undecided(&choices, 2), Some(0));
+        assert_eq!(next_undecided(&[Some(ConflictChoice::KeepLocal)], 0), None);
+    }
+
+    #[test]
+    fn test_choices_advance_and_apply() {
+        let mut state = ConflictResolverState::new(vec![
+            make_entry(1, "checkbox", "checked", "unchecked"),
+            make_entry(2, "checkbox", "unchecked", "checked"),
+        ]);
+
+        // Nothing chosen yet: Enter doesn't apply
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            ResolverOutcome::Continue
+        );
+        assert!(state.status.is_some());
+
+        state.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(state.selected_index, 1);
+        state.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(state.undecided_count(), 0);
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            ResolverOutcome::Apply
+        );
+
+        let decisions = state.decisions();
+        assert_eq!(decisions[0].1, ConflictChoice::KeepLocal);
+        assert_eq!(decisions[0].1.value(&decisions[0].0), "checked");
+        assert_eq!(decisions[1].1.resolution(), ConflictResolution::KeepRemote);
+        assert_eq!(decisions[1].1.value(&decisions[1].0), "checked");
+    }
+
+    #[test]
+    fn test_edit_validates_value() {
+        let mut state =
+            ConflictResolverState::new(vec![make_entry(1, "checkbox", "checked", "unchecked")]);
+
+        state.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(state.editing.as_deref(), Some("checked"));
+
+        // Clear the prefilled value and type something invalid
+        for _ in 0.."checked".len() {
+            state.handle_key(key(KeyCode::Backspace));
+        }
+        type_text(&mut state, "maybe");
+        state.handle_key(key(KeyCode::Enter));
+        assert!(state.editing.is_some());
+        assert!(state.status.is_some());
+
+        for _ in 0.."maybe".len() {
+            state.handle_key(key(KeyCode::Backspace));
+        }
+        type_text(&mut state, "Unchecked");
+        state.handle_key(key(KeyCode::Enter));
+        assert!(state.editing.is_none());
+        assert_eq!(
+            state.choice(0),
+            Some(&ConflictChoice::Edited("unchecked".to_string()))
+        );
+
+        // 'q' while editing is text, not quit
+        state.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(
+            state.handle_key(key(KeyCode::Char('q'))),
+            ResolverOutcome::Continue
+        );
+        state.handle_key(key(KeyCode::Esc));
+        assert_eq!(
+            state.handle_key(key(KeyCode::Char('q'))),
+            ResolverOutcome::Cancel
+        );
+    }
+
+    #[test]
+    fn test_undo_choice() {
+        let mut state =
+            ConflictResolverState::new(vec![make_entry(1, "checkbox", "checked", "unchecked")]);
+        state.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(state.undecided_count(), 0);
+        state.handle_key(key(KeyCode::Char('u')));
+        assert_eq!(state.undecided_count(), 1);
+        assert!(state.decisions().is_empty());
+    }
+}
@@ -70,6 +70,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
                         "rejected" => Color::Red,
                         "blocks" => Color::Red,
                         "enables" => Color::Cyan,
+                        "supersedes" => Color::Gray,
                         _ => Color::DarkGray,
                     };
 
@@ -327,6 +327,27 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
         ]));
     }
 
+    // Tags
+    let tags = app.get_tags(node);
+    if !tags.is_empty() {
+        let mut spans = vec![Span::styled("Tags: ", Style::default().fg(Color::DarkGray))];
+        for tag in tags {
+            spans.push(Span::styled(
+                format!("#{} ", tag),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Custom fields
+    for (name, value) in App::get_fields(node) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", name), Style::default().fg(Color::DarkGray)),
+            Span::styled(value, Style::default().fg(Color::White)),
+        ]));
+    }
+
     lines.push(Line::from(""));
 
     // Action hints
@@ -1,5 +1,6 @@
 //! TUI Views
 
+pub mod conflicts;
 pub mod dag;
 pub mod detail;
 pub mod roadmap;
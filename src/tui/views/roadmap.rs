@@ -12,7 +12,10 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
 
+use chrono::NaiveDate;
+
 use crate::db::RoadmapItem;
+use crate::roadmap::{is_overdue, parse_due_date};
 
 // =============================================================================
 // Model - State
@@ -43,6 +46,8 @@ pub struct RoadmapState {
     pub show_detail: bool,
     /// GitHub repo for issue URLs (e.g., "owner/repo")
     pub github_repo: Option<String>,
+    /// Only show items from this roadmap file (`deciduous tui --file`)
+    pub file_filter: Option<String>,
 }
 
 // =============================================================================
@@ -112,6 +117,37 @@ pub fn filter_by_mode(items: &[RoadmapItem], mode: RoadmapViewMode) -> Vec<Roadm
     }
 }
 
+/// Filter items to those imported from one roadmap file
+///
+/// Items imported before files were tracked count as ROADMAP.md.
+pub fn filter_by_file(items: &[RoadmapItem], file: Option<&str>) -> Vec<RoadmapItem> {
+    match file {
+        None => items.to_vec(),
+        Some(file) => items
+            .iter()
+            .filter(|item| item.roadmap_path.as_deref().unwrap_or("ROADMAP.md") == file)
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Nesting depth of an item: how many parent items it sits under
+pub fn item_depth(item: &RoadmapItem, items: &[RoadmapItem]) -> usize {
+    let mut depth = 0;
+    let mut parent = item.parent_id;
+    // Bounded in case of a cycle in hand-edited data
+    while let Some(id) = parent.filter(|_| depth < items.len()) {
+        match items.iter().find(|i| i.id == id) {
+            Some(p) => {
+                depth += 1;
+                parent = p.parent_id;
+            }
+            None => break,
+        }
+    }
+    depth
+}
+
 /// Group items by section
 pub fn group_by_section(items: &[RoadmapItem]) -> Vec<(String, Vec<&RoadmapItem>)> {
     use std::collections::BTreeMap;
@@ -185,6 +221,14 @@ pub fn count_by_status(items: &[RoadmapItem]) -> (usize, usize) {
     (active, complete)
 }
 
+/// Check if an open item is past its section's due date
+pub fn is_item_overdue(item: &RoadmapItem, today: NaiveDate) -> bool {
+    item.due_date
+        .as_deref()
+        .and_then(parse_due_date)
+        .is_some_and(|due| is_overdue(due, is_item_complete(item), today))
+}
+
 // =============================================================================
 // Update - State Mutations (Methods)
 // =============================================================================
@@ -205,9 +249,16 @@ impl RoadmapState {
         self.github_repo = repo;
     }
 
-    /// Refresh visible items based on current mode
+    /// Show only items from one roadmap file (None shows all files)
+    pub fn set_file_filter(&mut self, file: Option<String>) {
+        self.file_filter = file;
+        self.refresh_visible();
+    }
+
+    /// Refresh visible items based on current mode and file filter
     fn refresh_visible(&mut self) {
-        self.visible_items = filter_by_mode(&self.all_items, self.view_mode);
+        let items = filter_by_file(&self.all_items, self.file_filter.as_deref());
+        self.visible_items = filter_by_mode(&items, self.view_mode);
         self.selected_index = clamp_selection(self.selected_index, self.visible_items.len());
     }
 
@@ -279,7 +330,15 @@ impl RoadmapState {
 
     /// Get counts for status bar
     pub fn get_counts(&self) -> (usize, usize) {
-        count_by_status(&self.all_items)
+        count_by_status(&filter_by_file(
+            &self.all_items,
+            self.file_filter.as_deref(),
+        ))
+    }
+
+    /// Nesting depth of an item among all loaded items
+    pub fn depth_of(&self, item: &RoadmapItem) -> usize {
+        item_depth(item, &self.all_items)
     }
 
     /// Get GitHub issue URL for selected item (if it has an issue and github_repo is configured)
@@ -395,6 +454,7 @@ fn draw_list(frame: &mut Frame, state: &RoadmapState, area: Rect) {
                 let is_selected = idx == state.selected_index;
                 list_items.push(render_item_grouped(
                     item,
+                    state.depth_of(item),
                     display_idx,
                     is_selected,
                     inner_area.width,
@@ -425,7 +485,10 @@ fn draw_tab_bar(
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
-        .title(" Roadmap ");
+        .title(match state.file_filter {
+            Some(ref file) => format!(" Roadmap: {} ", file),
+            None => " Roadmap ".to_string(),
+        });
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -496,6 +559,7 @@ fn render_section_header(section: &str, _width: u16) -> ListItem<'static> {
 /// Render a roadmap item (grouped under section, no section name shown)
 fn render_item_grouped(
     item: &RoadmapItem,
+    depth: usize,
     index: usize,
     is_selected: bool,
     width: u16,
@@ -503,8 +567,9 @@ fn render_item_grouped(
     // Line 1: indent, number, checkbox, title
     let mut line1_spans = vec![];
 
-    // Indent (2 spaces)
-    line1_spans.push(Span::raw("  "));
+    // Indent (2 spaces, plus 2 per nesting level)
+    let indent = "  ".repeat(depth + 1);
+    line1_spans.push(Span::raw(indent.clone()));
 
     // Row number
     let num_style = if is_selected {
@@ -524,7 +589,7 @@ fn render_item_grouped(
     line1_spans.push(Span::raw(" "));
 
     // Title
-    let max_title_len = (width as usize).saturating_sub(12);
+    let max_title_len = (width as usize).saturating_sub(10 + indent.len());
     let title = truncate_str(&item.title, max_title_len);
     let title_style = if is_selected {
         Style::default().fg(Color::White).bold()
@@ -539,7 +604,7 @@ fn render_item_grouped(
 
     // Line 2: status indicators (indented)
     let mut line2_spans = vec![];
-    line2_spans.push(Span::raw("       ")); // Indent to align under title
+    line2_spans.push(Span::raw(format!("{}     ", indent))); // Indent to align under title
 
     // Issue link
     if let Some(issue_num) = item.github_issue_number {
@@ -567,6 +632,27 @@ fn render_item_grouped(
         ));
     }
 
+    // Milestone and due date, red once overdue
+    if let Some(ref milestone) = item.milestone {
+        line2_spans.push(Span::styled(
+            format!(" @{}", truncate_str(milestone, 20)),
+            Style::default().fg(Color::Blue),
+        ));
+    }
+    if let Some(ref due) = item.due_date {
+        if is_item_overdue(item, chrono::Local::now().date_naive()) {
+            line2_spans.push(Span::styled(
+                format!(" due {} (overdue)", due),
+                Style::default().fg(Color::Red).bold(),
+            ));
+        } else {
+            line2_spans.push(Span::styled(
+                format!(" due {}", due),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+
     let style = if is_selected {
         Style::default().bg(Color::Rgb(40, 40, 50))
     } else {
@@ -711,6 +797,31 @@ fn draw_detail(frame: &mut Frame, state: &RoadmapState, area: Rect) {
         ]));
     }
 
+    // Roadmap file
+    if let Some(ref file) = item.roadmap_path {
+        lines.push(Line::from(vec![
+            Span::styled("File: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(file.as_str(), Style::default().fg(Color::White)),
+        ]));
+    }
+
+    // Milestone
+    if let Some(ref milestone) = item.milestone {
+        let mut spans = vec![
+            Span::styled("Milestone: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(milestone.as_str(), Style::default().fg(Color::Blue)),
+        ];
+        if let Some(ref due) = item.due_date {
+            let due_style = if is_item_overdue(item, chrono::Local::now().date_naive()) {
+                Style::default().fg(Color::Red).bold()
+            } else {
+                Style::default().fg(Color::White)
+            };
+            spans.push(Span::styled(format!(" (due {})", due), due_style));
+        }
+        lines.push(Line::from(spans));
+    }
+
     // Description
     if let Some(ref desc) = item.description {
         lines.push(Line::from(""));
@@ -893,6 +1004,11 @@ mod tests {
             created_at: "2024-01-01".to_string(),
             updated_at: "2024-01-01".to_string(),
             last_synced_at: None,
+            synced_checkbox_state: None,
+            milestone: None,
+            due_date: None,
+            goal_change_id: None,
+            roadmap_path: None,
         }
     }
 
@@ -1037,6 +1153,25 @@ mod tests {
         assert_eq!(move_down(0, 0), 0);
     }
 
+    #[test]
+    fn test_is_item_overdue() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+        let mut open = make_item(1, "Open", "unchecked", None, None);
+        assert!(!is_item_overdue(&open, today));
+
+        open.due_date = Some("2024-06-01".to_string());
+        assert!(is_item_overdue(&open, today));
+        open.due_date = Some("2024-06-15".to_string());
+        assert!(!is_item_overdue(&open, today));
+        open.due_date = Some("soon".to_string());
+        assert!(!is_item_overdue(&open, today));
+
+        // Done items are never overdue
+        let mut done = make_item(2, "Done", "checked", None, None);
+        done.due_date = Some("2024-06-01".to_string());
+        assert!(!is_item_overdue(&done, today));
+    }
+
     #[test]
     fn test_clamp_selection() {
         assert_eq!(clamp_selection(5, 10), 5);
@@ -1117,4 +1252,43 @@ mod tests {
         state.selected_index = 0;
         assert!(state.selected_issue_url().is_none());
     }
+
+    #[test]
+    fn test_filter_by_file() {
+        let mut api = make_item(1, "Api task", "unchecked", None, None);
+        api.roadmap_path = Some("packages/api/ROADMAP.md".to_string());
+        let mut root = make_item(2, "Root task", "unchecked", None, None);
+        root.roadmap_path = Some("ROADMAP.md".to_string());
+        let legacy = make_item(3, "Legacy task", "unchecked", None, None);
+        let items = vec![api, root, legacy];
+
+        assert_eq!(filter_by_file(&items, None).len(), 3);
+        let titles = |file| -> Vec<String> {
+            filter_by_file(&items, Some(file))
+                .into_iter()
+                .map(|i| i.title)
+                .collect()
+        };
+        assert_eq!(titles("packages/api/ROADMAP.md"), vec!["Api task"]);
+        assert_eq!(titles("ROADMAP.md"), vec!["Root task", "Legacy task"]);
+
+        let mut state = RoadmapState::new();
+        state.set_items(items);
+        state.set_file_filter(Some("packages/api/ROADMAP.md".to_string()));
+        assert_eq!(state.visible_items().len(), 1);
+        assert_eq!(state.get_counts(), (1, 0));
+    }
+
+    #[test]
+    fn test_item_depth() {
+        let parent = make_item(1, "Parser", "unchecked", None, None);
+        let mut child = make_item(2, "Tests", "unchecked", None, None);
+        child.parent_id = Some(1);
+        let mut grandchild = make_item(3, "Fixtures", "unchecked", None, None);
+        grandchild.parent_id = Some(2);
+        let items = vec![parent, child, grandchild];
+
+        let depths: Vec<usize> = items.iter().map(|i| item_depth(i, &items)).collect();
+        assert_eq!(depths, vec![0, 1, 2]);
+    }
 }
@@ -430,6 +430,420 @@ fn test_command_log() {
     assert!(!out.is_empty());
 }
 
+// =============================================================================
+// Undo/Redo Tests
+// =============================================================================
+
+#[test]
+fn test_undo_redo_add() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    run_deciduous(&["add", "goal", "Kept Goal"], &db_path);
+    run_deciduous(&["add", "goal", "Mistaken Goal"], &db_path);
+
+    // Dry run reports but leaves the node in place
+    let output = run_deciduous(&["undo", "--dry-run"], &db_path);
+    assert!(
+        output.status.success(),
+        "undo --dry-run failed: {}",
+        stderr(&output)
+    );
+    assert!(stdout(&output).contains("Would undo"));
+    assert!(stdout(&run_deciduous(&["nodes"], &db_path)).contains("Mistaken Goal"));
+
+    let output = run_deciduous(&["undo"], &db_path);
+    assert!(output.status.success(), "undo failed: {}", stderr(&output));
+    let out = stdout(&run_deciduous(&["nodes"], &db_path));
+    assert!(out.contains("Kept Goal"));
+    assert!(!out.contains("Mistaken Goal"));
+
+    let output = run_deciduous(&["redo"], &db_path);
+    assert!(output.status.success(), "redo failed: {}", stderr(&output));
+    assert!(stdout(&run_deciduous(&["nodes"], &db_path)).contains("Mistaken Goal"));
+}
+
+// =============================================================================
+// Merge Tests
+// =============================================================================
+
+#[test]
+fn test_merge_duplicate_nodes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    run_deciduous(&["add", "goal", "Add auth"], &db_path);
+    run_deciduous(&["add", "goal", "Add authentication"], &db_path);
+    run_deciduous(&["add", "action", "Login form"], &db_path);
+    run_deciduous(&["link", "2", "3"], &db_path);
+
+    let output = run_deciduous(&["merge", "1", "2"], &db_path);
+    assert!(output.status.success(), "merge failed: {}", stderr(&output));
+    assert!(stdout(&output).contains("1 edge(s) rewired"));
+
+    let out = stdout(&run_deciduous(&["nodes"], &db_path));
+    assert!(out.contains("Add auth"));
+    assert!(!out.contains("Add authentication"));
+
+    // Merging a node into itself is rejected
+    let output = run_deciduous(&["merge", "1", "1"], &db_path);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_audit_find_duplicates_dry_run() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    run_deciduous(&["add", "goal", "Add auth"], &db_path);
+    run_deciduous(&["add", "goal", "Add authentication"], &db_path);
+    run_deciduous(&["add", "goal", "Speed up CI"], &db_path);
+
+    let output = run_deciduous(&["audit", "--find-duplicates", "--dry-run"], &db_path);
+    assert!(
+        output.status.success(),
+        "audit --find-duplicates failed: {}",
+        stderr(&output)
+    );
+    let out = stdout(&output);
+    assert!(out.contains("1 group(s)"));
+    assert!(out.contains("Add authentication"));
+    assert!(!out.contains("Speed up CI"));
+
+    // Dry run leaves all nodes in place
+    assert!(stdout(&run_deciduous(&["nodes"], &db_path)).contains("Add authentication"));
+}
+
+// =============================================================================
+// File Lookup Tests
+// =============================================================================
+
+#[test]
+fn test_nodes_by_file_and_files_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    run_deciduous(
+        &[
+            "add",
+            "action",
+            "Schema change",
+            "-f",
+            "src/db.rs,src/schema.rs",
+        ],
+        &db_path,
+    );
+    run_deciduous(
+        &["add", "action", "TUI tweak", "-f", "src/tui/app.rs"],
+        &db_path,
+    );
+
+    let output = run_deciduous(&["nodes", "--file", "src/db.rs"], &db_path);
+    assert!(
+        output.status.success(),
+        "nodes --file failed: {}",
+        stderr(&output)
+    );
+    let out = stdout(&output);
+    assert!(out.contains("Schema change"));
+    assert!(!out.contains("TUI tweak"));
+
+    let out = stdout(&run_deciduous(&["nodes", "--file", "src/tui/*"], &db_path));
+    assert!(out.contains("TUI tweak"));
+    assert!(!out.contains("Schema change"));
+
+    let output = run_deciduous(&["files", "1"], &db_path);
+    assert!(output.status.success(), "files failed: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("src/db.rs"));
+    assert!(out.contains("src/schema.rs"));
+}
+
+#[test]
+fn test_tags_add_filter_and_list() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    let output = run_deciduous(
+        &["add", "goal", "Harden auth", "--tags", "security,Q3"],
+        &db_path,
+    );
+    assert!(output.status.success(), "add failed: {}", stderr(&output));
+    assert!(stdout(&output).contains("[tags: security, Q3]"));
+    run_deciduous(&["add", "goal", "Speed up sync"], &db_path);
+
+    let output = run_deciduous(&["tag", "add", "2", "perf"], &db_path);
+    assert!(
+        output.status.success(),
+        "tag add failed: {}",
+        stderr(&output)
+    );
+
+    let out = stdout(&run_deciduous(&["nodes", "--tag", "security"], &db_path));
+    assert!(out.contains("Harden auth"));
+    assert!(out.contains("#q3"));
+    assert!(!out.contains("Speed up sync"));
+
+    let out = stdout(&run_deciduous(&["tag", "ls"], &db_path));
+    assert!(out.contains("perf"));
+    assert!(out.contains("security"));
+
+    run_deciduous(&["tag", "rm", "1", "security"], &db_path);
+    let out = stdout(&run_deciduous(&["tag", "ls", "1"], &db_path));
+    assert_eq!(out.trim(), "q3");
+
+    // Invalid tags are rejected before the node is created
+    let output = run_deciduous(&["add", "goal", "Bad", "--tags", "two words"], &db_path);
+    assert!(!output.status.success());
+    assert!(!stdout(&run_deciduous(&["nodes"], &db_path)).contains("Bad"));
+}
+
+#[test]
+fn test_custom_fields_add_and_edit() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+    let config_dir = temp_dir.path().join(".deciduous");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[fields.action]\ncost_estimate = \"number\"\nrisk = \"enum[low,med,high]\"\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_deciduous"))
+            .args(args)
+            .env("DECIDUOUS_DB_PATH", &db_path)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute deciduous")
+    };
+
+    let output = run(&["add", "action", "Cache tokens", "--field", "risk=high"]);
+    assert!(output.status.success(), "add failed: {}", stderr(&output));
+
+    // Values are checked against the declared type
+    let output = run(&["add", "action", "Bad", "--field", "risk=extreme"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not one of"));
+    let output = run(&["add", "goal", "Bad", "--field", "risk=low"]);
+    assert!(!output.status.success());
+
+    let output = run(&["edit", "1", "--field", "cost_estimate=3", "--unset", "risk"]);
+    assert!(output.status.success(), "edit failed: {}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("cost_estimate"));
+    assert!(!out.contains("risk"));
+
+    let graph: serde_json::Value = serde_json::from_str(&stdout(&run(&["graph"]))).unwrap();
+    let meta: serde_json::Value =
+        serde_json::from_str(graph["nodes"][0]["metadata_json"].as_str().unwrap()).unwrap();
+    assert_eq!(meta["fields"]["cost_estimate"], 3.0);
+}
+
+#[test]
+fn test_signed_patches() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let key = temp_dir.path().join("id_ed25519");
+    let keygen = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+        .arg(&key)
+        .status();
+    if !keygen.map(|s| s.success()).unwrap_or(false) {
+        return; // ssh-keygen not available
+    }
+    let public = std::fs::read_to_string(key.with_extension("pub")).unwrap();
+
+    let config_dir = temp_dir.path().join(".deciduous");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("allowed_signers"),
+        format!("alice@example.com {}", public),
+    )
+    .unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "[patches]\nsigning_key = {:?}\nsigner = \"alice@example.com\"\nrequire_signed = true\n",
+            key.display().to_string()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str], db: &str| {
+        Command::new(env!("CARGO_BIN_EXE_deciduous"))
+            .args(args)
+            .env("DECIDUOUS_DB_PATH", temp_dir.path().join(db))
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute deciduous")
+    };
+
+    run(&["add", "goal", "Signed goal"], "alice.db");
+    let output = run(&["diff", "export", "-o", "signed.json"], "alice.db");
+    assert!(
+        output.status.success(),
+        "export failed: {}",
+        stderr(&output)
+    );
+    assert!(stdout(&output).contains("signed by alice@example.com"));
+
+    let output = run(&["diff", "verify", "signed.json"], "alice.db");
+    assert!(
+        output.status.success(),
+        "verify failed: {}",
+        stdout(&output)
+    );
+
+    // Tampering breaks the signature
+    let patch_path = temp_dir.path().join("signed.json");
+    let signed = std::fs::read_to_string(&patch_path).unwrap();
+    std::fs::write(
+        temp_dir.path().join("tampered.json"),
+        signed.replace("Signed goal", "Forged goal"),
+    )
+    .unwrap();
+    let output = run(&["diff", "verify", "tampered.json"], "alice.db");
+    assert!(!output.status.success());
+    let output = run(&["diff", "apply", "tampered.json"], "bob.db");
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Rejected"));
+
+    // Unsigned patches are refused when require_signed is set
+    let mut unsigned: serde_json::Value = serde_json::from_str(&signed).unwrap();
+    unsigned.as_object_mut().unwrap().remove("signature");
+    std::fs::write(temp_dir.path().join("unsigned.json"), unsigned.to_string()).unwrap();
+    let output = run(&["diff", "apply", "unsigned.json"], "bob.db");
+    assert!(!output.status.success());
+
+    // The signer is recorded on the nodes the patch introduced
+    let output = run(&["diff", "apply", "signed.json"], "bob.db");
+    assert!(output.status.success(), "apply failed: {}", stderr(&output));
+    let graph: serde_json::Value =
+        serde_json::from_str(&stdout(&run(&["graph"], "bob.db"))).unwrap();
+    assert_eq!(graph["nodes"].as_array().unwrap().len(), 1);
+    assert!(graph["nodes"][0]["metadata_json"]
+        .as_str()
+        .unwrap()
+        .contains("\"signed_by\":\"alice@example.com\""));
+}
+
+#[test]
+fn test_diff_squash_and_gc() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_deciduous"))
+            .args(args)
+            .env("DECIDUOUS_DB_PATH", dir.join("test.db"))
+            .current_dir(dir)
+            .output()
+            .expect("Failed to execute deciduous")
+    };
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git")
+    };
+
+    run(&["add", "goal", "Squash patches"]);
+    run(&["add", "action", "Write squash"]);
+    run(&["link", "1", "2"]);
+    run(&["diff", "export", "-n", "1", "-o", "patches/a.json"]);
+    run(&["diff", "export", "-n", "1-2", "-o", "patches/b.json"]);
+
+    let output = run(&[
+        "diff",
+        "squash",
+        "patches/a.json",
+        "patches/b.json",
+        "-o",
+        "patches/all.json",
+        "--delete",
+    ]);
+    assert!(
+        output.status.success(),
+        "squash failed: {}",
+        stderr(&output)
+    );
+    assert!(stdout(&output).contains("2 nodes, 1 edges (1 duplicates dropped)"));
+    assert!(!dir.join("patches/a.json").exists());
+    let squashed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("patches/all.json")).unwrap())
+            .unwrap();
+    assert_eq!(squashed["nodes"].as_array().unwrap().len(), 2);
+
+    // Sync the graph onto main, then add a node the base branch doesn't have
+    git(&["init", "-q", "-b", "main"]);
+    run(&["sync"]);
+    git(&["add", "docs/graph-data.json"]);
+    git(&["commit", "-q", "-m", "Sync graph"]);
+    run(&["add", "outcome", "Unsynced"]);
+    run(&["diff", "export", "-n", "3", "-o", "patches/new.json"]);
+
+    let output = run(&["diff", "gc", "--base", "main", "-p", "patches", "--dry-run"]);
+    assert!(output.status.success(), "gc failed: {}", stderr(&output));
+    assert!(dir.join("patches/all.json").exists());
+
+    let output = run(&["diff", "gc", "--base", "main", "-p", "patches"]);
+    assert!(output.status.success(), "gc failed: {}", stderr(&output));
+    assert!(!dir.join("patches/all.json").exists());
+    assert!(dir.join("patches/new.json").exists());
+}
+
+// =============================================================================
+// Lint Tests
+// =============================================================================
+
+#[test]
+fn test_audit_lint_json() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+
+    run_deciduous(&["add", "decision", "Pick a cache"], &db_path);
+
+    // Hygiene findings are warnings by default, so the run still passes
+    let output = Command::new(env!("CARGO_BIN_EXE_deciduous"))
+        .args(["audit", "--lint", "--json"])
+        .env("DECIDUOUS_DB_PATH", &db_path)
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute deciduous");
+    assert!(output.status.success(), "lint failed: {}", stderr(&output));
+
+    let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(report["errors"], 0);
+    assert_eq!(report["findings"][0]["rule"], "decision-without-options");
+    assert_eq!(report["findings"][0]["level"], "warn");
+}
+
+#[test]
+fn test_audit_lint_config_fails_build() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+    let config_dir = temp_dir.path().join(".deciduous");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[lint.rules]\naction-without-commit = \"error\"\n",
+    )
+    .unwrap();
+
+    run_deciduous(&["add", "action", "Uncommitted work"], &db_path);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_deciduous"))
+        .args(["audit", "--lint"])
+        .env("DECIDUOUS_DB_PATH", &db_path)
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute deciduous");
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("action-without-commit"));
+}
+
 // =============================================================================
 // Error Handling Tests
 // =============================================================================
@@ -38,6 +38,7 @@ export const EDGE_COLORS: Record<EdgeType, string> = {
   rejected: '#ef4444',   // Red
   blocks: '#f97316',     // Orange
   enables: '#06b6d4',    // Cyan
+  supersedes: '#a3a3a3', // Neutral gray
 };
 
 /**